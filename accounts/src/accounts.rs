@@ -12,6 +12,7 @@ use keys::Address;
 use transaction::{Transaction, TransactionFlags};
 use tree_primitives::accounts_proof::AccountsProof;
 use tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
+use tree_primitives::accounts_tree_node::AccountsTreeNode;
 
 use crate::tree::AccountsTree;
 
@@ -58,6 +59,47 @@ impl Accounts {
         }
     }
 
+    /// Returns all accounts as consecutive chunks of the given size.
+    pub fn get_chunks(
+        &self,
+        size: usize,
+        txn: &db::Transaction,
+    ) -> Option<Vec<AccountsTreeChunk<Account>>> {
+        let mut chunks = Vec::new();
+        let mut start = String::new();
+
+        // Continue as long as there are accounts after the last chunk.
+        while !self
+            .tree
+            .get_terminal_nodes(txn, &start.parse().ok()?, 1)?
+            .is_empty()
+        {
+            let chunk = self.tree.get_chunk(txn, &start, size)?;
+            start = chunk.tail().prefix().to_string();
+            chunks.push(chunk);
+        }
+
+        Some(chunks)
+    }
+
+    /// Replaces all accounts with the accounts in the given chunks. The chunks must have been
+    /// verified before, and the caller has to check the resulting root hash.
+    pub fn reinitialize(&self, txn: &mut WriteTransaction, chunks: &[AccountsTreeChunk<Account>]) {
+        self.tree.clear(txn);
+
+        for chunk in chunks {
+            for node in chunk.terminal_nodes() {
+                if let AccountsTreeNode::TerminalNode { prefix, account } = node {
+                    if let Some(address) = prefix.to_address() {
+                        self.tree.put_batch(txn, &address, account.clone());
+                    }
+                }
+            }
+        }
+
+        self.tree.finalize_batch(txn);
+    }
+
     pub fn get_accounts_proof(
         &self,
         txn: &db::Transaction,
@@ -2,6 +2,7 @@ use std::marker::PhantomData;
 use std::str::FromStr;
 
 use account::AccountsTreeLeave;
+use database::cursor::{ReadCursor, WriteCursor};
 use database::{Database, Environment, Transaction, WriteTransaction};
 use hash::{Blake2bHash, Hash};
 use keys::Address;
@@ -39,6 +40,25 @@ impl<A: AccountsTreeLeave> AccountsTree<A> {
         tree
     }
 
+    /// Removes all accounts from the tree, leaving only an empty root node.
+    pub fn clear(&self, txn: &mut WriteTransaction) {
+        let mut cursor = txn.write_cursor(&self.db);
+        let mut pos: Option<(Vec<u8>, AccountsTreeNode<A>)> = cursor.first();
+
+        while pos.is_some() {
+            cursor.remove();
+            pos = cursor.next();
+        }
+        drop(cursor);
+
+        let root = AddressNibbles::empty();
+        txn.put_reserve(
+            &self.db,
+            &root,
+            &AccountsTreeNode::<A>::new_branch(root.clone(), NO_CHILDREN),
+        );
+    }
+
     pub fn put(&self, txn: &mut WriteTransaction, address: &Address, account: A) {
         self.put_batch(txn, address, account);
         self.finalize_batch(txn);
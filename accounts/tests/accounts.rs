@@ -579,3 +579,44 @@ fn can_generate_accounts_proof() {
         proof2.get_account(&address_recipient_2).unwrap()
     );
 }
+
+#[test]
+fn it_can_reinitialize_from_chunks() {
+    let env = VolatileEnvironment::new(10).unwrap();
+    let accounts = Accounts::new(env.clone());
+
+    let rewards: Vec<Inherent> = (1..=5u8)
+        .map(|i| Inherent {
+            ty: InherentType::Reward,
+            target: Address::from([i; Address::SIZE]),
+            value: Coin::from_u64_unchecked(1000 * i as u64),
+            data: vec![],
+        })
+        .collect();
+
+    let mut txn = WriteTransaction::new(&env);
+    assert!(accounts.commit(&mut txn, &[], &rewards, 1, 1).is_ok());
+    txn.commit();
+
+    let txn = ReadTransaction::new(&env);
+    let mut chunks = accounts.get_chunks(2, &txn).unwrap();
+    assert_eq!(chunks.len(), 3);
+    for chunk in chunks.iter_mut() {
+        assert!(chunk.verify());
+        assert_eq!(chunk.root(), accounts.hash(Some(&txn)));
+    }
+
+    // Rebuild the tree from the chunks in another environment.
+    let env2 = VolatileEnvironment::new(10).unwrap();
+    let accounts2 = Accounts::new(env2.clone());
+
+    let mut txn2 = WriteTransaction::new(&env2);
+    accounts2.reinitialize(&mut txn2, &chunks);
+    txn2.commit();
+
+    assert_eq!(accounts2.hash(None), accounts.hash(Some(&txn)));
+    assert_eq!(
+        accounts2.get(&Address::from([3u8; Address::SIZE]), None),
+        accounts.get(&Address::from([3u8; Address::SIZE]), Some(&txn))
+    );
+}
@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use nimiq_account::{Account, Inherent};
+use nimiq_accounts::Accounts;
+use nimiq_database::cursor::{ReadCursor, WriteCursor};
+use nimiq_database::{Database, Environment, ReadTransaction, Transaction, WriteTransaction};
+use nimiq_keys::Address;
+use nimiq_transaction::Transaction as BlockchainTransaction;
+
+/// A database of the states of accounts before they were changed by a block. In archive mode, it
+/// is used to answer queries for the state of an account at any block after archive mode was
+/// enabled.
+///
+/// The state of an account after a given block is the state stored for the first later block that
+/// changed the account. If no later block changed the account, it is the current state.
+///
+/// Whole account states are stored, not the changes to them. For the staking contract, which
+/// holds all validators and stakers and is changed by most blocks, this means a full copy per
+/// block.
+#[derive(Debug)]
+pub struct ArchiveStore {
+    env: Environment,
+    // A database of account states indexed by the address of the account concatenated with the
+    // big-endian byte representation of the number of the block that changed the state.
+    account_db: Database,
+}
+
+impl ArchiveStore {
+    const ACCOUNT_DB_NAME: &'static str = "AccountHistory";
+
+    pub fn new(env: Environment) -> Self {
+        let account_db = env.open_database(Self::ACCOUNT_DB_NAME.to_string());
+        ArchiveStore { env, account_db }
+    }
+
+    /// Stores the states of all accounts that are changed by the given transactions and inherents.
+    /// This must be called before they are committed to the accounts tree.
+    pub fn put_block(
+        &self,
+        txn: &mut WriteTransaction,
+        accounts: &Accounts,
+        transactions: &[BlockchainTransaction],
+        inherents: &[Inherent],
+        block_number: u32,
+    ) {
+        for address in Self::affected_addresses(transactions, inherents) {
+            let account = accounts.get(address, Some(&txn));
+            txn.put_reserve(&self.account_db, &key(address, block_number), &account);
+        }
+    }
+
+    /// Removes the states stored for a block that is reverted.
+    pub fn remove_block(
+        &self,
+        txn: &mut WriteTransaction,
+        transactions: &[BlockchainTransaction],
+        inherents: &[Inherent],
+        block_number: u32,
+    ) {
+        for address in Self::affected_addresses(transactions, inherents) {
+            txn.remove(&self.account_db, &key(address, block_number));
+        }
+    }
+
+    /// Returns the state of an account after the block at `block_number`. Returns None if no block
+    /// after it changed the account, so its state is the current one.
+    pub fn get_account(
+        &self,
+        address: &Address,
+        block_number: u32,
+        txn_option: Option<&Transaction>,
+    ) -> Option<Account> {
+        let read_txn: ReadTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = ReadTransaction::new(&self.env);
+                &read_txn
+            }
+        };
+
+        // Seek to the first state stored for a later block. If there is none for this address, the
+        // cursor will be on the states of another address.
+        let mut cursor = txn.cursor(&self.account_db);
+        let (stored_key, account) = cursor
+            .seek_range_key::<Vec<u8>, Account>(&key(address, block_number.saturating_add(1)))?;
+
+        if stored_key.starts_with(address.as_bytes()) {
+            Some(account)
+        } else {
+            None
+        }
+    }
+
+    /// Removes all stored states.
+    pub fn clear(&self, txn: &mut WriteTransaction) {
+        let mut cursor = txn.write_cursor(&self.account_db);
+        let mut pos: Option<(Vec<u8>, Account)> = cursor.first();
+
+        while pos.is_some() {
+            cursor.remove();
+            pos = cursor.next();
+        }
+    }
+
+    /// Returns the addresses of the accounts that can be changed by the given transactions and
+    /// inherents.
+    fn affected_addresses<'a>(
+        transactions: &'a [BlockchainTransaction],
+        inherents: &'a [Inherent],
+    ) -> HashSet<&'a Address> {
+        transactions
+            .iter()
+            .flat_map(|tx| vec![&tx.sender, &tx.recipient])
+            .chain(inherents.iter().map(|inherent| &inherent.target))
+            .collect()
+    }
+}
+
+/// Transforms an address and a block number into the corresponding database key.
+fn key(address: &Address, block_number: u32) -> Vec<u8> {
+    let mut bytes = address.as_bytes().to_vec();
+    bytes.extend_from_slice(&block_number.to_be_bytes());
+    bytes
+}
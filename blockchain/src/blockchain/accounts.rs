@@ -5,27 +5,43 @@ use nimiq_primitives::policy;
 
 use crate::blockchain_state::BlockchainState;
 use crate::history_store::ExtendedTransaction;
-use crate::{Blockchain, PushError};
+use crate::{Blockchain, PushError, StakingEventKind};
 
 /// Implements methods to handle the accounts.
 impl Blockchain {
-    /// Updates the accounts given a block.
+    /// Updates the accounts given a block. Returns the changes to the staking contract caused by
+    /// the block.
     pub fn commit_accounts(
         &self,
         state: &BlockchainState,
         block: &Block,
         first_view_number: u32,
         txn: &mut WriteTransaction,
-    ) -> Result<(), PushError> {
+    ) -> Result<Vec<StakingEventKind>, PushError> {
         // Get the accounts from the state.
         let accounts = &state.accounts;
 
         // Check the type of the block.
-        match block {
+        let staking_events = match block {
             Block::Macro(ref macro_block) => {
                 // Initialize a vector to store the inherents
                 let inherents = self.create_macro_block_inherents(state, &macro_block.header);
 
+                // Collect the staking events before the inherents change the staking contract.
+                let staking_events =
+                    self.staking_events_from_macro_block(accounts, &inherents, txn);
+
+                // Store the states of the accounts changed by the block, if in archive mode.
+                if let Some(ref archive_store) = self.archive_store {
+                    archive_store.put_block(
+                        txn,
+                        accounts,
+                        &[],
+                        &inherents,
+                        macro_block.header.block_number,
+                    );
+                }
+
                 // Commit block to AccountsTree and create the receipts.
                 let receipts = accounts.commit(
                     txn,
@@ -60,6 +76,13 @@ impl Blockchain {
                     policy::epoch_at(macro_block.header.block_number),
                     &ext_txs,
                 );
+
+                // Take a snapshot of the accounts tree at election blocks, if enabled.
+                if macro_block.is_election_block() {
+                    self.store_snapshot(txn, accounts, &macro_block.hash());
+                }
+
+                staking_events
             }
             Block::Micro(ref micro_block) => {
                 // Get the body of the block.
@@ -76,6 +99,17 @@ impl Blockchain {
                 let inherents =
                     self.create_slash_inherents(&body.fork_proofs, &view_changes, Some(txn));
 
+                // Store the states of the accounts changed by the block, if in archive mode.
+                if let Some(ref archive_store) = self.archive_store {
+                    archive_store.put_block(
+                        txn,
+                        accounts,
+                        &body.transactions,
+                        &inherents,
+                        micro_block.header.block_number,
+                    );
+                }
+
                 // Commit block to AccountsTree and create the receipts.
                 let receipts = accounts.commit(
                     txn,
@@ -95,6 +129,12 @@ impl Blockchain {
                 self.chain_store
                     .put_receipts(txn, micro_block.header.block_number, &receipts);
 
+                let staking_events = Self::staking_events_from_micro_block(
+                    &body.transactions,
+                    &inherents,
+                    &receipts,
+                );
+
                 // Store the transactions and the inherents into the History tree.
                 let ext_txs = ExtendedTransaction::from(
                     micro_block.header.block_number,
@@ -108,21 +148,24 @@ impl Blockchain {
                     policy::epoch_at(micro_block.header.block_number),
                     &ext_txs,
                 );
+
+                staking_events
             }
-        }
+        };
 
-        Ok(())
+        Ok(staking_events)
     }
 
     /// Reverts the accounts given a block. This only applies to micro blocks, since macro blocks
-    /// are final and can't be reverted.
+    /// are final and can't be reverted. Returns the changes to the staking contract that were
+    /// caused by the block and are now undone.
     pub(crate) fn revert_accounts(
         &self,
         accounts: &Accounts,
         txn: &mut WriteTransaction,
         micro_block: &MicroBlock,
         prev_view_number: u32,
-    ) -> Result<(), PushError> {
+    ) -> Result<Vec<StakingEventKind>, PushError> {
         assert_eq!(
             micro_block.header.state_root,
             accounts.hash(Some(&txn)),
@@ -160,6 +203,19 @@ impl Blockchain {
             panic!("Failed to revert - {}", e);
         }
 
+        let staking_events =
+            Self::staking_events_from_micro_block(&body.transactions, &inherents, &receipts);
+
+        // Remove the states of the accounts changed by the block, if in archive mode.
+        if let Some(ref archive_store) = self.archive_store {
+            archive_store.remove_block(
+                txn,
+                &body.transactions,
+                &inherents,
+                micro_block.header.block_number,
+            );
+        }
+
         // Remove the transactions from the History tree. For this you only need to calculate the
         // number of transactions that you want to remove.
         let num_txs = body.transactions.len() + inherents.len();
@@ -170,6 +226,6 @@ impl Blockchain {
             num_txs,
         );
 
-        Ok(())
+        Ok(staking_events)
     }
 }
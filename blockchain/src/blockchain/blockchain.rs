@@ -21,14 +21,16 @@ use nimiq_utils::time::OffsetTime;
 use nimiq_vrf::VrfSeed;
 use std::str::FromStr;
 
+use crate::archive_store::ArchiveStore;
 use crate::blockchain_state::BlockchainState;
 use crate::chain_info::ChainInfo;
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::chain_store::ChainStore;
 use crate::history_store::HistoryStore;
+use crate::snapshot_store::SnapshotStore;
 use crate::reward::genesis_parameters;
-use crate::{BlockchainError, BlockchainEvent, ForkEvent};
+use crate::{BlockchainError, BlockchainEvent, ForkEvent, StakingEvent};
 
 /// The Blockchain struct. It stores all information of the blockchain. It is the main data
 /// structure in this crate.
@@ -43,10 +45,18 @@ pub struct Blockchain {
     pub notifier: RwLock<Notifier<'static, BlockchainEvent>>,
     // The fork notifier processes fork events.
     pub fork_notifier: RwLock<Notifier<'static, ForkEvent>>,
+    // The staking notifier processes changes to the staking contract.
+    pub staking_notifier: RwLock<Notifier<'static, StakingEvent>>,
     // The chain store is a database containing all of the chain infos, blocks and receipts.
     pub chain_store: Arc<ChainStore>,
     // The history store is a database containing all of the history trees and transactions.
     pub history_store: Arc<HistoryStore>,
+    // The archive store is a database containing the past states of the accounts. It is only
+    // present if the blockchain runs in archive mode.
+    pub(crate) archive_store: Option<ArchiveStore>,
+    // The snapshot store is a database containing the accounts tree at the latest election block.
+    // It is only present if the blockchain keeps snapshots to serve them to other nodes.
+    pub(crate) snapshot_store: Option<SnapshotStore>,
     // The current state of the blockchain.
     pub state: RwLock<BlockchainState>,
     // A write lock for the blockchain. Guarantees that only one thread writes to it at a time.
@@ -152,6 +162,52 @@ impl Blockchain {
         })
     }
 
+    /// Enables or disables archive mode. In archive mode, the past states of the accounts are kept
+    /// for all blocks from the one at which archive mode was first enabled. Disabling archive mode
+    /// discards this history.
+    pub fn set_archive(&mut self, archive: bool) {
+        let mut txn = WriteTransaction::new(&self.env);
+
+        if archive {
+            let archive_store = ArchiveStore::new(self.env.clone());
+
+            if self.chain_store.get_archive_start(Some(&txn)).is_none() {
+                // Discard any states left over from a previous time archive mode was enabled.
+                archive_store.clear(&mut txn);
+
+                let block_number = self.state.get_mut().main_chain.head.block_number();
+                self.chain_store.set_archive_start(&mut txn, block_number);
+
+                info!("Archive mode enabled at block #{}", block_number);
+            }
+
+            self.archive_store = Some(archive_store);
+        } else {
+            self.chain_store.remove_archive_start(&mut txn);
+            self.archive_store = None;
+        }
+
+        txn.commit();
+    }
+
+    /// Enables or disables keeping snapshots of the accounts tree. If enabled, the accounts tree is
+    /// stored at every election block, so that it can be served to nodes that sync from a
+    /// snapshot. The first snapshot is taken at the next election block.
+    pub fn set_snapshots(&mut self, enabled: bool) {
+        if enabled {
+            self.snapshot_store = Some(SnapshotStore::new(self.env.clone()));
+        } else {
+            let mut txn = WriteTransaction::new(&self.env);
+            if let Some(ref snapshot_store) = self.snapshot_store {
+                snapshot_store.clear(&mut txn);
+            }
+            self.chain_store.remove_snapshot_block(&mut txn);
+            txn.commit();
+
+            self.snapshot_store = None;
+        }
+    }
+
     /// Loads a blockchain from given inputs.
     fn load(
         env: Environment,
@@ -187,6 +243,16 @@ impl Blockchain {
             return Err(BlockchainError::InconsistentState);
         }
 
+        // Rebuild the address index of the history store if it was created by an older version.
+        let mut txn = WriteTransaction::new(&env);
+        if chain_store.get_history_index_version(Some(&txn)) < HistoryStore::ADDRESS_INDEX_VERSION {
+            info!("Rebuilding the address index of the history store");
+            history_store
+                .rebuild_address_index(&mut txn, policy::epoch_at(main_chain.head.block_number()));
+            chain_store.set_history_index_version(&mut txn, HistoryStore::ADDRESS_INDEX_VERSION);
+        }
+        txn.commit();
+
         // Load macro chain from store.
         let macro_chain_info = chain_store
             .get_chain_info_at(
@@ -242,8 +308,11 @@ impl Blockchain {
             time,
             notifier: RwLock::new(Notifier::new()),
             fork_notifier: RwLock::new(Notifier::new()),
+            staking_notifier: RwLock::new(Notifier::new()),
             chain_store,
             history_store,
+            archive_store: None,
+            snapshot_store: None,
             state: RwLock::new(BlockchainState {
                 accounts,
                 main_chain,
@@ -290,6 +359,7 @@ impl Blockchain {
         // Store genesis block.
         chain_store.put_chain_info(&mut txn, &head_hash, &main_chain, true);
         chain_store.set_head(&mut txn, &head_hash);
+        chain_store.set_history_index_version(&mut txn, HistoryStore::ADDRESS_INDEX_VERSION);
         txn.commit();
 
         Ok(Blockchain {
@@ -298,8 +368,11 @@ impl Blockchain {
             time,
             notifier: RwLock::new(Notifier::new()),
             fork_notifier: RwLock::new(Notifier::new()),
+            staking_notifier: RwLock::new(Notifier::new()),
             chain_store,
             history_store,
+            archive_store: None,
+            snapshot_store: None,
             state: RwLock::new(BlockchainState {
                 accounts,
                 macro_info: main_chain.clone(),
@@ -0,0 +1,167 @@
+use std::io::{self, Read, Write};
+
+use beserial::{Deserialize, Serialize, SerializingError};
+use nimiq_block::Block;
+use nimiq_database::ReadTransaction;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_primitives::networks::NetworkId;
+
+use crate::{AbstractBlockchain, Blockchain, ChainFileError, PushResult};
+
+/// The header of a chain file. It is followed by the blocks, each prefixed by its serialized size
+/// as a u32.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainFileHeader {
+    // The version of the file format.
+    pub version: u8,
+    // The network the blocks belong to.
+    pub network_id: NetworkId,
+    // The hash of the genesis block the blocks build on.
+    pub genesis_hash: Blake2bHash,
+}
+
+impl ChainFileHeader {
+    pub const VERSION: u8 = 1;
+}
+
+/// Implements methods to export the chain to a file and import it again.
+impl Blockchain {
+    /// The number of blocks that are fetched from the chain store at a time when exporting.
+    const EXPORT_BATCH_SIZE: u32 = 128;
+
+    /// Writes the main chain blocks from `first_block` up to `last_block` (or the head if None) to
+    /// the given writer. The genesis block is never exported. Returns the number of exported
+    /// blocks.
+    pub fn export_chain<W: Write>(
+        &self,
+        writer: &mut W,
+        first_block: u32,
+        last_block: Option<u32>,
+    ) -> Result<u32, ChainFileError> {
+        let txn = ReadTransaction::new(&self.env);
+
+        let genesis_hash = self
+            .chain_store
+            .get_block_at(0, false, Some(&txn))
+            .ok_or(ChainFileError::BlockNotFound(0))?
+            .hash();
+
+        let header = ChainFileHeader {
+            version: ChainFileHeader::VERSION,
+            network_id: self.network_id,
+            genesis_hash,
+        };
+        header.serialize(writer)?;
+
+        // Start at the predecessor of the first block, since the blocks following it are fetched.
+        let start_block = first_block.saturating_sub(1);
+        let mut hash = self
+            .chain_store
+            .get_block_at(start_block, false, Some(&txn))
+            .ok_or(ChainFileError::BlockNotFound(start_block))?
+            .hash();
+
+        let last_block = last_block.unwrap_or_else(|| self.block_number());
+        let mut num_blocks = 0;
+
+        'outer: loop {
+            let blocks = self.chain_store.get_blocks_forward(
+                &hash,
+                Self::EXPORT_BATCH_SIZE,
+                true,
+                Some(&txn),
+            );
+
+            if blocks.is_empty() {
+                break;
+            }
+
+            for block in blocks {
+                if block.block_number() > last_block {
+                    break 'outer;
+                }
+
+                hash = block.hash();
+
+                (block.serialized_size() as u32).serialize(writer)?;
+                block.serialize(writer)?;
+                num_blocks += 1;
+            }
+        }
+
+        writer.flush()?;
+
+        Ok(num_blocks)
+    }
+
+    /// Reads a chain file from the given reader and pushes its blocks to the blockchain, fully
+    /// verifying them. The callback is called with the number of each pushed block and the result.
+    /// Returns the number of blocks that extended the chain.
+    pub fn import_chain<R: Read, F: FnMut(u32, &PushResult)>(
+        &self,
+        reader: &mut R,
+        mut progress: F,
+    ) -> Result<u32, ChainFileError> {
+        let header: ChainFileHeader = Deserialize::deserialize(reader)?;
+
+        if header.version != ChainFileHeader::VERSION {
+            return Err(ChainFileError::UnsupportedVersion(header.version));
+        }
+
+        if header.network_id != self.network_id {
+            return Err(ChainFileError::WrongNetwork(header.network_id));
+        }
+
+        let genesis_hash = self
+            .chain_store
+            .get_block_at(0, false, None)
+            .ok_or(ChainFileError::BlockNotFound(0))?
+            .hash();
+
+        if header.genesis_hash != genesis_hash {
+            return Err(ChainFileError::WrongGenesis(header.genesis_hash));
+        }
+
+        let mut num_blocks = 0;
+
+        while let Some(block) = Self::read_block(reader)? {
+            let block_number = block.block_number();
+
+            let result = self
+                .push(block)
+                .map_err(|e| ChainFileError::Push(block_number, e))?;
+
+            if result == PushResult::Extended || result == PushResult::Rebranched {
+                num_blocks += 1;
+            }
+
+            progress(block_number, &result);
+        }
+
+        Ok(num_blocks)
+    }
+
+    /// Reads the next block from a chain file. Returns None at the end of the file.
+    fn read_block<R: Read>(reader: &mut R) -> Result<Option<Block>, ChainFileError> {
+        let size: u32 = match Deserialize::deserialize(reader) {
+            Ok(size) => size,
+            Err(SerializingError::IoError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // Read the block into a buffer first, so that a corrupted size can't make us read past it.
+        let mut buffer = Vec::new();
+        reader
+            .by_ref()
+            .take(u64::from(size))
+            .read_to_end(&mut buffer)?;
+
+        if buffer.len() != size as usize {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(Some(Deserialize::deserialize_from_vec(&buffer)?))
+    }
+}
@@ -160,19 +160,7 @@ impl Blockchain {
 
         // Calculate the cumulative transaction fees for the current batch. This is necessary to
         // create the chain info for the block.
-        let mut cum_tx_fees = Coin::ZERO;
-
-        let current_batch = policy::batch_at(block.block_number());
-
-        for i in (0..ext_txs.len()).rev() {
-            if policy::batch_at(ext_txs[i].block_number) != current_batch {
-                break;
-            }
-
-            if let ExtTxData::Basic(tx) = &ext_txs[i].data {
-                cum_tx_fees += tx.fee;
-            }
-        }
+        let cum_tx_fees = Self::batch_tx_fees(ext_txs, block.block_number());
 
         // Create the chain info for the current block and store it.
         let chain_info = ChainInfo {
@@ -287,6 +275,17 @@ impl Blockchain {
 
         // Update the accounts tree, one block at a time.
         for i in 0..block_numbers.len() {
+            // Store the states of the accounts changed by the block, if in archive mode.
+            if let Some(ref archive_store) = self.archive_store {
+                archive_store.put_block(
+                    &mut txn,
+                    &state.accounts,
+                    &block_transactions[i],
+                    &block_inherents[i],
+                    block_numbers[i],
+                );
+            }
+
             // Commit block to AccountsTree and create the receipts.
             let receipts = state.accounts.commit(
                 &mut txn,
@@ -323,6 +322,11 @@ impl Blockchain {
         // Check if this block is an election block.
         let is_election_block = macro_block.is_election_block();
 
+        // Take a snapshot of the accounts tree at election blocks, if enabled.
+        if is_election_block {
+            self.store_snapshot(&mut txn, &state.accounts, &block_hash);
+        }
+
         // Get a write transaction to the current state.
         drop(state);
         let mut state = self.state.write();
@@ -359,6 +363,26 @@ impl Blockchain {
         Ok(PushResult::Extended)
     }
 
+    /// Calculates the cumulative transaction fees of the batch of the given block from the
+    /// extended transactions of its epoch.
+    pub(crate) fn batch_tx_fees(ext_txs: &[ExtendedTransaction], block_number: u32) -> Coin {
+        let mut cum_tx_fees = Coin::ZERO;
+
+        let current_batch = policy::batch_at(block_number);
+
+        for ext_tx in ext_txs.iter().rev() {
+            if policy::batch_at(ext_tx.block_number) != current_batch {
+                break;
+            }
+
+            if let ExtTxData::Basic(tx) = &ext_tx.data {
+                cum_tx_fees += tx.fee;
+            }
+        }
+
+        cum_tx_fees
+    }
+
     /// Reverts a given number of micro blocks from the blockchain.
     fn revert_blocks(
         &self,
@@ -1,9 +1,12 @@
 pub mod accounts;
 #[allow(clippy::module_inception)]
 pub mod blockchain;
+pub mod chain_file;
 pub mod history_sync;
 pub mod inherents;
 pub mod push;
 pub mod slots;
+pub mod snapshot;
+pub mod staking_events;
 pub mod verify;
 pub mod wrappers;
@@ -9,7 +9,7 @@ use crate::blockchain_state::BlockchainState;
 use crate::chain_info::ChainInfo;
 use crate::{
     AbstractBlockchain, Blockchain, BlockchainEvent, ChainOrdering, ForkEvent, PushError,
-    PushResult,
+    PushResult, StakingEvent, StakingEventKind,
 };
 
 /// Implements methods to push blocks into the chain. This is used when the node has already synced
@@ -188,15 +188,20 @@ impl Blockchain {
 
         let state = self.state.read();
 
-        if let Err(e) = self.check_and_commit(
+        let staking_events = match self.check_and_commit(
             &state,
             &chain_info.head,
             prev_info.head.next_view_number(),
             &mut txn,
         ) {
-            txn.abort();
-            return Err(e);
-        }
+            Ok(staking_events) => staking_events,
+            Err(e) => {
+                txn.abort();
+                return Err(e);
+            }
+        };
+
+        let block_number = chain_info.head.block_number();
 
         drop(state);
 
@@ -247,18 +252,20 @@ impl Blockchain {
             if is_election_block {
                 self.notifier
                     .read()
-                    .notify(BlockchainEvent::EpochFinalized(block_hash));
+                    .notify(BlockchainEvent::EpochFinalized(block_hash.clone()));
             } else {
                 self.notifier
                     .read()
-                    .notify(BlockchainEvent::Finalized(block_hash));
+                    .notify(BlockchainEvent::Finalized(block_hash.clone()));
             }
         } else {
             self.notifier
                 .read()
-                .notify(BlockchainEvent::Extended(block_hash));
+                .notify(BlockchainEvent::Extended(block_hash.clone()));
         }
 
+        self.notify_staking_events(&block_hash, block_number, staking_events, false);
+
         Ok(PushResult::Extended)
     }
 
@@ -320,6 +327,8 @@ impl Blockchain {
 
         let state = self.state.upgradable_read();
 
+        let mut reverted_staking_events = vec![];
+
         current = (state.head_hash.clone(), state.main_chain.clone());
 
         // Check if ancestor is in current batch.
@@ -343,13 +352,19 @@ impl Blockchain {
                         .get_chain_info(&prev_hash, true, Some(&read_txn))
                         .expect("Corrupted store: Failed to find main chain predecessor while rebranching");
 
-                    self.revert_accounts(
+                    let events = self.revert_accounts(
                         &state.accounts,
                         &mut write_txn,
                         &micro_block,
                         prev_info.head.view_number(),
                     )?;
 
+                    reverted_staking_events.push((
+                        current.0.clone(),
+                        micro_block.header.block_number,
+                        events,
+                    ));
+
                     assert_eq!(
                         prev_info.head.state_root(),
                         &state.accounts.hash(Some(&write_txn)),
@@ -368,33 +383,42 @@ impl Blockchain {
 
         let mut fork_iter = fork_chain.iter().rev();
 
+        let mut staking_events = vec![];
+
         while let Some(fork_block) = fork_iter.next() {
             match fork_block.1.head {
                 Block::Macro(_) => unreachable!(),
                 Block::Micro(ref micro_block) => {
-                    if let Err(e) = self.check_and_commit(
+                    match self.check_and_commit(
                         &state,
                         &fork_block.1.head,
                         prev_view_number,
                         &mut write_txn,
                     ) {
-                        warn!("Failed to apply fork block while rebranching - {:?}", e);
-                        write_txn.abort();
-
-                        // Delete invalid fork blocks from store.
-                        let mut write_txn = WriteTransaction::new(&self.env);
-
-                        for block in vec![fork_block].into_iter().chain(fork_iter) {
-                            self.chain_store.remove_chain_info(
-                                &mut write_txn,
-                                &block.0,
-                                micro_block.header.block_number,
-                            )
+                        Ok(events) => staking_events.push((
+                            fork_block.0.clone(),
+                            micro_block.header.block_number,
+                            events,
+                        )),
+                        Err(e) => {
+                            warn!("Failed to apply fork block while rebranching - {:?}", e);
+                            write_txn.abort();
+
+                            // Delete invalid fork blocks from store.
+                            let mut write_txn = WriteTransaction::new(&self.env);
+
+                            for block in vec![fork_block].into_iter().chain(fork_iter) {
+                                self.chain_store.remove_chain_info(
+                                    &mut write_txn,
+                                    &block.0,
+                                    micro_block.header.block_number,
+                                )
+                            }
+
+                            write_txn.commit();
+
+                            return Err(PushError::InvalidFork);
                         }
-
-                        write_txn.commit();
-
-                        return Err(PushError::InvalidFork);
                     }
 
                     prev_view_number = fork_block.1.head.next_view_number();
@@ -473,16 +497,48 @@ impl Blockchain {
 
         self.notifier.read().notify(event);
 
+        // Retract the staking events of the reverted blocks, in the reverse order in which they
+        // were emitted, before emitting the ones of the adopted blocks.
+        for (block_hash, block_number, mut events) in reverted_staking_events {
+            events.reverse();
+            self.notify_staking_events(&block_hash, block_number, events, true);
+        }
+
+        for (block_hash, block_number, events) in staking_events {
+            self.notify_staking_events(&block_hash, block_number, events, false);
+        }
+
         Ok(PushResult::Rebranched)
     }
 
+    /// Notifies the staking notifier of the staking events caused by a block that was added to or
+    /// reverted from the main chain.
+    fn notify_staking_events(
+        &self,
+        block_hash: &Blake2bHash,
+        block_number: u32,
+        events: Vec<StakingEventKind>,
+        reverted: bool,
+    ) {
+        let notifier = self.staking_notifier.read();
+
+        for kind in events {
+            notifier.notify(StakingEvent {
+                block_hash: block_hash.clone(),
+                block_number,
+                reverted,
+                kind,
+            });
+        }
+    }
+
     fn check_and_commit(
         &self,
         state: &BlockchainState,
         block: &Block,
         first_view_number: u32,
         txn: &mut WriteTransaction,
-    ) -> Result<(), PushError> {
+    ) -> Result<Vec<StakingEventKind>, PushError> {
         // Check transactions against replay attacks. This is only necessary for micro blocks.
         if block.is_micro() {
             let transactions = block.transactions();
@@ -498,12 +554,15 @@ impl Blockchain {
         }
 
         // Commit block to AccountsTree.
-        if let Err(e) = self.commit_accounts(&state, &block, first_view_number, txn) {
-            warn!("Rejecting block - commit failed: {:?}", e);
-            #[cfg(feature = "metrics")]
-            self.metrics.note_invalid_block();
-            return Err(e);
-        }
+        let staking_events = match self.commit_accounts(&state, &block, first_view_number, txn) {
+            Ok(staking_events) => staking_events,
+            Err(e) => {
+                warn!("Rejecting block - commit failed: {:?}", e);
+                #[cfg(feature = "metrics")]
+                self.metrics.note_invalid_block();
+                return Err(e);
+            }
+        };
 
         // Verify the state against the block.
         if let Err(e) = self.verify_block_state(&state, &block, Some(&txn)) {
@@ -511,6 +570,6 @@ impl Blockchain {
             return Err(e);
         }
 
-        Ok(())
+        Ok(staking_events)
     }
 }
@@ -0,0 +1,524 @@
+use std::io::{Read, Write};
+
+use beserial::{Deserialize, Serialize};
+use nimiq_account::Account;
+use nimiq_accounts::Accounts;
+use nimiq_block::{Block, BlockError, MacroBlock};
+use nimiq_database::{ReadTransaction, WriteTransaction};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_primitives::coin::Coin;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_primitives::policy;
+use nimiq_tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
+
+use crate::chain_info::ChainInfo;
+use crate::history_store::{ExtendedTransaction, HistoryStore};
+use crate::{
+    AbstractBlockchain, Blockchain, BlockchainEvent, ChainFileError, PushError, PushResult,
+};
+
+/// The header of an accounts snapshot file. It is followed by the election blocks from the
+/// genesis block up to the block of the snapshot, the histories of the epochs in the transaction
+/// validity window before that block and the chunks of the accounts tree at that block. The
+/// blocks, the histories, the extended transactions of each history and the chunks are each
+/// prefixed by their number as a u32.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFileHeader {
+    // The version of the file format.
+    pub version: u8,
+    // The network the snapshot belongs to.
+    pub network_id: NetworkId,
+    // The hash of the genesis block the election blocks build on.
+    pub genesis_hash: Blake2bHash,
+}
+
+impl SnapshotFileHeader {
+    pub const VERSION: u8 = 1;
+}
+
+/// Implements methods to create, serve and apply snapshots of the accounts tree at election
+/// blocks.
+impl Blockchain {
+    /// The number of accounts in a chunk of a snapshot.
+    pub const SNAPSHOT_CHUNK_SIZE: usize = 256;
+
+    /// Returns the election block of the stored snapshot and its number of chunks. Returns None if
+    /// the blockchain doesn't keep snapshots or none was taken yet.
+    pub fn get_snapshot(&self) -> Option<(MacroBlock, u32)> {
+        let snapshot_store = self.snapshot_store.as_ref()?;
+        let txn = ReadTransaction::new(&self.env);
+
+        let block_hash = self.chain_store.get_snapshot_block(Some(&txn))?;
+        let block = self
+            .chain_store
+            .get_block(&block_hash, true, Some(&txn))?
+            .unwrap_macro();
+
+        Some((block, snapshot_store.num_chunks(Some(&txn))))
+    }
+
+    /// Returns a chunk of the stored snapshot, if it was taken at the block with the given hash.
+    pub fn get_snapshot_chunk(
+        &self,
+        block_hash: &Blake2bHash,
+        index: u32,
+    ) -> Option<AccountsTreeChunk<Account>> {
+        let snapshot_store = self.snapshot_store.as_ref()?;
+        let txn = ReadTransaction::new(&self.env);
+
+        if self.chain_store.get_snapshot_block(Some(&txn))? != *block_hash {
+            return None;
+        }
+
+        snapshot_store.get_chunk(index, Some(&txn))
+    }
+
+    /// Returns the first epoch whose history is part of a snapshot at the given block. The
+    /// histories cover the transaction validity window before the block, so that transactions
+    /// from that window can't be replayed after the snapshot is applied.
+    pub fn snapshot_history_start(block_number: u32) -> u32 {
+        let first_block = block_number
+            .saturating_sub(policy::TRANSACTION_VALIDITY_WINDOW)
+            .max(1);
+        policy::epoch_at(first_block)
+    }
+
+    /// Checks that a chunk of a snapshot is consistent and belongs to the accounts tree with the
+    /// given root.
+    pub fn verify_snapshot_chunk(
+        chunk: &mut AccountsTreeChunk<Account>,
+        state_root: &Blake2bHash,
+    ) -> bool {
+        // A chunk without proof has no tail node.
+        if chunk.proof.nodes().is_empty() || !chunk.tail().is_terminal() {
+            return false;
+        }
+
+        chunk.verify() && chunk.root() == *state_root
+    }
+
+    /// Replaces the stored snapshot with the current accounts tree, if the blockchain keeps
+    /// snapshots. This is called when an election block is committed.
+    pub(crate) fn store_snapshot(
+        &self,
+        txn: &mut WriteTransaction,
+        accounts: &Accounts,
+        block_hash: &Blake2bHash,
+    ) {
+        if let Some(ref snapshot_store) = self.snapshot_store {
+            let chunks = accounts
+                .get_chunks(Self::SNAPSHOT_CHUNK_SIZE, &txn)
+                .expect("Failed to split the accounts tree into chunks");
+
+            snapshot_store.put(txn, &chunks);
+            self.chain_store.set_snapshot_block(txn, block_hash);
+        }
+    }
+
+    /// Extends the chain with the given election blocks and replaces the accounts tree with the
+    /// given snapshot of the accounts tree at the last of them. This skips replaying the history
+    /// of these epochs. The head of the chain must be an election block and the given blocks must
+    /// be the election blocks following it. Blocks that are already known are skipped.
+    /// The justification of every block is verified against the validators of the previous
+    /// election block and the accounts tree is verified against the state root of the last block
+    /// before the snapshot is applied.
+    /// The histories are those of the epochs from `snapshot_history_start` up to the epoch of the
+    /// last block. Each of them is verified against the history root of its election block and
+    /// stored, so that transactions in the validity window can't be replayed. The transaction fees
+    /// of the batch of the last block, which are needed to reward the next batch, are computed
+    /// from its history.
+    pub fn push_snapshot(
+        &self,
+        election_blocks: Vec<MacroBlock>,
+        histories: Vec<Vec<ExtendedTransaction>>,
+        mut chunks: Vec<AccountsTreeChunk<Account>>,
+    ) -> Result<PushResult, PushError> {
+        // Only one push operation at a time.
+        let push_lock = self.push_lock.lock();
+
+        // Create a new database read transaction.
+        let read_txn = ReadTransaction::new(&self.env);
+
+        let snapshot_block = election_blocks.last().ok_or(PushError::InvalidSnapshot)?;
+
+        // Check if we already know the block of the snapshot.
+        if self
+            .chain_store
+            .get_chain_info(&snapshot_block.hash(), false, Some(&read_txn))
+            .is_some()
+        {
+            return Ok(PushResult::Known);
+        }
+
+        // The snapshot must contain the history of every epoch in the validity window.
+        let snapshot_number = snapshot_block.header.block_number;
+        let history_start = Self::snapshot_history_start(snapshot_number);
+
+        if histories.len() as u32 != policy::epoch_at(snapshot_number) + 1 - history_start {
+            warn!("Rejecting snapshot - wrong number of histories");
+            return Err(PushError::InvalidSnapshot);
+        }
+
+        // A snapshot replaces the whole state, so it can only be applied at an election block.
+        let head_hash = self.head_hash();
+        let mut prev_info = self
+            .chain_store
+            .get_chain_info(&head_hash, false, Some(&read_txn))
+            .expect("Couldn't fetch chain info for the head of the chain!");
+
+        if !policy::is_election_block_at(prev_info.head.block_number()) {
+            warn!("Rejecting snapshot - head of the chain is not an election block");
+            return Err(PushError::InvalidSnapshot);
+        }
+
+        // Skip the election blocks that we already have.
+        let new_blocks: Vec<MacroBlock> = election_blocks
+            .into_iter()
+            .filter(|block| block.header.block_number > prev_info.head.block_number())
+            .collect();
+
+        if new_blocks.is_empty() {
+            warn!("Rejecting snapshot - snapshot block is not a successor");
+            return Err(PushError::Orphan);
+        }
+
+        // Verify the election blocks.
+        let mut prev_hash = prev_info.head.hash();
+        let mut validators = self
+            .current_validators()
+            .expect("Couldn't fetch the validators of the current epoch!");
+
+        for block in &new_blocks {
+            if block.header.version != policy::VERSION {
+                warn!("Rejecting snapshot - block with wrong version");
+                return Err(PushError::InvalidBlock(BlockError::UnsupportedVersion));
+            }
+
+            if !block.is_election_block() || block.header.parent_election_hash != prev_hash {
+                warn!("Rejecting snapshot - election block without correct parent");
+                return Err(PushError::Orphan);
+            }
+
+            let body = block
+                .body
+                .as_ref()
+                .ok_or(PushError::InvalidBlock(BlockError::MissingBody))?;
+
+            if body.hash::<Blake2bHash>() != block.header.body_root {
+                warn!("Rejecting snapshot - Header body hash doesn't match real body hash");
+                return Err(PushError::InvalidBlock(BlockError::BodyHashMismatch));
+            }
+
+            let justification = block
+                .justification
+                .as_ref()
+                .ok_or(PushError::InvalidBlock(BlockError::NoJustification))?;
+
+            if !justification.verify(block.hash(), block.header.block_number, &validators) {
+                warn!("Rejecting snapshot - election block with bad justification");
+                return Err(PushError::InvalidBlock(BlockError::InvalidJustification));
+            }
+
+            let epoch = policy::epoch_at(block.header.block_number);
+
+            if epoch >= history_start {
+                let history_root = HistoryStore::get_root_from_ext_txs(
+                    &histories[(epoch - history_start) as usize],
+                )
+                .ok_or(PushError::InvalidBlock(BlockError::InvalidHistoryRoot))?;
+
+                if block.header.history_root != history_root {
+                    warn!("Rejecting snapshot - wrong history root");
+                    return Err(PushError::InvalidBlock(BlockError::InvalidHistoryRoot));
+                }
+            }
+
+            prev_hash = block.hash();
+            validators = block
+                .get_validators()
+                .ok_or(PushError::InvalidBlock(BlockError::InvalidValidators))?;
+        }
+
+        // Verify the chunks against the state root of the snapshot block.
+        let snapshot_block = new_blocks.last().unwrap();
+        let snapshot_hash = snapshot_block.hash();
+
+        for chunk in chunks.iter_mut() {
+            if !Self::verify_snapshot_chunk(chunk, &snapshot_block.header.state_root) {
+                warn!("Rejecting snapshot - invalid chunk");
+                return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
+            }
+        }
+
+        // Calculate the cumulative transaction fees for the batch of the snapshot block.
+        let cum_tx_fees = Self::batch_tx_fees(
+            histories.last().unwrap(),
+            snapshot_block.header.block_number,
+        );
+
+        // Create a new database write transaction.
+        let mut txn = WriteTransaction::new(&self.env);
+
+        // Store the chain infos of the election blocks. Only the fees of the last block are
+        // needed, since the history of the other epochs is skipped.
+        for (i, block) in new_blocks.iter().enumerate() {
+            let block_hash = block.hash();
+
+            let chain_info = ChainInfo {
+                on_main_chain: true,
+                main_chain_successor: None,
+                head: Block::Macro(block.clone()),
+                cum_tx_fees: if i == new_blocks.len() - 1 {
+                    cum_tx_fees
+                } else {
+                    Coin::ZERO
+                },
+            };
+
+            self.chain_store
+                .put_chain_info(&mut txn, &block_hash, &chain_info, true);
+
+            prev_info.main_chain_successor = Some(block_hash);
+
+            self.chain_store
+                .put_chain_info(&mut txn, &prev_info.head.hash(), &prev_info, false);
+
+            prev_info = chain_info;
+        }
+
+        self.chain_store.set_head(&mut txn, &snapshot_hash);
+
+        // Receipts are only needed to revert micro blocks after the head.
+        self.chain_store.clear_receipts(&mut txn);
+
+        // Store the histories of the new epochs in the validity window.
+        for block in &new_blocks {
+            let epoch = policy::epoch_at(block.header.block_number);
+
+            if epoch >= history_start {
+                self.history_store.add_to_history(
+                    &mut txn,
+                    epoch,
+                    &histories[(epoch - history_start) as usize],
+                );
+            }
+        }
+
+        // Get a read transaction to the current state.
+        let state = self.state.read();
+
+        // Replace the accounts tree and check that it matches the snapshot block.
+        state.accounts.reinitialize(&mut txn, &chunks);
+
+        if state.accounts.hash(Some(&txn)) != snapshot_block.header.state_root {
+            warn!("Rejecting snapshot - accounts hash mismatch");
+            txn.abort();
+            return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
+        }
+
+        // The past states of the accounts before the snapshot are unknown.
+        if let Some(ref archive_store) = self.archive_store {
+            archive_store.clear(&mut txn);
+            self.chain_store
+                .set_archive_start(&mut txn, snapshot_block.header.block_number);
+        }
+
+        if let Some(ref snapshot_store) = self.snapshot_store {
+            snapshot_store.put(&mut txn, &chunks);
+            self.chain_store
+                .set_snapshot_block(&mut txn, &snapshot_hash);
+        }
+
+        info!(
+            "Applied accounts snapshot at block #{}",
+            snapshot_block.header.block_number
+        );
+
+        // Get a write transaction to the current state.
+        drop(state);
+        let mut state = self.state.write();
+
+        // Update the blockchain state.
+        state.main_chain = prev_info.clone();
+        state.head_hash = snapshot_hash.clone();
+        state.macro_info = prev_info;
+        state.macro_head_hash = snapshot_hash.clone();
+        state.election_head = snapshot_block.clone();
+        state.election_head_hash = snapshot_hash.clone();
+        state.previous_slots = if new_blocks.len() > 1 {
+            new_blocks[new_blocks.len() - 2].get_validators()
+        } else {
+            state.current_slots.take()
+        };
+        state.current_slots = snapshot_block.get_validators();
+
+        // Give up database transactions and push lock before creating notifications.
+        txn.commit();
+        drop(state);
+        drop(push_lock);
+
+        self.notifier
+            .read()
+            .notify(BlockchainEvent::EpochFinalized(snapshot_hash));
+
+        Ok(PushResult::Extended)
+    }
+
+    /// Writes a snapshot file to the given writer. The snapshot is the stored one or, if the head
+    /// of the chain is an election block, the current accounts tree. Returns the number of chunks.
+    pub fn export_snapshot<W: Write>(&self, writer: &mut W) -> Result<u32, ChainFileError> {
+        // Hold the state lock, so that the accounts tree doesn't change while we read it.
+        let state = self.state.read();
+        let txn = ReadTransaction::new(&self.env);
+
+        let (snapshot_info, chunks) = match self.chain_store.get_snapshot_block(Some(&txn)) {
+            Some(block_hash) if self.snapshot_store.is_some() => {
+                let snapshot_store = self.snapshot_store.as_ref().unwrap();
+                let chain_info = self
+                    .chain_store
+                    .get_chain_info(&block_hash, true, Some(&txn))
+                    .ok_or(ChainFileError::NoSnapshot)?;
+                let chunks = (0..snapshot_store.num_chunks(Some(&txn)))
+                    .map(|i| snapshot_store.get_chunk(i, Some(&txn)))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(ChainFileError::NoSnapshot)?;
+                (chain_info, chunks)
+            }
+            _ if policy::is_election_block_at(state.main_chain.head.block_number()) => {
+                let chunks = state
+                    .accounts
+                    .get_chunks(Self::SNAPSHOT_CHUNK_SIZE, &txn)
+                    .ok_or(ChainFileError::NoSnapshot)?;
+                (state.main_chain.clone(), chunks)
+            }
+            _ => return Err(ChainFileError::NoSnapshot),
+        };
+
+        let genesis_hash = self
+            .chain_store
+            .get_block_at(0, false, Some(&txn))
+            .ok_or(ChainFileError::BlockNotFound(0))?
+            .hash();
+
+        let header = SnapshotFileHeader {
+            version: SnapshotFileHeader::VERSION,
+            network_id: self.network_id,
+            genesis_hash,
+        };
+        header.serialize(writer)?;
+
+        // Collect the election blocks, so that the importer can verify the block of the snapshot.
+        let snapshot_number = snapshot_info.head.block_number();
+        let num_epochs = policy::epoch_at(snapshot_number);
+        let mut election_blocks = Vec::new();
+
+        for epoch in 1..=num_epochs {
+            let block_number = policy::election_block_of(epoch);
+            let block = self
+                .chain_store
+                .get_block_at(block_number, true, Some(&txn))
+                .ok_or(ChainFileError::BlockNotFound(block_number))?;
+            election_blocks.push(block.unwrap_macro());
+        }
+
+        // Collect the histories of the epochs in the validity window. They might be missing if
+        // this node itself started from a snapshot.
+        let history_start = Self::snapshot_history_start(snapshot_number);
+        let mut histories = Vec::new();
+
+        for epoch in history_start..=num_epochs {
+            let history = self.history_store.get_epoch_transactions(epoch, Some(&txn));
+            let history_root = HistoryStore::get_root_from_ext_txs(&history);
+
+            if history_root.as_ref()
+                != Some(&election_blocks[epoch as usize - 1].header.history_root)
+            {
+                return Err(ChainFileError::MissingHistory(epoch));
+            }
+
+            histories.push(history);
+        }
+
+        num_epochs.serialize(writer)?;
+
+        for block in &election_blocks {
+            block.serialize(writer)?;
+        }
+
+        (histories.len() as u32).serialize(writer)?;
+
+        for history in &histories {
+            (history.len() as u32).serialize(writer)?;
+
+            for ext_tx in history {
+                ext_tx.serialize(writer)?;
+            }
+        }
+
+        (chunks.len() as u32).serialize(writer)?;
+
+        for chunk in &chunks {
+            chunk.serialize(writer)?;
+        }
+
+        writer.flush()?;
+
+        Ok(chunks.len() as u32)
+    }
+
+    /// Reads a snapshot file from the given reader and applies it using `push_snapshot`.
+    pub fn import_snapshot<R: Read>(&self, reader: &mut R) -> Result<PushResult, ChainFileError> {
+        let header: SnapshotFileHeader = Deserialize::deserialize(reader)?;
+
+        if header.version != SnapshotFileHeader::VERSION {
+            return Err(ChainFileError::UnsupportedVersion(header.version));
+        }
+
+        if header.network_id != self.network_id {
+            return Err(ChainFileError::WrongNetwork(header.network_id));
+        }
+
+        let genesis_hash = self
+            .chain_store
+            .get_block_at(0, false, None)
+            .ok_or(ChainFileError::BlockNotFound(0))?
+            .hash();
+
+        if header.genesis_hash != genesis_hash {
+            return Err(ChainFileError::WrongGenesis(header.genesis_hash));
+        }
+
+        let num_blocks: u32 = Deserialize::deserialize(reader)?;
+        let mut election_blocks = Vec::new();
+        for _ in 0..num_blocks {
+            let block: MacroBlock = Deserialize::deserialize(reader)?;
+            election_blocks.push(block);
+        }
+
+        let num_histories: u32 = Deserialize::deserialize(reader)?;
+        let mut histories = Vec::new();
+        for _ in 0..num_histories {
+            let num_ext_txs: u32 = Deserialize::deserialize(reader)?;
+            let mut history = Vec::new();
+            for _ in 0..num_ext_txs {
+                let ext_tx: ExtendedTransaction = Deserialize::deserialize(reader)?;
+                history.push(ext_tx);
+            }
+            histories.push(history);
+        }
+
+        let num_chunks: u32 = Deserialize::deserialize(reader)?;
+        let mut chunks = Vec::new();
+        for _ in 0..num_chunks {
+            let chunk: AccountsTreeChunk<Account> = Deserialize::deserialize(reader)?;
+            chunks.push(chunk);
+        }
+
+        let block_number = election_blocks
+            .last()
+            .map_or(0, |block| block.header.block_number);
+
+        self.push_snapshot(election_blocks, histories, chunks)
+            .map_err(|e| ChainFileError::Push(block_number, e))
+    }
+}
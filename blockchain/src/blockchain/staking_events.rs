@@ -0,0 +1,355 @@
+use beserial::Deserialize;
+use nimiq_account::staking_contract::SlashReceipt;
+use nimiq_account::{Account, Inherent, InherentType, Receipt, Receipts};
+use nimiq_accounts::Accounts;
+use nimiq_database::Transaction as DBTransaction;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_primitives::account::{AccountType, ValidatorId};
+use nimiq_primitives::coin::Coin;
+use nimiq_primitives::slots::SlashedSlot;
+use nimiq_transaction::account::staking_contract::{
+    IncomingStakingTransactionData, OutgoingStakingTransactionProof, SelfStakingTransactionData,
+};
+use nimiq_transaction::{SignatureProof, Transaction};
+
+use crate::Blockchain;
+
+/// An event emitted when a block on the main chain changes the state of the staking contract. When
+/// the block is reverted by a rebranch, the event is emitted again with `reverted` set, to retract
+/// the change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct StakingEvent {
+    /// The hash of the block that caused the change.
+    pub block_hash: Blake2bHash,
+    /// The number of the block that caused the change.
+    pub block_number: u32,
+    /// Whether the block was reverted, which undoes the change.
+    pub reverted: bool,
+    /// The change itself.
+    #[cfg_attr(feature = "serde-derive", serde(flatten))]
+    pub kind: StakingEventKind,
+}
+
+/// An enum representing the different changes to the staking contract. They are derived from the
+/// transactions, inherents and receipts applied by a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde-derive",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", tag = "type")
+)]
+pub enum StakingEventKind {
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorCreated {
+        validator_id: ValidatorId,
+        reward_address: Address,
+        balance: Coin,
+    },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorUpdated { validator_id: ValidatorId },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorRetired { validator_id: ValidatorId },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorReactivated { validator_id: ValidatorId },
+    /// The validator was parked because of a slash and has to send an unpark transaction.
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorParked { validator_id: ValidatorId },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorUnparked { validator_id: ValidatorId },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    ValidatorDropped {
+        validator_id: ValidatorId,
+        value: Coin,
+    },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    Staked {
+        validator_id: ValidatorId,
+        staker_address: Address,
+        value: Coin,
+    },
+    /// Inactive stake was withdrawn from the staking contract. Inactive stake isn't delegated to
+    /// any validator anymore, so this event has no validator id and is only matched by filtering
+    /// for the staker address.
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    Unstaked {
+        staker_address: Address,
+        value: Coin,
+    },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    StakeRetired {
+        validator_id: ValidatorId,
+        staker_address: Address,
+        value: Coin,
+    },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    StakeReactivated {
+        validator_id: ValidatorId,
+        staker_address: Address,
+        value: Coin,
+    },
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    StakeRededicated {
+        from_validator_id: ValidatorId,
+        to_validator_id: ValidatorId,
+        staker_address: Address,
+        value: Coin,
+    },
+    /// A slot of the validator was slashed, either because of a fork proof or a view change.
+    #[cfg_attr(feature = "serde-derive", serde(rename_all = "camelCase"))]
+    Slashed {
+        validator_id: ValidatorId,
+        slot: u16,
+        event_block: u32,
+        newly_disabled: bool,
+        newly_lost_rewards: bool,
+    },
+}
+
+impl StakingEventKind {
+    /// Returns the validators affected by this change. This is empty for `Unstaked`, which
+    /// doesn't affect any validator.
+    pub fn validator_ids(&self) -> Vec<&ValidatorId> {
+        match self {
+            StakingEventKind::ValidatorCreated { validator_id, .. }
+            | StakingEventKind::ValidatorUpdated { validator_id }
+            | StakingEventKind::ValidatorRetired { validator_id }
+            | StakingEventKind::ValidatorReactivated { validator_id }
+            | StakingEventKind::ValidatorParked { validator_id }
+            | StakingEventKind::ValidatorUnparked { validator_id }
+            | StakingEventKind::ValidatorDropped { validator_id, .. }
+            | StakingEventKind::Staked { validator_id, .. }
+            | StakingEventKind::StakeRetired { validator_id, .. }
+            | StakingEventKind::StakeReactivated { validator_id, .. }
+            | StakingEventKind::Slashed { validator_id, .. } => vec![validator_id],
+            StakingEventKind::StakeRededicated {
+                from_validator_id,
+                to_validator_id,
+                ..
+            } => vec![from_validator_id, to_validator_id],
+            StakingEventKind::Unstaked { .. } => vec![],
+        }
+    }
+
+    /// Returns the staker affected by this change, if any.
+    pub fn staker_address(&self) -> Option<&Address> {
+        match self {
+            StakingEventKind::Staked { staker_address, .. }
+            | StakingEventKind::Unstaked { staker_address, .. }
+            | StakingEventKind::StakeRetired { staker_address, .. }
+            | StakingEventKind::StakeReactivated { staker_address, .. }
+            | StakingEventKind::StakeRededicated { staker_address, .. } => Some(staker_address),
+            _ => None,
+        }
+    }
+}
+
+/// Implements methods to derive staking events from the contents of a block.
+impl Blockchain {
+    /// Returns the staking events caused by the transactions and slash inherents of a micro block.
+    /// The receipts must be the ones created when committing the block.
+    pub(crate) fn staking_events_from_micro_block(
+        transactions: &[Transaction],
+        inherents: &[Inherent],
+        receipts: &Receipts,
+    ) -> Vec<StakingEventKind> {
+        let mut events = vec![];
+
+        // Slash inherents are applied before the transactions. The receipts of inherents are
+        // indexed separately for the inherents applied before and after the transactions.
+        let pre_tx_inherents = inherents.iter().filter(|i| i.is_pre_transactions());
+
+        for (index, inherent) in pre_tx_inherents.enumerate() {
+            if inherent.ty != InherentType::Slash {
+                continue;
+            }
+
+            let slot: SlashedSlot = match Deserialize::deserialize_from_vec(&inherent.data) {
+                Ok(slot) => slot,
+                Err(_) => continue,
+            };
+
+            let receipt = receipts.receipts.iter().find_map(|receipt| match receipt {
+                Receipt::Inherent {
+                    index: i,
+                    data,
+                    pre_transactions: true,
+                } if *i as usize == index => SlashReceipt::deserialize_from_vec(data).ok(),
+                _ => None,
+            });
+
+            let receipt = match receipt {
+                Some(receipt) => receipt,
+                None => continue,
+            };
+
+            if receipt.newly_parked {
+                events.push(StakingEventKind::ValidatorParked {
+                    validator_id: slot.validator_id.clone(),
+                });
+            }
+
+            events.push(StakingEventKind::Slashed {
+                validator_id: slot.validator_id,
+                slot: slot.slot,
+                event_block: slot.event_block,
+                newly_disabled: receipt.newly_disabled,
+                newly_lost_rewards: receipt.newly_lost_rewards,
+            });
+        }
+
+        for transaction in transactions {
+            if let Some(event) = Self::staking_event_from_transaction(transaction) {
+                events.push(event);
+            }
+        }
+
+        events
+    }
+
+    /// Returns the staking events caused by the inherents of a macro block. This must be called
+    /// *before* the block is committed, since finalizing an epoch retires the validators that were
+    /// parked during the previous epoch and that information is gone afterwards.
+    pub(crate) fn staking_events_from_macro_block(
+        &self,
+        accounts: &Accounts,
+        inherents: &[Inherent],
+        txn: &DBTransaction,
+    ) -> Vec<StakingEventKind> {
+        if !inherents
+            .iter()
+            .any(|inherent| inherent.ty == InherentType::FinalizeEpoch)
+        {
+            return vec![];
+        }
+
+        let staking_contract_address = self
+            .staking_contract_address()
+            .expect("NetworkInfo doesn't have a staking contract address set!");
+
+        let staking_contract = match accounts.get(staking_contract_address, Some(txn)) {
+            Account::Staking(staking_contract) => staking_contract,
+            _ => unreachable!("Account type must be Staking."),
+        };
+
+        let mut validator_ids: Vec<&ValidatorId> = staking_contract
+            .previous_epoch_parking
+            .iter()
+            .filter(|validator_id| {
+                staking_contract
+                    .active_validators_by_id
+                    .contains_key(validator_id)
+            })
+            .collect();
+        validator_ids.sort();
+
+        validator_ids
+            .into_iter()
+            .map(|validator_id| StakingEventKind::ValidatorRetired {
+                validator_id: validator_id.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the staking event caused by a single transaction, if it interacts with the staking
+    /// contract. Since the transaction was already committed, it is assumed to be valid.
+    fn staking_event_from_transaction(transaction: &Transaction) -> Option<StakingEventKind> {
+        let incoming = transaction.recipient_type == AccountType::Staking;
+        let outgoing = transaction.sender_type == AccountType::Staking;
+
+        if incoming && outgoing && transaction.sender == transaction.recipient {
+            let staker_address = SignatureProof::deserialize_from_vec(&transaction.proof)
+                .ok()?
+                .compute_signer();
+
+            let event = match SelfStakingTransactionData::parse(transaction).ok()? {
+                SelfStakingTransactionData::RetireStake(validator_id) => {
+                    StakingEventKind::StakeRetired {
+                        validator_id,
+                        staker_address,
+                        value: transaction.value,
+                    }
+                }
+                SelfStakingTransactionData::ReactivateStake(validator_id) => {
+                    StakingEventKind::StakeReactivated {
+                        validator_id,
+                        staker_address,
+                        value: transaction.value,
+                    }
+                }
+                SelfStakingTransactionData::RededicateStake {
+                    from_validator_id,
+                    to_validator_id,
+                } => StakingEventKind::StakeRededicated {
+                    from_validator_id,
+                    to_validator_id,
+                    staker_address,
+                    value: transaction.value,
+                },
+            };
+
+            return Some(event);
+        }
+
+        if incoming {
+            let event = match IncomingStakingTransactionData::parse(transaction).ok()? {
+                IncomingStakingTransactionData::CreateValidator { reward_address, .. } => {
+                    // The validator id is derived from the hash of the creation transaction.
+                    let validator_id: ValidatorId =
+                        transaction.hash::<Blake2bHash>().as_slice()[0..20].into();
+                    StakingEventKind::ValidatorCreated {
+                        validator_id,
+                        reward_address,
+                        balance: transaction.value,
+                    }
+                }
+                IncomingStakingTransactionData::UpdateValidator { validator_id, .. } => {
+                    StakingEventKind::ValidatorUpdated { validator_id }
+                }
+                IncomingStakingTransactionData::RetireValidator { validator_id, .. } => {
+                    StakingEventKind::ValidatorRetired { validator_id }
+                }
+                IncomingStakingTransactionData::ReactivateValidator { validator_id, .. } => {
+                    StakingEventKind::ValidatorReactivated { validator_id }
+                }
+                IncomingStakingTransactionData::UnparkValidator { validator_id, .. } => {
+                    StakingEventKind::ValidatorUnparked { validator_id }
+                }
+                IncomingStakingTransactionData::Stake {
+                    validator_id,
+                    staker_address,
+                } => StakingEventKind::Staked {
+                    validator_id,
+                    staker_address: staker_address.unwrap_or_else(|| transaction.sender.clone()),
+                    value: transaction.value,
+                },
+            };
+
+            return Some(event);
+        }
+
+        if outgoing {
+            let event = match OutgoingStakingTransactionProof::parse(transaction).ok()? {
+                OutgoingStakingTransactionProof::DropValidator { validator_id, .. } => {
+                    StakingEventKind::ValidatorDropped {
+                        validator_id,
+                        value: transaction.value,
+                    }
+                }
+                OutgoingStakingTransactionProof::Unstake(proof) => StakingEventKind::Unstaked {
+                    staker_address: proof.compute_signer(),
+                    value: transaction.value,
+                },
+            };
+
+            return Some(event);
+        }
+
+        None
+    }
+}
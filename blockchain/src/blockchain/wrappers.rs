@@ -2,7 +2,7 @@ use parking_lot::{MutexGuard, RwLockReadGuard};
 
 use nimiq_account::{Account, StakingContract};
 use nimiq_block::Block;
-use nimiq_database::WriteTransaction;
+use nimiq_database::{ReadTransaction, WriteTransaction};
 use nimiq_genesis::NetworkInfo;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
@@ -84,6 +84,38 @@ impl Blockchain {
         self.state.read().accounts.get(address, None)
     }
 
+    /// Returns the state of an account after the block at the given block number. Returns None if
+    /// the blockchain is not in archive mode or the state at that block is not available.
+    pub fn get_account_at(&self, address: &Address, block_number: u32) -> Option<Account> {
+        let archive_store = self.archive_store.as_ref()?;
+        let state = self.state.read();
+        let txn = ReadTransaction::new(&self.env);
+
+        let archive_start = self.chain_store.get_archive_start(Some(&txn))?;
+        if block_number < archive_start || block_number > state.main_chain.head.block_number() {
+            return None;
+        }
+
+        Some(
+            archive_store
+                .get_account(address, block_number, Some(&txn))
+                .unwrap_or_else(|| state.accounts.get(address, Some(&txn))),
+        )
+    }
+
+    /// Returns the staking contract after the block at the given block number. Returns None if the
+    /// blockchain is not in archive mode or the state at that block is not available.
+    pub fn get_staking_contract_at(&self, block_number: u32) -> Option<StakingContract> {
+        let validator_registry = self
+            .staking_contract_address()
+            .expect("NetworkInfo doesn't have a staking contract address set!");
+
+        match self.get_account_at(validator_registry, block_number)? {
+            Account::Staking(x) => Some(x),
+            _ => unreachable!("Account type must be Staking."),
+        }
+    }
+
     /// Checks if we have seen some transaction with this hash inside the validity window. This is
     /// used to prevent replay attacks.
     pub fn contains_tx_in_validity_window(&self, tx_hash: &Blake2bHash) -> bool {
@@ -30,6 +30,9 @@ impl ChainStore {
     const RECEIPT_DB_NAME: &'static str = "Receipts";
 
     const HEAD_KEY: &'static str = "head";
+    const HISTORY_INDEX_VERSION_KEY: &'static str = "historyIndexVersion";
+    const ARCHIVE_START_KEY: &'static str = "archiveStart";
+    const SNAPSHOT_BLOCK_KEY: &'static str = "snapshotBlock";
 
     pub fn new(env: Environment) -> Self {
         let chain_db = env.open_database(Self::CHAIN_DB_NAME.to_string());
@@ -60,6 +63,59 @@ impl ChainStore {
         txn.put(&self.chain_db, ChainStore::HEAD_KEY, hash);
     }
 
+    /// Returns the version of the address index of the history store. Stores created before the
+    /// version was recorded have version 0.
+    pub fn get_history_index_version(&self, txn_option: Option<&Transaction>) -> u32 {
+        let version = match txn_option {
+            Some(txn) => txn.get(&self.chain_db, ChainStore::HISTORY_INDEX_VERSION_KEY),
+            None => ReadTransaction::new(&self.env)
+                .get(&self.chain_db, ChainStore::HISTORY_INDEX_VERSION_KEY),
+        };
+        version.unwrap_or(0)
+    }
+
+    pub fn set_history_index_version(&self, txn: &mut WriteTransaction, version: u32) {
+        txn.put(
+            &self.chain_db,
+            ChainStore::HISTORY_INDEX_VERSION_KEY,
+            &version,
+        );
+    }
+
+    pub fn get_archive_start(&self, txn_option: Option<&Transaction>) -> Option<u32> {
+        match txn_option {
+            Some(txn) => txn.get(&self.chain_db, ChainStore::ARCHIVE_START_KEY),
+            None => {
+                ReadTransaction::new(&self.env).get(&self.chain_db, ChainStore::ARCHIVE_START_KEY)
+            }
+        }
+    }
+
+    pub fn set_archive_start(&self, txn: &mut WriteTransaction, block_number: u32) {
+        txn.put(&self.chain_db, ChainStore::ARCHIVE_START_KEY, &block_number);
+    }
+
+    pub fn remove_archive_start(&self, txn: &mut WriteTransaction) {
+        txn.remove(&self.chain_db, ChainStore::ARCHIVE_START_KEY);
+    }
+
+    pub fn get_snapshot_block(&self, txn_option: Option<&Transaction>) -> Option<Blake2bHash> {
+        match txn_option {
+            Some(txn) => txn.get(&self.chain_db, ChainStore::SNAPSHOT_BLOCK_KEY),
+            None => {
+                ReadTransaction::new(&self.env).get(&self.chain_db, ChainStore::SNAPSHOT_BLOCK_KEY)
+            }
+        }
+    }
+
+    pub fn set_snapshot_block(&self, txn: &mut WriteTransaction, hash: &Blake2bHash) {
+        txn.put(&self.chain_db, ChainStore::SNAPSHOT_BLOCK_KEY, hash);
+    }
+
+    pub fn remove_snapshot_block(&self, txn: &mut WriteTransaction) {
+        txn.remove(&self.chain_db, ChainStore::SNAPSHOT_BLOCK_KEY);
+    }
+
     pub fn get_chain_info(
         &self,
         hash: &Blake2bHash,
@@ -1,5 +1,6 @@
 use thiserror::Error;
 
+use beserial::SerializingError;
 use nimiq_account::AccountError;
 use nimiq_block::{Block, BlockError, ForkProof};
 use nimiq_hash::Blake2bHash;
@@ -73,10 +74,36 @@ pub enum PushError {
     AccountsError(#[from] AccountError),
     #[error("Invalid fork")]
     InvalidFork,
+    #[error("Invalid snapshot")]
+    InvalidSnapshot,
     #[error("Blockchain error: {0}")]
     BlockchainError(#[from] BlockchainError),
 }
 
+/// An enum representing the errors that can occur when exporting or importing a chain file or an
+/// accounts snapshot.
+#[derive(Error, Debug)]
+pub enum ChainFileError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] SerializingError),
+    #[error("Unsupported chain file version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Chain file is for another network: {:?}", _0)]
+    WrongNetwork(NetworkId),
+    #[error("Chain file has another genesis block: {0}")]
+    WrongGenesis(Blake2bHash),
+    #[error("Block #{0} not found")]
+    BlockNotFound(u32),
+    #[error("Failed to push block #{0}: {1}")]
+    Push(u32, PushError),
+    #[error("No accounts snapshot available")]
+    NoSnapshot,
+    #[error("History of epoch {0} not available")]
+    MissingHistory(u32),
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Direction {
     Forward,
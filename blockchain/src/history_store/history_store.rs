@@ -17,7 +17,7 @@ use crate::history_store::mmr_store::MMRStore;
 use crate::history_store::ordered_hash::OrderedHash;
 use crate::history_store::{ExtendedTransaction, HistoryTreeChunk, HistoryTreeProof};
 use crate::ExtTxData;
-use nimiq_database::cursor::ReadCursor;
+use nimiq_database::cursor::{ReadCursor, WriteCursor};
 use nimiq_keys::Address;
 
 /// A struct that contains databases to store history trees (which are Merkle Mountain Ranges
@@ -38,10 +38,118 @@ pub struct HistoryStore {
     tx_hash_db: Database,
     // A database of all leaf hashes indexed by the block number where the transaction appears.
     block_db: Database,
-    // A database of all transaction (not inherents) hashes indexed by their sender address.
+    // A database of all transaction hashes indexed by their sender and recipient addresses, and of
+    // all inherent hashes indexed by their target address.
     address_db: Database,
 }
 
+/// Selects the transactions of an address by how they affect it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionDirection {
+    /// All transactions and inherents of the address.
+    Any,
+    /// Transactions that were received by the address and inherents that target it.
+    Incoming,
+    /// Transactions that were sent by the address.
+    Outgoing,
+}
+
+impl Default for TransactionDirection {
+    fn default() -> Self {
+        TransactionDirection::Any
+    }
+}
+
+impl TransactionDirection {
+    /// Checks if the extended transaction affects `address` in this direction.
+    fn matches(self, ext_tx: &ExtendedTransaction, address: &Address) -> bool {
+        match (&ext_tx.data, self) {
+            (_, TransactionDirection::Any) => true,
+            (ExtTxData::Basic(tx), TransactionDirection::Incoming) => &tx.recipient == address,
+            (ExtTxData::Basic(tx), TransactionDirection::Outgoing) => &tx.sender == address,
+            (ExtTxData::Inherent(inherent), TransactionDirection::Incoming) => {
+                &inherent.target == address
+            }
+            (ExtTxData::Inherent(_), TransactionDirection::Outgoing) => false,
+        }
+    }
+}
+
+/// A position in the history of an address, after which a query continues.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HistoryPosition {
+    /// Continue with the transactions older than the transaction with this hash.
+    Hash(Blake2bHash),
+    /// Continue with the transactions in blocks before this block number.
+    BlockNumber(u32),
+}
+
+/// Selects the transactions of an address that are returned by a query. The bounds of the ranges
+/// are inclusive.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    pub direction: TransactionDirection,
+    pub start_after: Option<HistoryPosition>,
+    pub min_block_number: Option<u32>,
+    pub max_block_number: Option<u32>,
+    pub min_timestamp: Option<u64>,
+    pub max_timestamp: Option<u64>,
+}
+
+/// What to do with a transaction of an address when going back through its history.
+enum FilterResult {
+    Take,
+    Skip,
+    /// The transaction and all older ones are filtered out.
+    Stop,
+}
+
+impl HistoryFilter {
+    /// Checks if the extended transactions need to be fetched to apply the filter, since the
+    /// address index only contains their hashes.
+    fn needs_ext_tx(&self) -> bool {
+        self.direction != TransactionDirection::Any
+            || matches!(self.start_after, Some(HistoryPosition::BlockNumber(_)))
+            || self.min_block_number.is_some()
+            || self.max_block_number.is_some()
+            || self.min_timestamp.is_some()
+            || self.max_timestamp.is_some()
+    }
+
+    fn apply(&self, ext_tx: &ExtendedTransaction, address: &Address) -> FilterResult {
+        // The history of an address is ordered by block, so all older transactions are out of
+        // range, too.
+        if self
+            .min_block_number
+            .map_or(false, |min| ext_tx.block_number < min)
+            || self
+                .min_timestamp
+                .map_or(false, |min| ext_tx.block_time < min)
+        {
+            return FilterResult::Stop;
+        }
+
+        let after_start = match self.start_after {
+            Some(HistoryPosition::BlockNumber(block_number)) => ext_tx.block_number < block_number,
+            _ => true,
+        };
+
+        if after_start
+            && self
+                .max_block_number
+                .map_or(true, |max| ext_tx.block_number <= max)
+            && self
+                .max_timestamp
+                .map_or(true, |max| ext_tx.block_time <= max)
+            && self.direction.matches(ext_tx, address)
+        {
+            FilterResult::Take
+        } else {
+            FilterResult::Skip
+        }
+    }
+}
+
 impl HistoryStore {
     const HIST_TREE_DB_NAME: &'static str = "HistoryTrees";
     const EXT_TX_DB_NAME: &'static str = "ExtendedTransactions";
@@ -49,6 +157,11 @@ impl HistoryStore {
     const BLOCK_DB_NAME: &'static str = "LeafHashesByBlock";
     const ADDRESS_DB_NAME: &'static str = "TxHashesByAddress";
 
+    /// The version of the address index. Stores with an older index have to rebuild it with
+    /// `rebuild_address_index`.
+    /// Version 1 also indexes inherents by their target address.
+    pub const ADDRESS_INDEX_VERSION: u32 = 1;
+
     /// Creates a new HistoryStore.
     pub fn new(env: Environment) -> Self {
         let hist_tree_db = env.open_database(Self::HIST_TREE_DB_NAME.to_string());
@@ -329,12 +442,13 @@ impl HistoryStore {
         tree.num_leaves()
     }
 
-    /// Returns a vector containing all transaction (no inherents) hashes corresponding to the given
-    /// address. It fetches the transactions from most recent to least recent up to the maximum
-    /// number given.
+    /// Returns a vector containing all transaction and inherent hashes corresponding to the given
+    /// address that pass the filter. It fetches the transactions from most recent to least recent
+    /// up to the maximum number given.
     pub fn get_tx_hashes_by_address(
         &self,
         address: &Address,
+        filter: &HistoryFilter,
         max: u16,
         txn_option: Option<&Transaction>,
     ) -> Vec<Blake2bHash> {
@@ -353,26 +467,71 @@ impl HistoryStore {
 
         let mut tx_hashes = vec![];
 
-        // Seek to the first transaction hash at the given address.
-        let mut cursor = txn.cursor(&self.address_db);
+        self.visit_tx_hashes_by_address(address, filter, txn, |tx_hash| {
+            tx_hashes.push(tx_hash);
+            tx_hashes.len() < max as usize
+        });
 
-        cursor.seek_key::<Address, OrderedHash>(address);
+        tx_hashes
+    }
 
-        // Then go to the last transaction hash at the given address.
-        match cursor.last_duplicate::<OrderedHash>() {
-            Some(v) => tx_hashes.push(v.hash),
-            None => return tx_hashes,
+    /// Returns the number of transactions and inherents corresponding to the given address that
+    /// pass the filter.
+    pub fn count_tx_hashes_by_address(
+        &self,
+        address: &Address,
+        filter: &HistoryFilter,
+        txn_option: Option<&Transaction>,
+    ) -> usize {
+        let read_txn: ReadTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = ReadTransaction::new(&self.env);
+                &read_txn
+            }
         };
 
-        while tx_hashes.len() < max as usize {
-            // Get previous transaction hash.
-            match cursor.prev_duplicate::<Address, OrderedHash>() {
-                Some((_, v)) => tx_hashes.push(v.hash),
-                None => break,
+        // Without a filter, we can count the entries in the index directly.
+        if filter == &HistoryFilter::default() {
+            let mut cursor = txn.cursor(&self.address_db);
+
+            return match cursor.seek_key::<Address, OrderedHash>(address) {
+                Some(_) => cursor.count_duplicates(),
+                None => 0,
             };
         }
 
-        tx_hashes
+        let mut count = 0;
+
+        self.visit_tx_hashes_by_address(address, filter, txn, |_| {
+            count += 1;
+            true
+        });
+
+        count
+    }
+
+    /// Rebuilds the index of transaction hashes by address from the history trees of all epochs up
+    /// to `last_epoch`. This migrates the index of a store that was created by an older version.
+    pub fn rebuild_address_index(&self, txn: &mut WriteTransaction, last_epoch: u32) {
+        // Remove the old index.
+        let mut cursor = txn.write_cursor(&self.address_db);
+        let mut pos: Option<(Address, OrderedHash)> = cursor.first();
+
+        while pos.is_some() {
+            cursor.remove();
+            pos = cursor.next();
+        }
+
+        drop(cursor);
+
+        // Index the extended transactions of each epoch in the order in which they were added.
+        for epoch_number in 0..=last_epoch {
+            for ext_tx in self.get_epoch_transactions(epoch_number, Some(txn)) {
+                self.put_address_index(txn, &ext_tx);
+            }
+        }
     }
 
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
@@ -399,6 +558,60 @@ impl HistoryStore {
         self.prove_with_position(epoch_number, positions, txn_option)
     }
 
+    /// Returns a proof for all the extended transactions corresponding to the given transaction
+    /// hashes against the history tree of the given epoch as it was at the given block number,
+    /// i.e. against the history root of that block. Transactions after that block are ignored.
+    pub fn prove_at_block(
+        &self,
+        epoch_number: u32,
+        block_number: u32,
+        hashes: Vec<&Blake2bHash>,
+        txn_option: Option<&Transaction>,
+    ) -> Option<HistoryTreeProof> {
+        // Rebuild the history tree up to the given block. The extended transactions are ordered by
+        // block number, so this is a prefix of the history tree of the epoch.
+        let ext_txs: Vec<ExtendedTransaction> = self
+            .get_epoch_transactions(epoch_number, txn_option)
+            .into_iter()
+            .take_while(|ext_tx| ext_tx.block_number <= block_number)
+            .collect();
+
+        let mut tree = MerkleMountainRange::new(MemoryStore::new());
+
+        for ext_tx in &ext_txs {
+            tree.push(ext_tx).ok()?;
+        }
+
+        // Get the leaf indexes.
+        let mut positions = vec![];
+
+        for hash in hashes {
+            let mut indices = self
+                .get_leaves_by_tx_hash(hash, txn_option)
+                .iter()
+                .map(|i| i.index as usize)
+                .filter(|i| {
+                    ext_txs
+                        .get(*i)
+                        .map_or(false, |ext_tx| ext_tx.tx_hash() == *hash)
+                })
+                .collect();
+
+            positions.append(&mut indices)
+        }
+
+        // Create Merkle proof.
+        let proof = tree.prove(&positions).ok()?;
+
+        let history = positions.iter().map(|i| ext_txs[*i].clone()).collect();
+
+        Some(HistoryTreeProof {
+            proof,
+            positions,
+            history,
+        })
+    }
+
     /// Returns a proof for all the extended transactions at the given positions (leaf indexes). The
     /// proof also includes the extended transactions.
     fn prove_with_position(
@@ -575,32 +788,24 @@ impl HistoryStore {
 
         txn.put(&self.block_db, &ext_tx.block_number, leaf_hash);
 
-        match &ext_tx.data {
-            ExtTxData::Basic(tx) => {
-                let index_tx_sender = self.get_last_tx_index_for_address(&tx.sender, Some(txn)) + 1;
-
-                txn.put(
-                    &self.address_db,
-                    &tx.sender,
-                    &OrderedHash {
-                        index: index_tx_sender,
-                        hash: tx_hash.clone(),
-                    },
-                );
-
-                let index_tx_recipient =
-                    self.get_last_tx_index_for_address(&tx.recipient, Some(txn)) + 1;
-
-                txn.put(
-                    &self.address_db,
-                    &tx.recipient,
-                    &OrderedHash {
-                        index: index_tx_recipient,
-                        hash: tx_hash,
-                    },
-                );
-            }
-            ExtTxData::Inherent(_) => {}
+        self.put_address_index(txn, ext_tx);
+    }
+
+    /// Adds the hash of an extended transaction to the index of all addresses it affects.
+    fn put_address_index(&self, txn: &mut WriteTransaction, ext_tx: &ExtendedTransaction) {
+        let tx_hash = ext_tx.tx_hash();
+
+        for address in Self::affected_addresses(ext_tx) {
+            let index = self.get_last_tx_index_for_address(address, Some(txn)) + 1;
+
+            txn.put(
+                &self.address_db,
+                address,
+                &OrderedHash {
+                    index,
+                    hash: tx_hash.clone(),
+                },
+            );
         }
     }
 
@@ -634,66 +839,103 @@ impl HistoryStore {
 
         txn.remove_item(&self.block_db, &ext_tx.block_number, leaf_hash);
 
-        match &ext_tx.data {
-            ExtTxData::Basic(tx) => {
-                let mut cursor = txn.cursor(&self.address_db);
-
-                // Seek to the last transaction hash at the sender's address and
-                // go back until you find the correct one.
-                let mut sender_value = None;
-
-                if cursor
-                    .seek_key::<Address, OrderedHash>(&tx.sender)
-                    .is_some()
-                {
-                    let mut duplicate = cursor.last_duplicate::<OrderedHash>();
-
-                    while let Some(v) = duplicate {
-                        if v.hash == tx_hash {
-                            sender_value = Some(v);
-                            break;
-                        }
-
-                        duplicate = cursor
-                            .prev_duplicate::<Address, OrderedHash>()
-                            .map(|(_, v)| v);
-                    }
-                }
+        for address in Self::affected_addresses(&ext_tx) {
+            let mut cursor = txn.cursor(&self.address_db);
+
+            // Seek to the last transaction hash at the address and go back until you find the
+            // correct one.
+            let mut value = None;
 
-                // Seek to the last transaction hash at the recipient's address and
-                // go back until you find the correct one.
-                let mut recipient_value = None;
-
-                if cursor
-                    .seek_key::<Address, OrderedHash>(&tx.recipient)
-                    .is_some()
-                {
-                    let mut duplicate = cursor.last_duplicate::<OrderedHash>();
-
-                    while let Some(v) = duplicate {
-                        if v.hash == tx_hash {
-                            recipient_value = Some(v);
-                            break;
-                        }
-
-                        duplicate = cursor
-                            .prev_duplicate::<Address, OrderedHash>()
-                            .map(|(_, v)| v);
+            if cursor.seek_key::<Address, OrderedHash>(address).is_some() {
+                let mut duplicate = cursor.last_duplicate::<OrderedHash>();
+
+                while let Some(v) = duplicate {
+                    if v.hash == tx_hash {
+                        value = Some(v);
+                        break;
                     }
+
+                    duplicate = cursor
+                        .prev_duplicate::<Address, OrderedHash>()
+                        .map(|(_, v)| v);
                 }
+            }
 
-                // Now remove both values. This weird construction is because of Rust's borrowing rules.
-                drop(cursor);
+            // Now remove the value. This weird construction is because of Rust's borrowing rules.
+            drop(cursor);
 
-                if let Some(v) = sender_value {
-                    txn.remove_item(&self.address_db, &tx.sender, &v);
-                }
+            if let Some(v) = value {
+                txn.remove_item(&self.address_db, address, &v);
+            }
+        }
+    }
+
+    /// Returns the addresses under which an extended transaction is indexed: the sender and the
+    /// recipient of a transaction or the target of an inherent.
+    fn affected_addresses(ext_tx: &ExtendedTransaction) -> Vec<&Address> {
+        match &ext_tx.data {
+            ExtTxData::Basic(tx) => vec![&tx.sender, &tx.recipient],
+            ExtTxData::Inherent(inherent) => vec![&inherent.target],
+        }
+    }
 
-                if let Some(v) = recipient_value {
-                    txn.remove_item(&self.address_db, &tx.recipient, &v);
+    /// Goes back through the transaction hashes of the given address, starting with the most
+    /// recent one, and calls `visit` with those that pass the filter until it returns false.
+    fn visit_tx_hashes_by_address<F: FnMut(Blake2bHash) -> bool>(
+        &self,
+        address: &Address,
+        filter: &HistoryFilter,
+        txn: &Transaction,
+        mut visit: F,
+    ) {
+        let start_after_hash = match &filter.start_after {
+            Some(HistoryPosition::Hash(hash)) => Some(hash),
+            _ => None,
+        };
+        let mut found_start = start_after_hash.is_none();
+
+        // Seek to the first transaction hash at the given address.
+        let mut cursor = txn.cursor(&self.address_db);
+
+        if cursor.seek_key::<Address, OrderedHash>(address).is_none() {
+            return;
+        }
+
+        // Then go back from the last transaction hash at the given address.
+        let mut entry = cursor.last_duplicate::<OrderedHash>();
+
+        while let Some(v) = entry {
+            // Get previous transaction hash.
+            entry = cursor
+                .prev_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+
+            // Skip all transactions up to and including the one to start after. A transaction
+            // can be indexed twice for the same address if it is sent to its sender.
+            if start_after_hash == Some(&v.hash) {
+                found_start = true;
+                continue;
+            }
+            if !found_start {
+                continue;
+            }
+
+            if filter.needs_ext_tx() {
+                let ext_tx = match self.get_ext_tx_by_hash(&v.hash, Some(txn)).pop() {
+                    Some(ext_tx) => ext_tx,
+                    None => continue,
+                };
+
+                match filter.apply(&ext_tx, address) {
+                    FilterResult::Take => {}
+                    FilterResult::Skip => continue,
+                    FilterResult::Stop => break,
                 }
             }
-            ExtTxData::Inherent(_) => {}
+
+            if !visit(v.hash) {
+                break;
+            }
         }
     }
 
@@ -762,7 +1004,7 @@ impl HistoryStore {
         leaf_hashes
     }
 
-    /// Returns the index of the last transaction (no inherent) associated to the given address.
+    /// Returns the index of the last transaction or inherent associated to the given address.
     fn get_last_tx_index_for_address(
         &self,
         address: &Address,
@@ -1049,9 +1291,13 @@ mod tests {
         history_store.add_to_history(&mut txn, 0, &ext_txs);
 
         // Verify method works.
+        let sender =
+            Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
+                .unwrap();
+
         let query_sender = history_store.get_tx_hashes_by_address(
-            &Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
-                .unwrap(),
+            &sender,
+            &HistoryFilter::default(),
             99,
             Some(&txn),
         );
@@ -1061,12 +1307,246 @@ mod tests {
         assert_eq!(query_sender[1], ext_1.tx_hash());
         assert_eq!(query_sender[2], ext_0.tx_hash());
 
-        let query_recipient =
-            history_store.get_tx_hashes_by_address(&Address::burn_address(), 2, Some(&txn));
+        let query_outgoing = history_store.get_tx_hashes_by_address(
+            &sender,
+            &HistoryFilter {
+                direction: TransactionDirection::Outgoing,
+                ..Default::default()
+            },
+            99,
+            Some(&txn),
+        );
+
+        assert_eq!(query_outgoing, query_sender);
+
+        let query_incoming = history_store.get_tx_hashes_by_address(
+            &sender,
+            &HistoryFilter {
+                direction: TransactionDirection::Incoming,
+                ..Default::default()
+            },
+            99,
+            Some(&txn),
+        );
+
+        assert!(query_incoming.is_empty());
+
+        // The inherents target the burn address, which is also the recipient of the transactions.
+        let query_recipient = history_store.get_tx_hashes_by_address(
+            &Address::burn_address(),
+            &HistoryFilter::default(),
+            2,
+            Some(&txn),
+        );
 
         assert_eq!(query_recipient.len(), 2);
-        assert_eq!(query_recipient[0], ext_3.tx_hash());
-        assert_eq!(query_recipient[1], ext_1.tx_hash());
+        assert_eq!(query_recipient[0], ext_4.tx_hash());
+        assert_eq!(query_recipient[1], ext_3.tx_hash());
+
+        let query_incoming = history_store.get_tx_hashes_by_address(
+            &Address::burn_address(),
+            &HistoryFilter {
+                direction: TransactionDirection::Incoming,
+                ..Default::default()
+            },
+            99,
+            Some(&txn),
+        );
+
+        assert_eq!(query_incoming.len(), 5);
+        assert_eq!(query_incoming[2], ext_2.tx_hash());
+
+        let query_outgoing = history_store.get_tx_hashes_by_address(
+            &Address::burn_address(),
+            &HistoryFilter {
+                direction: TransactionDirection::Outgoing,
+                ..Default::default()
+            },
+            99,
+            Some(&txn),
+        );
+
+        assert!(query_outgoing.is_empty());
+    }
+
+    #[test]
+    fn get_tx_hashes_by_address_with_filter_works() {
+        // Initialize History Store.
+        let env = VolatileEnvironment::new(10).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create extended transactions, one second apart.
+        let ext_txs: Vec<ExtendedTransaction> = vec![
+            create_transaction(1, 0),
+            create_transaction(1, 1),
+            create_inherent(2, 2),
+            create_transaction(3, 3),
+            create_transaction(4, 4),
+        ]
+        .into_iter()
+        .map(|mut ext_tx| {
+            ext_tx.block_time = ext_tx.block_number as u64 * 1000;
+            ext_tx
+        })
+        .collect();
+
+        let hashes: Vec<Blake2bHash> = ext_txs.iter().map(|ext_tx| ext_tx.tx_hash()).collect();
+
+        // Add extended transactions to History Store.
+        let mut txn = WriteTransaction::new(&env);
+        history_store.add_to_history(&mut txn, 0, &ext_txs);
+
+        let query = |filter: HistoryFilter, max: u16| {
+            history_store.get_tx_hashes_by_address(
+                &Address::burn_address(),
+                &filter,
+                max,
+                Some(&txn),
+            )
+        };
+
+        // Verify pagination works.
+        assert_eq!(
+            query(HistoryFilter::default(), 2),
+            vec![hashes[4].clone(), hashes[3].clone()]
+        );
+        assert_eq!(
+            query(
+                HistoryFilter {
+                    start_after: Some(HistoryPosition::Hash(hashes[3].clone())),
+                    ..Default::default()
+                },
+                2
+            ),
+            vec![hashes[2].clone(), hashes[1].clone()]
+        );
+        assert_eq!(
+            query(
+                HistoryFilter {
+                    start_after: Some(HistoryPosition::Hash(hashes[1].clone())),
+                    ..Default::default()
+                },
+                2
+            ),
+            vec![hashes[0].clone()]
+        );
+        assert_eq!(
+            query(
+                HistoryFilter {
+                    start_after: Some(HistoryPosition::BlockNumber(3)),
+                    ..Default::default()
+                },
+                99
+            ),
+            vec![hashes[2].clone(), hashes[1].clone(), hashes[0].clone()]
+        );
+
+        // Verify the range filters work.
+        assert_eq!(
+            query(
+                HistoryFilter {
+                    min_block_number: Some(2),
+                    max_block_number: Some(3),
+                    ..Default::default()
+                },
+                99
+            ),
+            vec![hashes[3].clone(), hashes[2].clone()]
+        );
+        assert_eq!(
+            query(
+                HistoryFilter {
+                    min_timestamp: Some(2000),
+                    max_timestamp: Some(3000),
+                    ..Default::default()
+                },
+                99
+            ),
+            vec![hashes[3].clone(), hashes[2].clone()]
+        );
+
+        // Verify counting works.
+        let count = |filter: HistoryFilter| {
+            history_store.count_tx_hashes_by_address(&Address::burn_address(), &filter, Some(&txn))
+        };
+
+        assert_eq!(count(HistoryFilter::default()), 5);
+        assert_eq!(
+            count(HistoryFilter {
+                min_block_number: Some(2),
+                ..Default::default()
+            }),
+            3
+        );
+        assert_eq!(
+            count(HistoryFilter {
+                direction: TransactionDirection::Outgoing,
+                ..Default::default()
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn remove_partial_history_removes_address_index() {
+        // Initialize History Store.
+        let env = VolatileEnvironment::new(10).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create extended transactions.
+        let ext_0 = create_transaction(0, 0);
+        let ext_1 = create_inherent(1, 1);
+        let ext_2 = create_transaction(1, 2);
+
+        let ext_txs = vec![ext_0.clone(), ext_1, ext_2];
+
+        // Add extended transactions to History Store and remove the last two again.
+        let mut txn = WriteTransaction::new(&env);
+        history_store.add_to_history(&mut txn, 0, &ext_txs);
+        history_store.remove_partial_history(&mut txn, 0, 2);
+
+        // Verify method works.
+        let query = history_store.get_tx_hashes_by_address(
+            &Address::burn_address(),
+            &HistoryFilter::default(),
+            99,
+            Some(&txn),
+        );
+
+        assert_eq!(query, vec![ext_0.tx_hash()]);
+    }
+
+    #[test]
+    fn rebuild_address_index_works() {
+        // Initialize History Store.
+        let env = VolatileEnvironment::new(10).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create extended transactions in two epochs.
+        let ext_0 = create_transaction(0, 0);
+        let ext_1 = create_inherent(0, 1);
+        let ext_2 = create_transaction(1, 2);
+
+        // Add extended transactions to History Store.
+        let mut txn = WriteTransaction::new(&env);
+        history_store.add_to_history(&mut txn, 0, &[ext_0.clone(), ext_1.clone()]);
+        history_store.add_to_history(&mut txn, 1, &[ext_2.clone()]);
+
+        let query = |txn: &WriteTransaction| {
+            history_store.get_tx_hashes_by_address(
+                &Address::burn_address(),
+                &HistoryFilter::default(),
+                99,
+                Some(txn),
+            )
+        };
+
+        let expected = vec![ext_2.tx_hash(), ext_1.tx_hash(), ext_0.tx_hash()];
+        assert_eq!(query(&txn), expected);
+
+        // Verify that rebuilding the index results in the same index.
+        history_store.rebuild_address_index(&mut txn, 1);
+        assert_eq!(query(&txn), expected);
     }
 
     #[test]
@@ -1106,6 +1586,50 @@ mod tests {
         assert!(proof.verify(root).unwrap());
     }
 
+    #[test]
+    fn prove_at_block_works() {
+        // Initialize History Store.
+        let env = VolatileEnvironment::new(10).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create extended transactions.
+        let ext_0 = create_transaction(0, 0);
+        let ext_1 = create_transaction(0, 1);
+        let ext_2 = create_inherent(0, 2);
+        let ext_3 = create_transaction(1, 3);
+        let ext_4 = create_inherent(1, 4);
+
+        let ext_txs = vec![ext_0, ext_1.clone(), ext_2, ext_3, ext_4.clone()];
+
+        // Add extended transactions to History Store.
+        let mut txn = WriteTransaction::new(&env);
+        history_store.add_to_history(&mut txn, 0, &ext_txs);
+
+        // The proof at block 0 is against the history root of block 0 and doesn't contain the
+        // transactions of block 1.
+        let root = HistoryStore::get_root_from_ext_txs(&ext_txs[..3]).unwrap();
+
+        let proof = history_store
+            .prove_at_block(0, 0, vec![&ext_1.tx_hash(), &ext_4.tx_hash()], Some(&txn))
+            .unwrap();
+
+        assert_eq!(proof.positions, vec![1]);
+        assert_eq!(proof.history.len(), 1);
+        assert_eq!(proof.history[0].tx_hash(), ext_1.tx_hash());
+
+        assert!(proof.verify(root).unwrap());
+
+        // The proof at block 1 is against the whole history tree.
+        let root = history_store.get_history_tree_root(0, Some(&txn)).unwrap();
+
+        let proof = history_store
+            .prove_at_block(0, 1, vec![&ext_1.tx_hash(), &ext_4.tx_hash()], Some(&txn))
+            .unwrap();
+
+        assert_eq!(proof.positions, vec![1, 4]);
+        assert!(proof.verify(root).unwrap());
+    }
+
     fn create_inherent(block: u32, value: u64) -> ExtendedTransaction {
         ExtendedTransaction {
             block_number: block,
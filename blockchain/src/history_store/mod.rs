@@ -1,5 +1,5 @@
 pub use extended_transaction::*;
-pub use history_store::HistoryStore;
+pub use history_store::{HistoryFilter, HistoryPosition, HistoryStore, TransactionDirection};
 pub use history_tree_chunk::{HistoryTreeChunk, CHUNK_SIZE};
 pub use history_tree_proof::HistoryTreeProof;
 
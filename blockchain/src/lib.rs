@@ -5,12 +5,16 @@ extern crate log;
 
 pub use abstract_blockchain::AbstractBlockchain;
 pub use blockchain::blockchain::Blockchain;
+pub use blockchain::chain_file::ChainFileHeader;
+pub use blockchain::snapshot::SnapshotFileHeader;
+pub use blockchain::staking_events::{StakingEvent, StakingEventKind};
 pub use chain_info::ChainInfo;
 pub use chain_ordering::ChainOrdering;
 pub use error::*;
 pub use history_store::*;
 
 pub(crate) mod abstract_blockchain;
+pub(crate) mod archive_store;
 pub(crate) mod blockchain;
 pub(crate) mod blockchain_state;
 pub(crate) mod chain_info;
@@ -21,3 +25,4 @@ pub(crate) mod chain_store;
 pub(crate) mod error;
 pub(crate) mod history_store;
 pub mod reward;
+pub(crate) mod snapshot_store;
@@ -0,0 +1,75 @@
+use nimiq_account::Account;
+use nimiq_database::cursor::{ReadCursor, WriteCursor};
+use nimiq_database::{
+    Database, DatabaseFlags, Environment, ReadTransaction, Transaction, WriteTransaction,
+};
+use nimiq_tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
+
+/// A database containing the accounts tree at the latest election block, split into chunks. It is
+/// used to serve accounts snapshots to other nodes, since the accounts tree itself changes with
+/// every block.
+#[derive(Debug)]
+pub struct SnapshotStore {
+    env: Environment,
+    // A database of the chunks of the snapshot indexed by their position.
+    chunk_db: Database,
+}
+
+impl SnapshotStore {
+    const CHUNK_DB_NAME: &'static str = "AccountsSnapshot";
+
+    pub fn new(env: Environment) -> Self {
+        let chunk_db =
+            env.open_database_with_flags(Self::CHUNK_DB_NAME.to_string(), DatabaseFlags::UINT_KEYS);
+        SnapshotStore { env, chunk_db }
+    }
+
+    /// Replaces the stored snapshot with the given chunks.
+    pub fn put(&self, txn: &mut WriteTransaction, chunks: &[AccountsTreeChunk<Account>]) {
+        self.clear(txn);
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            txn.put_reserve(&self.chunk_db, &(i as u32), chunk);
+        }
+    }
+
+    /// Returns the chunk at the given position.
+    pub fn get_chunk(
+        &self,
+        index: u32,
+        txn_option: Option<&Transaction>,
+    ) -> Option<AccountsTreeChunk<Account>> {
+        match txn_option {
+            Some(txn) => txn.get(&self.chunk_db, &index),
+            None => ReadTransaction::new(&self.env).get(&self.chunk_db, &index),
+        }
+    }
+
+    /// Returns the number of chunks of the stored snapshot.
+    pub fn num_chunks(&self, txn_option: Option<&Transaction>) -> u32 {
+        let read_txn: ReadTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = ReadTransaction::new(&self.env);
+                &read_txn
+            }
+        };
+
+        let mut cursor = txn.cursor(&self.chunk_db);
+        cursor
+            .last::<u32, AccountsTreeChunk<Account>>()
+            .map_or(0, |(index, _)| index + 1)
+    }
+
+    /// Removes the stored snapshot.
+    pub fn clear(&self, txn: &mut WriteTransaction) {
+        let mut cursor = txn.write_cursor(&self.chunk_db);
+        let mut pos: Option<(u32, AccountsTreeChunk<Account>)> = cursor.first();
+
+        while pos.is_some() {
+            cursor.remove();
+            pos = cursor.next();
+        }
+    }
+}
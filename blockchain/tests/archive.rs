@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use beserial::Deserialize;
+use nimiq_block_production::test_utils::{produce_macro_blocks, TemporaryBlockProducer};
+use nimiq_block_production::BlockProducer;
+use nimiq_blockchain::{AbstractBlockchain, Blockchain, PushResult};
+use nimiq_bls::{KeyPair, SecretKey};
+use nimiq_database::volatile::VolatileEnvironment;
+use nimiq_genesis::NetworkId;
+use nimiq_keys::Address;
+use nimiq_primitives::policy;
+
+/// Secret key of validator. Tests run with `genesis/src/genesis/unit-albatross.toml`
+const SECRET_KEY: &str = "196ffdb1a8acc7cbd76a251aeac0600a1d68b3aba1eba823b5e4dc5dbdcdc730afa752c05ab4f6ef8518384ad514f403c5a088a22b17bf1bc14f8ff8decc2a512c0a200f68d7bdf5a319b30356fe8d1d75ef510aed7a8660968c216c328a0000";
+
+/// Creates a block producer whose blockchain runs in archive mode from the genesis block on.
+fn archive_producer() -> TemporaryBlockProducer {
+    let env = VolatileEnvironment::new(11).unwrap();
+    let mut blockchain = Blockchain::new(env, NetworkId::UnitAlbatross).unwrap();
+    blockchain.set_archive(true);
+    let blockchain = Arc::new(blockchain);
+
+    let keypair =
+        KeyPair::from(SecretKey::deserialize_from_vec(&hex::decode(SECRET_KEY).unwrap()).unwrap());
+    let producer = BlockProducer::new_without_mempool(Arc::clone(&blockchain), keypair);
+    TemporaryBlockProducer {
+        blockchain,
+        producer,
+    }
+}
+
+#[test]
+fn it_can_query_accounts_in_archive_mode() {
+    let env = VolatileEnvironment::new(11).unwrap();
+    let mut blockchain = Blockchain::new(env, NetworkId::UnitAlbatross).unwrap();
+    let address = Address::from([1u8; Address::SIZE]);
+
+    // Past states are not available without archive mode.
+    assert_eq!(blockchain.get_account_at(&address, 0), None);
+
+    blockchain.set_archive(true);
+
+    // The state at the head is the current state.
+    let head = blockchain.block_number();
+    assert_eq!(
+        blockchain.get_account_at(&address, head),
+        Some(blockchain.get_account(&address))
+    );
+
+    // There is no state for blocks after the head.
+    assert_eq!(blockchain.get_account_at(&address, head + 1), None);
+
+    // Disabling archive mode discards the past states.
+    blockchain.set_archive(false);
+    assert_eq!(blockchain.get_account_at(&address, head), None);
+}
+
+#[test]
+fn it_can_query_past_balances() {
+    let temp_producer = archive_producer();
+    let blockchain = &temp_producer.blockchain;
+
+    // The reward address of the validator receives rewards with every macro block.
+    let reward_address = blockchain
+        .get_staking_contract()
+        .active_validators_sorted
+        .iter()
+        .next()
+        .unwrap()
+        .reward_address
+        .clone();
+
+    let num_batches = 3;
+    let mut balances = vec![blockchain.get_account(&reward_address).balance()];
+    for _ in 0..num_batches {
+        produce_macro_blocks(1, &temp_producer.producer, blockchain);
+        balances.push(blockchain.get_account(&reward_address).balance());
+    }
+    assert!(balances[num_batches] > balances[0]);
+
+    // The balance after each block is the one after the last macro block up to it.
+    for batch in 0..num_batches {
+        let macro_block_number = batch as u32 * policy::BATCH_LENGTH;
+        for block_number in macro_block_number..macro_block_number + policy::BATCH_LENGTH {
+            assert_eq!(
+                blockchain
+                    .get_account_at(&reward_address, block_number)
+                    .unwrap()
+                    .balance(),
+                balances[batch],
+                "Wrong balance after block {}",
+                block_number
+            );
+        }
+    }
+
+    let head = blockchain.block_number();
+    assert_eq!(
+        blockchain
+            .get_account_at(&reward_address, head)
+            .unwrap()
+            .balance(),
+        balances[num_batches]
+    );
+}
+
+#[test]
+fn it_can_query_past_states_across_a_rebranch() {
+    // Build forks using two producers, both in archive mode.
+    let temp_producer1 = archive_producer();
+    let temp_producer2 = archive_producer();
+    let staking_contract_address = temp_producer1
+        .blockchain
+        .staking_contract_address()
+        .unwrap()
+        .clone();
+
+    let block = temp_producer1.next_block(0, vec![]);
+    temp_producer2.push(block).unwrap();
+    let state1 = temp_producer1
+        .blockchain
+        .get_account(&staking_contract_address);
+
+    // The view changes slash validators, which changes the staking contract.
+    // [0] - [1] - [2]
+    //          \- [2']
+    temp_producer1.next_block(1, vec![]);
+    let fork = temp_producer2.next_block(2, vec![]);
+    let fork_state = temp_producer2
+        .blockchain
+        .get_account(&staking_contract_address);
+
+    assert_eq!(
+        temp_producer1.push(fork.clone()),
+        Ok(PushResult::Rebranched)
+    );
+    assert_eq!(temp_producer1.blockchain.head_hash(), fork.hash());
+
+    // States before the fork are unchanged, the state of the fork replaces the reverted one.
+    let blockchain1 = &temp_producer1.blockchain;
+    assert_eq!(
+        blockchain1.get_account_at(&staking_contract_address, 1),
+        Some(state1)
+    );
+    assert_eq!(
+        blockchain1.get_account_at(&staking_contract_address, 2),
+        Some(fork_state.clone())
+    );
+
+    // Once the chain moves on, the state after the fork is read from the archive.
+    let block = temp_producer1.next_block(3, vec![]);
+    temp_producer2.push(block).unwrap();
+    assert_eq!(
+        blockchain1.get_account_at(&staking_contract_address, 2),
+        Some(fork_state)
+    );
+    assert_eq!(
+        blockchain1.get_account_at(&staking_contract_address, 3),
+        Some(
+            temp_producer2
+                .blockchain
+                .get_account(&staking_contract_address)
+        )
+    );
+}
@@ -0,0 +1,40 @@
+use nimiq_block_production::test_utils::TemporaryBlockProducer;
+use nimiq_blockchain::AbstractBlockchain;
+use nimiq_primitives::policy;
+
+#[test]
+fn it_can_export_and_import_the_chain() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // Produce a batch and a few more micro blocks.
+    for _ in 0..(policy::BATCH_LENGTH + 3) {
+        temp_producer1.next_block(0, vec![]);
+    }
+
+    let mut file = Vec::new();
+    let num_exported = temp_producer1
+        .blockchain
+        .export_chain(&mut file, 1, None)
+        .unwrap();
+    assert_eq!(num_exported, policy::BATCH_LENGTH + 3);
+
+    let mut blocks_pushed = 0;
+    let num_imported = temp_producer2
+        .blockchain
+        .import_chain(&mut &file[..], |_, _| blocks_pushed += 1)
+        .unwrap();
+    assert_eq!(num_imported, num_exported);
+    assert_eq!(blocks_pushed, num_exported);
+    assert_eq!(
+        temp_producer2.blockchain.head_hash(),
+        temp_producer1.blockchain.head_hash()
+    );
+
+    // Importing the file again doesn't change the chain.
+    let num_imported = temp_producer2
+        .blockchain
+        .import_chain(&mut &file[..], |_, _| {})
+        .unwrap();
+    assert_eq!(num_imported, 0);
+}
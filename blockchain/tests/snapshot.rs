@@ -0,0 +1,123 @@
+use nimiq_block::BlockError;
+use nimiq_block_production::test_utils::TemporaryBlockProducer;
+use nimiq_blockchain::{AbstractBlockchain, Blockchain, ChainFileError, PushError, PushResult};
+use nimiq_database::ReadTransaction;
+use nimiq_primitives::policy;
+
+#[test]
+fn it_can_export_and_import_a_snapshot() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // Produce a whole epoch, so that the head is an election block.
+    for _ in 0..policy::EPOCH_LENGTH {
+        temp_producer1.next_block(0, vec![]);
+    }
+
+    let mut file = Vec::new();
+    let num_chunks = temp_producer1
+        .blockchain
+        .export_snapshot(&mut file)
+        .unwrap();
+    assert!(num_chunks > 0);
+
+    let result = temp_producer2
+        .blockchain
+        .import_snapshot(&mut &file[..])
+        .unwrap();
+    assert_eq!(result, PushResult::Extended);
+    assert_eq!(
+        temp_producer2.blockchain.head_hash(),
+        temp_producer1.blockchain.head_hash()
+    );
+    assert_eq!(
+        temp_producer2.blockchain.state().accounts.hash(None),
+        temp_producer1.blockchain.state().accounts.hash(None)
+    );
+
+    // The history of the validity window is imported, so that its transactions can't be replayed.
+    let history = temp_producer1
+        .blockchain
+        .history_store
+        .get_epoch_transactions(1, None);
+    assert!(!history.is_empty());
+    assert_eq!(
+        temp_producer2
+            .blockchain
+            .history_store
+            .get_epoch_transactions(1, None)
+            .len(),
+        history.len()
+    );
+    for ext_tx in &history {
+        assert!(temp_producer2
+            .blockchain
+            .contains_tx_in_validity_window(&ext_tx.tx_hash()));
+    }
+    assert_eq!(
+        temp_producer2.blockchain.state().main_chain.cum_tx_fees,
+        temp_producer1.blockchain.state().main_chain.cum_tx_fees
+    );
+
+    // Importing the snapshot again doesn't change the chain.
+    let result = temp_producer2
+        .blockchain
+        .import_snapshot(&mut &file[..])
+        .unwrap();
+    assert_eq!(result, PushResult::Known);
+
+    // The chain can be extended on top of the snapshot.
+    let block = temp_producer1.next_block(0, vec![]);
+    assert_eq!(temp_producer2.push(block), Ok(PushResult::Extended));
+
+    // Without a stored snapshot, only an election block at the head can be exported.
+    let mut file = Vec::new();
+    assert!(matches!(
+        temp_producer1.blockchain.export_snapshot(&mut file),
+        Err(ChainFileError::NoSnapshot)
+    ));
+}
+
+#[test]
+fn it_rejects_a_snapshot_without_the_history() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    for _ in 0..policy::EPOCH_LENGTH {
+        temp_producer1.next_block(0, vec![]);
+    }
+
+    let blockchain = &temp_producer1.blockchain;
+    let election_blocks = vec![blockchain
+        .chain_store
+        .get_block_at(policy::election_block_of(1), true, None)
+        .unwrap()
+        .unwrap_macro()];
+    let chunks = blockchain
+        .state()
+        .accounts
+        .get_chunks(
+            Blockchain::SNAPSHOT_CHUNK_SIZE,
+            &ReadTransaction::new(&blockchain.env),
+        )
+        .unwrap();
+    let mut history = blockchain.history_store.get_epoch_transactions(1, None);
+
+    // The history of the validity window is required.
+    assert_eq!(
+        temp_producer2
+            .blockchain
+            .push_snapshot(election_blocks.clone(), vec![], chunks.clone()),
+        Err(PushError::InvalidSnapshot)
+    );
+
+    // The history must match the history root of its election block.
+    history.pop();
+    assert_eq!(
+        temp_producer2
+            .blockchain
+            .push_snapshot(election_blocks, vec![history], chunks),
+        Err(PushError::InvalidBlock(BlockError::InvalidHistoryRoot))
+    );
+    assert_eq!(temp_producer2.blockchain.block_number(), 0);
+}
@@ -0,0 +1,98 @@
+use std::sync::{Arc, RwLock};
+
+use nimiq_block_production::test_utils::TemporaryBlockProducer;
+use nimiq_blockchain::{PushResult, StakingEvent, StakingEventKind};
+
+fn collect_staking_events(producer: &TemporaryBlockProducer) -> Arc<RwLock<Vec<StakingEvent>>> {
+    let events = Arc::new(RwLock::new(vec![]));
+    let events1 = Arc::clone(&events);
+
+    producer
+        .blockchain
+        .staking_notifier
+        .write()
+        .register(move |e: &StakingEvent| events1.write().unwrap().push(e.clone()));
+
+    events
+}
+
+fn num_slashes(events: &[StakingEvent]) -> usize {
+    events
+        .iter()
+        .filter(|event| matches!(event.kind, StakingEventKind::Slashed { .. }))
+        .count()
+}
+
+#[test]
+fn it_emits_slash_events_for_view_changes() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let events = collect_staking_events(&temp_producer);
+
+    // A block without view change doesn't change the staking contract.
+    temp_producer.next_block(0, vec![]);
+    assert!(events.read().unwrap().is_empty());
+
+    // Skipping two views slashes the producers of both views.
+    let block = temp_producer.next_block(2, vec![]);
+
+    let events = events.read().unwrap();
+    assert_eq!(num_slashes(&events), 2);
+
+    for event in events.iter() {
+        assert_eq!(event.block_hash, block.hash());
+        assert_eq!(event.block_number, block.block_number());
+        assert!(!event.reverted);
+    }
+
+    // A validator is parked at most once.
+    let parked = events
+        .iter()
+        .filter(|event| matches!(event.kind, StakingEventKind::ValidatorParked { .. }))
+        .count();
+    assert!(parked <= 1);
+}
+
+#[test]
+fn it_retracts_staking_events_on_rebranch() {
+    // Build forks using two producers.
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    let block = temp_producer1.next_block(0, vec![]);
+    temp_producer2.push(block).unwrap();
+
+    let events = collect_staking_events(&temp_producer1);
+
+    // [0] - [1]
+    //    \- [2]
+    let inferior = temp_producer1.next_block(1, vec![]);
+    let fork = temp_producer2.next_block(2, vec![]);
+
+    let inferior_events = events.read().unwrap().clone();
+    assert_eq!(num_slashes(&inferior_events), 1);
+
+    assert_eq!(
+        temp_producer1.push(fork.clone()),
+        Ok(PushResult::Rebranched)
+    );
+
+    let events = events.read().unwrap();
+    let new_events = &events[inferior_events.len()..];
+
+    // The events of the reverted block are retracted in reverse order.
+    let (retracted, adopted) = new_events.split_at(inferior_events.len());
+
+    for (retraction, event) in retracted.iter().zip(inferior_events.iter().rev()) {
+        assert!(retraction.reverted);
+        assert_eq!(retraction.block_hash, inferior.hash());
+        assert_eq!(retraction.kind, event.kind);
+    }
+
+    // Then the events of the adopted block are emitted.
+    assert_eq!(num_slashes(adopted), 2);
+
+    for event in adopted {
+        assert!(!event.reverted);
+        assert_eq!(event.block_hash, fork.hash());
+    }
+}
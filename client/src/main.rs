@@ -1,9 +1,10 @@
 use std::time::Duration;
 
 pub use nimiq::{
-    client::{Client, Consensus},
-    config::command_line::CommandLine,
-    config::config::ClientConfig,
+    chain_file::{export_chain, export_snapshot, import_chain, import_snapshot},
+    client::{Client, Consensus, NanoClient},
+    config::command_line::{Command, CommandLine},
+    config::config::{ClientConfig, ConsensusConfig},
     config::config_file::ConfigFile,
     error::Error,
     extras::{
@@ -41,6 +42,21 @@ async fn main_inner() -> Result<(), Error> {
     let config = builder.build()?;
     log::debug!("Final configuration: {:#?}", config);
 
+    // Run a subcommand instead of the client, if one was given.
+    if let Some(command) = command_line.command {
+        return match command {
+            Command::ExportChain { file, from, to } => export_chain(config, &file, from, to),
+            Command::ImportChain { file } => import_chain(config, &file),
+            Command::ExportSnapshot { file } => export_snapshot(config, &file),
+            Command::ImportSnapshot { file } => import_snapshot(config, &file),
+        };
+    }
+
+    // A nano client only runs the nano consensus.
+    if config.consensus == ConsensusConfig::Nano {
+        return run_nano_client(config, config_file.log.statistics).await;
+    }
+
     // Clone config for RPC and metrics server
     let rpc_config = config.rpc_server.clone();
     let _metrics_config = config.metrics_server.clone();
@@ -52,10 +68,12 @@ async fn main_inner() -> Result<(), Error> {
 
     // Initialize RPC server
     if let Some(rpc_config) = rpc_config {
-        use nimiq::extras::rpc_server::initialize_rpc_server;
-        let rpc_server = initialize_rpc_server(&client, rpc_config, client.wallet_store())
+        use nimiq::extras::rpc_server::initialize_rpc_servers;
+        let rpc_servers = initialize_rpc_servers(&client, rpc_config, client.wallet_store())
             .expect("Failed to initialize RPC server");
-        tokio::spawn(async move { rpc_server.run().await });
+        for rpc_server in rpc_servers {
+            tokio::spawn(async move { rpc_server.run().await });
+        }
     }
 
     // Initialize metrics server
@@ -88,6 +106,13 @@ async fn main_inner() -> Result<(), Error> {
     tokio::spawn(consensus);
     let consensus = client.consensus_proxy();
 
+    // Start ZKP prover
+    #[cfg(feature = "zkp-prover")]
+    if let Some(zkp_prover) = client.zkp_prover() {
+        log::info!("Spawning ZKP prover");
+        tokio::spawn(zkp_prover.run());
+    }
+
     // Start validator
     if let Some(validator) = client.validator() {
         log::info!("Spawning validator");
@@ -132,6 +157,52 @@ async fn main_inner() -> Result<(), Error> {
     }
 }
 
+async fn run_nano_client(config: ClientConfig, statistics_interval: u64) -> Result<(), Error> {
+    // Create client from config.
+    log::info!("Initializing nano client");
+    let mut client = NanoClient::from_config(config).await?;
+    log::info!("Nano client initialized");
+
+    // Start consensus.
+    let consensus = client.consensus().unwrap();
+
+    log::info!("Spawning nano consensus");
+    tokio::spawn(consensus);
+    let consensus = client.consensus_proxy();
+
+    let mut statistics_interval = statistics_interval;
+    let mut show_statistics = true;
+    if statistics_interval == 0 {
+        statistics_interval = 10;
+        show_statistics = false;
+    }
+
+    // Run periodically
+    let mut interval = tokio::time::interval(Duration::from_secs(statistics_interval));
+    loop {
+        interval.tick().await;
+
+        if show_statistics {
+            match client.network().network_info().await {
+                Ok(network_info) => {
+                    let head = client.blockchain_head();
+
+                    log::info!(
+                        "Consensus established: {:?} - Head: #{} - {}, Peers: {}",
+                        consensus.is_established(),
+                        head.block_number(),
+                        head.hash(),
+                        network_info.num_peers()
+                    );
+                }
+                Err(err) => {
+                    log::error!("Error retrieving NetworkInfo: {:?}", err);
+                }
+            };
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     if let Err(e) = main_inner().await {
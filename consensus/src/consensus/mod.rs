@@ -20,6 +20,8 @@ use crate::consensus::head_requests::{HeadRequests, HeadRequestsResult};
 use crate::consensus_agent::ConsensusAgent;
 use crate::sync::block_queue::{BlockQueue, BlockQueueConfig, BlockQueueEvent};
 use crate::sync::request_component::{BlockRequestComponent, HistorySyncStream};
+#[cfg(feature = "zkp-prover")]
+use crate::zkp::ZKPStore;
 
 mod head_requests;
 mod request_response;
@@ -99,6 +101,11 @@ pub struct Consensus<N: Network> {
     pub network: Arc<N>,
     pub env: Environment,
 
+    /// The store holding the proofs of the ZKP prover. It is opened once here, so that the prover
+    /// and the request handlers share the same handle.
+    #[cfg(feature = "zkp-prover")]
+    pub zkp_store: Arc<ZKPStore>,
+
     block_queue: BlockQueue<N, BlockRequestComponent<N::PeerType>>,
     tx_future: BoxFuture<'static, ()>,
 
@@ -193,7 +200,13 @@ impl<N: Network> Consensus<N> {
     ) -> Self {
         let (tx, _rx) = broadcast(256);
 
-        Self::init_network_requests(&network, &blockchain);
+        #[cfg(feature = "zkp-prover")]
+        let zkp_store = Arc::new(ZKPStore::new(env.clone()));
+
+        #[cfg(feature = "zkp-prover")]
+        Self::init_network_requests(&network, &blockchain, Some(Arc::clone(&zkp_store)));
+        #[cfg(not(feature = "zkp-prover"))]
+        Self::init_network_requests(&network, &blockchain, None);
 
         let established_flag = Arc::new(AtomicBool::new(false));
 
@@ -208,7 +221,11 @@ impl<N: Network> Consensus<N> {
                     if established_flag1.load(Ordering::Acquire) {
                         let acceptance = match mempool1.push_transaction(tx) {
                             ReturnCode::Accepted | ReturnCode::Known => MsgAcceptance::Accept,
-                            ReturnCode::Filtered | ReturnCode::FeeTooLow => MsgAcceptance::Ignore,
+                            ReturnCode::Filtered
+                            | ReturnCode::FeeTooLow
+                            | ReturnCode::MempoolFull
+                            | ReturnCode::SenderLimitReached
+                            | ReturnCode::ReplacementFeeTooLow => MsgAcceptance::Ignore,
                             ReturnCode::Invalid => MsgAcceptance::Reject,
                         };
 
@@ -230,6 +247,9 @@ impl<N: Network> Consensus<N> {
             network,
             env,
 
+            #[cfg(feature = "zkp-prover")]
+            zkp_store,
+
             block_queue,
             tx_future,
             events: tx,
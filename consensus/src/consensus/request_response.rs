@@ -4,16 +4,22 @@ use futures::StreamExt;
 
 use crate::messages::handlers::Handle;
 use crate::messages::{
-    RequestBatchSet, RequestBlock, RequestBlockHashes, RequestHead, RequestHistoryChunk,
-    RequestMissingBlocks,
+    RequestAccountsProof, RequestAccountsSnapshotChunk, RequestBatchSet, RequestBlock,
+    RequestBlockHashes, RequestHead, RequestHistoryChunk, RequestMissingBlocks,
+    RequestTransactionsProof, RequestZKP,
 };
+use crate::zkp::ZKPStore;
 use crate::Consensus;
 
 use blockchain::Blockchain;
 use network_interface::prelude::{Network, Peer};
 
 impl<N: Network> Consensus<N> {
-    pub(super) fn init_network_requests(network: &Arc<N>, blockchain: &Arc<Blockchain>) {
+    pub(super) fn init_network_requests(
+        network: &Arc<N>,
+        blockchain: &Arc<Blockchain>,
+        zkp_store: Option<Arc<ZKPStore>>,
+    ) {
         let blockchain_outer = blockchain;
         let blockchain = Arc::clone(blockchain_outer);
         let mut stream = network.receive_from_all::<RequestBlockHashes>();
@@ -125,5 +131,85 @@ impl<N: Network> Consensus<N> {
                 }
             }
         });
+
+        let mut stream = network.receive_from_all::<RequestZKP>();
+        tokio::spawn(async move {
+            while let Some((msg, peer)) = stream.next().await {
+                trace!("[REQUEST_ZKP] received from {:?}", peer.id());
+
+                let response = msg.handle_with_store(zkp_store.as_deref());
+
+                // Try to send the response, logging to debug if it fails
+                if let Err(err) = peer.send(&response).await {
+                    log::debug!("Failed to send RequestZKP Response: {:?}", err);
+                };
+            }
+        });
+
+        let blockchain = Arc::clone(blockchain_outer);
+        let mut stream = network.receive_from_all::<RequestAccountsProof>();
+        tokio::spawn(async move {
+            while let Some((msg, peer)) = stream.next().await {
+                trace!(
+                    "[REQUEST_ACCOUNTS_PROOF] for {} accounts at block {} received from {:?}",
+                    msg.addresses.len(),
+                    msg.block_hash,
+                    peer.id()
+                );
+
+                if let Some(response) = msg.handle(&blockchain) {
+                    // Try to send the response, logging to debug if it fails
+                    if let Err(err) = peer.send(&response).await {
+                        log::debug!("Failed to send RequestAccountsProof Response: {:?}", err);
+                    };
+                }
+            }
+        });
+
+        let blockchain = Arc::clone(blockchain_outer);
+        let mut stream = network.receive_from_all::<RequestTransactionsProof>();
+        tokio::spawn(async move {
+            while let Some((msg, peer)) = stream.next().await {
+                trace!(
+                    "[REQUEST_TRANSACTIONS_PROOF] for {} transactions and address {:?} received from {:?}",
+                    msg.hashes.len(),
+                    msg.address,
+                    peer.id()
+                );
+
+                if let Some(response) = msg.handle(&blockchain) {
+                    // Try to send the response, logging to debug if it fails
+                    if let Err(err) = peer.send(&response).await {
+                        log::debug!(
+                            "Failed to send RequestTransactionsProof Response: {:?}",
+                            err
+                        );
+                    };
+                }
+            }
+        });
+
+        let blockchain = Arc::clone(blockchain_outer);
+        let mut stream = network.receive_from_all::<RequestAccountsSnapshotChunk>();
+        tokio::spawn(async move {
+            while let Some((msg, peer)) = stream.next().await {
+                trace!(
+                    "[REQUEST_ACCOUNTS_SNAPSHOT_CHUNK] chunk {} of snapshot {:?} received from {:?}",
+                    msg.chunk_index,
+                    msg.block_hash,
+                    peer.id()
+                );
+
+                if let Some(response) = msg.handle(&blockchain) {
+                    // Try to send the response, logging to debug if it fails
+                    if let Err(err) = peer.send(&response).await {
+                        log::debug!(
+                            "Failed to send RequestAccountsSnapshotChunk Response: {:?}",
+                            err
+                        );
+                    };
+                }
+            }
+        });
     }
 }
@@ -8,7 +8,10 @@ use block::Block;
 use hash::Blake2bHash;
 use network_interface::peer::Peer;
 use network_interface::request_response::{RequestError, RequestResponse};
+use nimiq_account::Account;
+use nimiq_keys::Address;
 use nimiq_subscription::Subscription;
+use nimiq_tree_primitives::accounts_proof::AccountsProof;
 
 use crate::messages::*;
 
@@ -35,6 +38,12 @@ pub struct ConsensusAgent<P: Peer> {
     block_requests: RequestResponse<P, RequestBlock, ResponseBlock>,
     missing_block_requests: RequestResponse<P, RequestMissingBlocks, ResponseBlocks>,
     head_requests: RequestResponse<P, RequestHead, HeadResponse>,
+    zkp_requests: RequestResponse<P, RequestZKP, ResponseZKP>,
+    accounts_proof_requests: RequestResponse<P, RequestAccountsProof, ResponseAccountsProof>,
+    transactions_proof_requests:
+        RequestResponse<P, RequestTransactionsProof, ResponseTransactionsProof>,
+    accounts_snapshot_chunk_requests:
+        RequestResponse<P, RequestAccountsSnapshotChunk, ResponseAccountsSnapshotChunk>,
 }
 
 impl<P: Peer> Debug for ConsensusAgent<P> {
@@ -55,6 +64,10 @@ impl<P: Peer> ConsensusAgent<P> {
         let block_requests = RequestResponse::new(Arc::clone(&peer), timeout);
         let missing_block_requests = RequestResponse::new(Arc::clone(&peer), timeout);
         let head_requests = RequestResponse::new(Arc::clone(&peer), timeout);
+        let zkp_requests = RequestResponse::new(Arc::clone(&peer), timeout);
+        let accounts_proof_requests = RequestResponse::new(Arc::clone(&peer), timeout);
+        let transactions_proof_requests = RequestResponse::new(Arc::clone(&peer), timeout);
+        let accounts_snapshot_chunk_requests = RequestResponse::new(Arc::clone(&peer), timeout);
 
         ConsensusAgent {
             peer,
@@ -68,6 +81,10 @@ impl<P: Peer> ConsensusAgent<P> {
             block_requests,
             missing_block_requests,
             head_requests,
+            zkp_requests,
+            accounts_proof_requests,
+            transactions_proof_requests,
+            accounts_snapshot_chunk_requests,
         }
     }
 
@@ -162,4 +179,66 @@ impl<P: Peer> ConsensusAgent<P> {
 
         result.map(|response_blocks| response_blocks.hash)
     }
+
+    pub async fn request_zkp(&self) -> Result<Option<ZKProof>, RequestError> {
+        let result = self
+            .zkp_requests
+            .request(RequestZKP {
+                request_identifier: 0, // will automatically be set at a later point
+            })
+            .await;
+
+        result.map(|response| response.proof)
+    }
+
+    pub async fn request_accounts_proof(
+        &self,
+        block_hash: Blake2bHash,
+        addresses: Vec<Address>,
+    ) -> Result<Option<AccountsProof<Account>>, RequestError> {
+        let result = self
+            .accounts_proof_requests
+            .request(RequestAccountsProof {
+                block_hash,
+                addresses,
+                request_identifier: 0, // will automatically be set at a later point
+            })
+            .await;
+
+        result.map(|response| response.proof)
+    }
+
+    pub async fn request_transactions_proof(
+        &self,
+        hashes: Vec<Blake2bHash>,
+        address: Option<Address>,
+    ) -> Result<Vec<TransactionsProof>, RequestError> {
+        let result = self
+            .transactions_proof_requests
+            .request(RequestTransactionsProof {
+                hashes,
+                address,
+                request_identifier: 0, // will automatically be set at a later point
+            })
+            .await;
+
+        result.map(|response| response.proofs)
+    }
+
+    pub async fn request_accounts_snapshot_chunk(
+        &self,
+        block_hash: Option<Blake2bHash>,
+        chunk_index: u32,
+    ) -> Result<Option<AccountsSnapshotChunk>, RequestError> {
+        let result = self
+            .accounts_snapshot_chunk_requests
+            .request(RequestAccountsSnapshotChunk {
+                block_hash,
+                chunk_index,
+                request_identifier: 0, // will automatically be set at a later point
+            })
+            .await;
+
+        result.map(|response| response.chunk)
+    }
 }
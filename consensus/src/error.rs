@@ -1,6 +1,7 @@
 use thiserror::Error;
 
-use blockchain::BlockchainError;
+use blockchain::{BlockchainError, PushError};
+use network_interface::request_response::RequestError;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -16,5 +17,23 @@ pub enum SyncError {
     NoValidSyncTarget,
 }
 
+#[derive(Debug, Error)]
+pub enum SnapshotSyncError {
+    #[error("Request failed: {0}")]
+    RequestError(#[from] RequestError),
+    #[error("Peer doesn't serve an accounts snapshot")]
+    NoSnapshot,
+    #[error("Snapshot block is not a successor of our election head")]
+    UnknownSnapshotBlock,
+    #[error("Peer sent an invalid election block")]
+    InvalidBlock,
+    #[error("Peer sent an invalid history chunk for epoch {0}")]
+    InvalidHistory(u32),
+    #[error("Peer sent an invalid snapshot chunk {0}")]
+    InvalidChunk(u32),
+    #[error("Failed to apply the snapshot: {0}")]
+    PushError(#[from] PushError),
+}
+
 #[derive(Debug, Error)]
 pub enum BlockQueueError {}
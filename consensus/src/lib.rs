@@ -26,9 +26,12 @@ extern crate pin_project;
 
 pub use consensus::{Consensus, ConsensusEvent, ConsensusProxy};
 pub use error::Error;
+pub use nano::{NanoConsensus, NanoConsensusProxy};
 
 pub mod consensus;
 pub mod consensus_agent;
 pub mod error;
 pub mod messages;
+pub mod nano;
 pub mod sync;
+pub mod zkp;
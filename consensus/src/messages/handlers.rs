@@ -1,9 +1,13 @@
 use crate::messages::*;
+use crate::zkp::ZKPStore;
 use block::Block;
-use blockchain::{AbstractBlockchain, Blockchain, Direction, CHUNK_SIZE};
+use blockchain::{AbstractBlockchain, Blockchain, Direction, HistoryFilter, CHUNK_SIZE};
+use database::ReadTransaction;
+use hash::Blake2bHash;
 use network_interface::message::ResponseMessage;
 use nimiq_genesis::NetworkInfo;
 use primitives::policy;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 /// This trait defines the behaviour when receiving a message and how to generate the response.
@@ -188,3 +192,165 @@ impl Handle<HeadResponse> for RequestHead {
         Some(response)
     }
 }
+
+impl RequestZKP {
+    /// Responds with the most recent proof from the given store. The store is only available if
+    /// this node is built with the ZKP prover, otherwise we respond without a proof.
+    pub fn handle_with_store(&self, store: Option<&ZKPStore>) -> ResponseZKP {
+        ResponseZKP {
+            proof: store.and_then(|store| store.get()),
+            request_identifier: self.get_request_identifier(),
+        }
+    }
+}
+
+impl Handle<ResponseAccountsProof> for RequestAccountsProof {
+    fn handle(&self, blockchain: &Arc<Blockchain>) -> Option<ResponseAccountsProof> {
+        // We only have the accounts tree for our current head, so we can't prove accounts against
+        // any other block.
+        let state = blockchain.state();
+        let proof = if state.head_hash == self.block_hash {
+            let txn = ReadTransaction::new(&blockchain.env);
+            Some(state.accounts.get_accounts_proof(&txn, &self.addresses))
+        } else {
+            None
+        };
+
+        let response = ResponseAccountsProof {
+            proof,
+            request_identifier: self.get_request_identifier(),
+        };
+        Some(response)
+    }
+}
+
+impl Handle<ResponseTransactionsProof> for RequestTransactionsProof {
+    fn handle(&self, blockchain: &Arc<Blockchain>) -> Option<ResponseTransactionsProof> {
+        // Only finalized transactions are proven, so we only need the macro head. Everything up to
+        // it can't be reverted anymore, so we don't need to hold the state lock while proving.
+        let (macro_head_hash, macro_head_number) = {
+            let state = blockchain.state();
+            (
+                state.macro_head_hash.clone(),
+                state.macro_info.head.block_number(),
+            )
+        };
+        let macro_epoch = policy::epoch_at(macro_head_number);
+
+        let txn = ReadTransaction::new(&blockchain.env);
+
+        let mut hashes = self.hashes.clone();
+
+        if let Some(address) = &self.address {
+            hashes.extend(blockchain.history_store.get_tx_hashes_by_address(
+                address,
+                &HistoryFilter::default(),
+                Self::MAX_TRANSACTIONS_BY_ADDRESS,
+                Some(&txn),
+            ));
+        }
+
+        // Group the transactions by epoch, since there is a history tree for each epoch. Unknown
+        // and not yet finalized transactions are ignored.
+        let mut hashes_by_epoch: BTreeMap<u32, Vec<Blake2bHash>> = BTreeMap::new();
+
+        for hash in hashes {
+            if let Some(ext_tx) = blockchain
+                .history_store
+                .get_ext_tx_by_hash(&hash, Some(&txn))
+                .first()
+            {
+                if ext_tx.block_number <= macro_head_number {
+                    hashes_by_epoch
+                        .entry(policy::epoch_at(ext_tx.block_number))
+                        .or_default()
+                        .push(hash);
+                }
+            }
+        }
+
+        let mut proofs = vec![];
+
+        for (epoch_number, mut hashes) in hashes_by_epoch {
+            hashes.sort();
+            hashes.dedup();
+
+            // The history tree of a finalized epoch has the history root of its election block.
+            // Transactions of the current epoch are proven against the history root of our macro
+            // head. Epochs that we fail to prove are left out of the response.
+            let proof = if epoch_number < macro_epoch {
+                blockchain
+                    .chain_store
+                    .get_block_at(policy::election_block_of(epoch_number), false, Some(&txn))
+                    .and_then(|block| {
+                        let proof = blockchain.history_store.prove(
+                            epoch_number,
+                            hashes.iter().collect(),
+                            Some(&txn),
+                        )?;
+                        Some(TransactionsProof {
+                            block_hash: block.hash(),
+                            proof,
+                        })
+                    })
+            } else {
+                blockchain
+                    .history_store
+                    .prove_at_block(
+                        epoch_number,
+                        macro_head_number,
+                        hashes.iter().collect(),
+                        Some(&txn),
+                    )
+                    .map(|proof| TransactionsProof {
+                        block_hash: macro_head_hash.clone(),
+                        proof,
+                    })
+            };
+
+            match proof {
+                Some(proof) => proofs.push(proof),
+                None => debug!("Failed to prove transactions of epoch {}", epoch_number),
+            }
+        }
+
+        // We always respond, possibly with an empty list of proofs.
+        let response = ResponseTransactionsProof {
+            proofs,
+            request_identifier: self.get_request_identifier(),
+        };
+        Some(response)
+    }
+}
+
+impl Handle<ResponseAccountsSnapshotChunk> for RequestAccountsSnapshotChunk {
+    fn handle(&self, blockchain: &Arc<Blockchain>) -> Option<ResponseAccountsSnapshotChunk> {
+        // We only serve the latest snapshot, and only if we keep snapshots at all.
+        let chunk = blockchain
+            .get_snapshot()
+            .and_then(|(snapshot_block, num_chunks)| {
+                let block_hash = snapshot_block.hash();
+                if self
+                    .block_hash
+                    .as_ref()
+                    .map_or(false, |hash| *hash != block_hash)
+                {
+                    return None;
+                }
+
+                let chunk = blockchain.get_snapshot_chunk(&block_hash, self.chunk_index)?;
+                Some(AccountsSnapshotChunk {
+                    block_hash,
+                    chunk_index: self.chunk_index,
+                    num_chunks,
+                    chunk,
+                })
+            });
+
+        let response = ResponseAccountsSnapshotChunk {
+            chunk,
+            request_identifier: self.get_request_identifier(),
+        };
+        Some(response)
+    }
+}
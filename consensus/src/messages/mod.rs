@@ -1,8 +1,14 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use beserial::{Deserialize, Serialize};
 use block::{Block, MacroBlock};
-use blockchain::HistoryTreeChunk;
+use blockchain::{HistoryTreeChunk, HistoryTreeProof};
 use hash::Blake2bHash;
 use network_interface::message::*;
+use nimiq_account::Account;
+use nimiq_keys::Address;
+use nimiq_nano_zkp::NanoProof;
+use nimiq_tree_primitives::accounts_proof::AccountsProof;
+use nimiq_tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
 use std::fmt::Debug;
 
 use crate::request_response;
@@ -16,6 +22,15 @@ The consensus module uses the following messages:
 201 RequestResponseMessage<BlockHashes>
 202 RequestResponseMessage<RequestEpoch>
 203 RequestResponseMessage<Epoch>
+...
+212 RequestResponseMessage<RequestZKP>
+213 RequestResponseMessage<ResponseZKP>
+214 RequestResponseMessage<RequestAccountsProof>
+215 RequestResponseMessage<ResponseAccountsProof>
+216 RequestResponseMessage<RequestTransactionsProof>
+217 RequestResponseMessage<ResponseTransactionsProof>
+218 RequestResponseMessage<RequestAccountsSnapshotChunk>
+219 RequestResponseMessage<ResponseAccountsSnapshotChunk>
 */
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -223,3 +238,158 @@ request_response!(HeadResponse);
 impl Message for HeadResponse {
     const TYPE_ID: u64 = 211;
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestZKP {
+    pub request_identifier: u32,
+}
+request_response!(RequestZKP);
+
+impl Message for RequestZKP {
+    const TYPE_ID: u64 = 212;
+}
+
+/// An election block together with a zero-knowledge proof that there is a valid chain between the
+/// genesis block and that block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZKProof {
+    pub block: MacroBlock,
+    // The serialized `NanoProof`.
+    #[beserial(len_type(u16))]
+    pub proof: Vec<u8>,
+}
+
+impl ZKProof {
+    pub fn new(block: MacroBlock, proof: &NanoProof) -> Self {
+        let mut bytes = Vec::new();
+        proof
+            .serialize(&mut bytes)
+            .expect("Couldn't serialize the zk proof!");
+
+        ZKProof {
+            block,
+            proof: bytes,
+        }
+    }
+
+    /// Deserializes the proof. Returns None if the proof is malformed.
+    pub fn nano_proof(&self) -> Option<NanoProof> {
+        NanoProof::deserialize(&mut &self.proof[..]).ok()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponseZKP {
+    pub proof: Option<ZKProof>,
+    pub request_identifier: u32,
+}
+request_response!(ResponseZKP);
+
+impl Message for ResponseZKP {
+    const TYPE_ID: u64 = 213;
+}
+
+/// Requests a proof for the given accounts against the state root of the block with the given
+/// hash. Since full nodes only keep the current accounts tree, this only succeeds if the block is
+/// the head of the peer's main chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestAccountsProof {
+    pub block_hash: Blake2bHash,
+    #[beserial(len_type(u16, limit = 128))]
+    pub addresses: Vec<Address>,
+    pub request_identifier: u32,
+}
+request_response!(RequestAccountsProof);
+
+impl Message for RequestAccountsProof {
+    const TYPE_ID: u64 = 214;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponseAccountsProof {
+    pub proof: Option<AccountsProof<Account>>,
+    pub request_identifier: u32,
+}
+request_response!(ResponseAccountsProof);
+
+impl Message for ResponseAccountsProof {
+    const TYPE_ID: u64 = 215;
+}
+
+/// Requests proofs of inclusion for transactions. The transactions are given by their hashes
+/// and/or by an address, in which case the most recent transactions of that address are proven.
+/// Unknown and not yet finalized transactions are left out of the response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestTransactionsProof {
+    #[beserial(len_type(u16, limit = 128))]
+    pub hashes: Vec<Blake2bHash>,
+    pub address: Option<Address>,
+    pub request_identifier: u32,
+}
+request_response!(RequestTransactionsProof);
+
+impl RequestTransactionsProof {
+    /// The maximum number of transactions that are proven for an address.
+    pub const MAX_TRANSACTIONS_BY_ADDRESS: u16 = 128;
+}
+
+impl Message for RequestTransactionsProof {
+    const TYPE_ID: u64 = 216;
+}
+
+/// A proof for transactions of a single epoch. The proof is against the history root of the
+/// block with the given hash. This is the election block for finalized epochs and the latest macro
+/// block for the current epoch, so only finalized transactions are proven.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionsProof {
+    pub block_hash: Blake2bHash,
+    pub proof: HistoryTreeProof,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResponseTransactionsProof {
+    #[beserial(len_type(u16))]
+    pub proofs: Vec<TransactionsProof>,
+    pub request_identifier: u32,
+}
+request_response!(ResponseTransactionsProof);
+
+impl Message for ResponseTransactionsProof {
+    const TYPE_ID: u64 = 217;
+}
+
+/// Requests a chunk of the snapshot of the accounts tree that the peer keeps at its latest
+/// election block. If no block hash is given, the chunk is taken from the peer's current snapshot,
+/// otherwise the request only succeeds if the snapshot was taken at the given block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestAccountsSnapshotChunk {
+    pub block_hash: Option<Blake2bHash>,
+    pub chunk_index: u32,
+    pub request_identifier: u32,
+}
+request_response!(RequestAccountsSnapshotChunk);
+
+impl Message for RequestAccountsSnapshotChunk {
+    const TYPE_ID: u64 = 218;
+}
+
+/// A chunk of a snapshot of the accounts tree. The chunk must be verified against the state root
+/// of the election block with the given hash.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountsSnapshotChunk {
+    pub block_hash: Blake2bHash,
+    pub chunk_index: u32,
+    pub num_chunks: u32,
+    pub chunk: AccountsTreeChunk<Account>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponseAccountsSnapshotChunk {
+    pub chunk: Option<AccountsSnapshotChunk>,
+    pub request_identifier: u32,
+}
+request_response!(ResponseAccountsSnapshotChunk);
+
+impl Message for ResponseAccountsSnapshotChunk {
+    const TYPE_ID: u64 = 219;
+}
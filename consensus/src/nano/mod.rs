@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::task::{Context, Poll};
+use futures::{future::BoxFuture, Future, FutureExt, StreamExt};
+use parking_lot::RwLock;
+use tokio::sync::broadcast::{
+    channel as broadcast, Receiver as BroadcastReceiver, Sender as BroadcastSender,
+};
+use tokio::time::{delay_for, timeout};
+
+use block::Block;
+use blockchain::{AbstractBlockchain, PushError, PushResult};
+use hash::Blake2bHash;
+use network_interface::network::{MsgAcceptance, Network, NetworkEvent, PubsubId};
+use network_interface::peer::Peer;
+use nimiq_nano_blockchain::NanoBlockchain;
+
+use crate::consensus::ConsensusEvent;
+use crate::consensus_agent::ConsensusAgent;
+use crate::messages::{BlockHashType, RequestBlockHashesFilter};
+use crate::sync::block_queue::BlockTopic;
+
+type Agents<P> = HashMap<Arc<P>, Arc<ConsensusAgent<P>>>;
+
+pub struct NanoConsensusProxy<N: Network> {
+    pub blockchain: Arc<RwLock<NanoBlockchain>>,
+    pub network: Arc<N>,
+    established_flag: Arc<AtomicBool>,
+}
+
+impl<N: Network> Clone for NanoConsensusProxy<N> {
+    fn clone(&self) -> Self {
+        Self {
+            blockchain: Arc::clone(&self.blockchain),
+            network: Arc::clone(&self.network),
+            established_flag: Arc::clone(&self.established_flag),
+        }
+    }
+}
+
+impl<N: Network> NanoConsensusProxy<N> {
+    pub fn is_established(&self) -> bool {
+        self.established_flag.load(Ordering::Acquire)
+    }
+}
+
+/// The consensus of a nano node. It first syncs to the most recent election block using a
+/// zero-knowledge proof requested from its peers, catches up to the latest checkpoint block and
+/// afterwards follows the chain by pushing the announced blocks.
+pub struct NanoConsensus<N: Network> {
+    pub blockchain: Arc<RwLock<NanoBlockchain>>,
+    pub network: Arc<N>,
+
+    sync_future: BoxFuture<'static, ()>,
+
+    events: BroadcastSender<ConsensusEvent<N>>,
+    established_flag: Arc<AtomicBool>,
+}
+
+impl<N: Network> NanoConsensus<N> {
+    /// Minimum number of peers for consensus to be established.
+    const MIN_PEERS_ESTABLISHED: usize = 1;
+
+    /// Maximum number of block hashes requested when catching up with the macro blocks.
+    const MAX_BLOCK_HASHES: u16 = 128;
+
+    /// Time to wait before retrying to sync, e.g. if there are not enough peers.
+    const SYNC_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+    /// Timeout after which the set of peers is updated again while waiting for block announcements.
+    const BLOCK_ANNOUNCEMENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    pub async fn from_network(blockchain: Arc<RwLock<NanoBlockchain>>, network: Arc<N>) -> Self {
+        Self::with_min_peers(blockchain, network, Self::MIN_PEERS_ESTABLISHED).await
+    }
+
+    pub async fn with_min_peers(
+        blockchain: Arc<RwLock<NanoBlockchain>>,
+        network: Arc<N>,
+        min_peers: usize,
+    ) -> Self {
+        let (events, _rx) = broadcast(256);
+        let established_flag = Arc::new(AtomicBool::new(false));
+
+        let sync_future = Self::sync(
+            Arc::clone(&blockchain),
+            Arc::clone(&network),
+            events.clone(),
+            Arc::clone(&established_flag),
+            min_peers,
+        )
+        .boxed();
+
+        NanoConsensus {
+            blockchain,
+            network,
+            sync_future,
+            events,
+            established_flag,
+        }
+    }
+
+    pub fn subscribe_events(&self) -> BroadcastReceiver<ConsensusEvent<N>> {
+        self.events.subscribe()
+    }
+
+    pub fn is_established(&self) -> bool {
+        self.established_flag.load(Ordering::Acquire)
+    }
+
+    pub fn proxy(&self) -> NanoConsensusProxy<N> {
+        NanoConsensusProxy {
+            blockchain: Arc::clone(&self.blockchain),
+            network: Arc::clone(&self.network),
+            established_flag: Arc::clone(&self.established_flag),
+        }
+    }
+
+    /// Drives the nano consensus. This never returns.
+    async fn sync(
+        blockchain: Arc<RwLock<NanoBlockchain>>,
+        network: Arc<N>,
+        events: BroadcastSender<ConsensusEvent<N>>,
+        established_flag: Arc<AtomicBool>,
+        min_peers: usize,
+    ) {
+        let (peers, mut peer_updates) = network.get_peer_updates();
+        let mut agents: Agents<N::PeerType> = peers
+            .into_iter()
+            .map(|peer| (Arc::clone(&peer), Arc::new(ConsensusAgent::new(peer))))
+            .collect();
+
+        let mut block_stream = network
+            .subscribe::<BlockTopic>(&BlockTopic::default())
+            .await
+            .unwrap();
+
+        loop {
+            // Keep track of our peers.
+            while let Ok(event) = peer_updates.try_recv() {
+                match event {
+                    NetworkEvent::PeerJoined(peer) => {
+                        let agent = Arc::new(ConsensusAgent::new(Arc::clone(&peer)));
+                        agents.insert(peer, agent);
+                    }
+                    NetworkEvent::PeerLeft(peer) => {
+                        agents.remove(&peer);
+                        events.send(ConsensusEvent::PeerLeft).ok(); // Ignore result.
+                    }
+                }
+            }
+
+            let established = established_flag.load(Ordering::Acquire);
+
+            if established && agents.is_empty() {
+                warn!("Lost consensus!");
+                established_flag.swap(false, Ordering::Release);
+                events.send(ConsensusEvent::Lost).ok(); // Ignore result.
+                continue;
+            }
+
+            if !established {
+                if agents.len() < min_peers {
+                    delay_for(Self::SYNC_RETRY_DELAY).await;
+                    continue;
+                }
+
+                let agents: Vec<_> = agents.values().cloned().collect();
+
+                if Self::sync_zkp(&blockchain, &agents).await
+                    && Self::sync_head(&blockchain, &agents).await
+                {
+                    info!("Consensus established, synced to the head of our peers.");
+                    established_flag.swap(true, Ordering::Release);
+                    events.send(ConsensusEvent::Established).ok(); // Ignore result.
+                } else {
+                    delay_for(Self::SYNC_RETRY_DELAY).await;
+                }
+                continue;
+            }
+
+            // Follow the chain by pushing the announced blocks.
+            let (block, pubsub_id) =
+                match timeout(Self::BLOCK_ANNOUNCEMENT_TIMEOUT, block_stream.next()).await {
+                    Ok(Some(item)) => item,
+                    Ok(None) => panic!("The block stream should never end"),
+                    Err(_) => continue,
+                };
+
+            let source = pubsub_id.propagation_source();
+            let agent = agents
+                .iter()
+                .find(|(peer, _)| peer.id() == source)
+                .map(|(_, agent)| Arc::clone(agent));
+
+            let acceptance = match Self::push_block(&blockchain, agent, block).await {
+                Ok(PushResult::Known) | Ok(PushResult::Ignored) | Err(PushError::Orphan) => {
+                    MsgAcceptance::Ignore
+                }
+                Ok(_) => MsgAcceptance::Accept,
+                Err(e) => {
+                    debug!("Failed to push announced block: {}", e);
+                    MsgAcceptance::Reject
+                }
+            };
+
+            if let Err(e) = network.validate_message(pubsub_id, acceptance).await {
+                error!("Network error while relaying block message: {}", e);
+            }
+        }
+    }
+
+    /// Requests a zero-knowledge proof from our peers and pushes the first one that brings us
+    /// beyond our current election block. Returns true if we are synced to the most recent
+    /// election block of at least one of our peers.
+    async fn sync_zkp(
+        blockchain: &Arc<RwLock<NanoBlockchain>>,
+        agents: &[Arc<ConsensusAgent<N::PeerType>>],
+    ) -> bool {
+        for agent in agents {
+            let zkp = match agent.request_zkp().await {
+                Ok(Some(zkp)) => zkp,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!("Failed to request zkp from {:?}: {}", agent.peer.id(), e);
+                    continue;
+                }
+            };
+
+            let election_number = blockchain.read().election_head.header.block_number;
+            if zkp.block.header.block_number <= election_number {
+                return true;
+            }
+
+            let proof = match zkp.nano_proof() {
+                Some(proof) => proof,
+                None => {
+                    debug!("Received malformed zkp from {:?}", agent.peer.id());
+                    continue;
+                }
+            };
+
+            match blockchain.write().push_zkp(Block::Macro(zkp.block), proof) {
+                Ok(_) => return true,
+                Err(e) => debug!("Failed to push zkp from {:?}: {}", agent.peer.id(), e),
+            }
+        }
+
+        false
+    }
+
+    /// Catches up with the macro blocks after our election block and then with the micro blocks up
+    /// to the head of one of our peers. Returns true if we reached that head.
+    async fn sync_head(
+        blockchain: &Arc<RwLock<NanoBlockchain>>,
+        agents: &[Arc<ConsensusAgent<N::PeerType>>],
+    ) -> bool {
+        for agent in agents {
+            let locators = vec![blockchain.read().election_head_hash()];
+
+            let block_hashes = match agent
+                .request_block_hashes(
+                    locators,
+                    Self::MAX_BLOCK_HASHES,
+                    RequestBlockHashesFilter::ElectionAndLatestCheckpoint,
+                )
+                .await
+            {
+                Ok(block_hashes) => block_hashes,
+                Err(e) => {
+                    debug!(
+                        "Failed to request block hashes from {:?}: {}",
+                        agent.peer.id(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if !Self::push_macro_blocks(blockchain, agent, block_hashes.hashes).await {
+                continue;
+            }
+
+            let head_hash = match agent.request_head().await {
+                Ok(head_hash) => head_hash,
+                Err(e) => {
+                    debug!("Failed to request head from {:?}: {}", agent.peer.id(), e);
+                    continue;
+                }
+            };
+
+            if head_hash == blockchain.read().head_hash() {
+                return true;
+            }
+
+            let locators = vec![blockchain.read().head_hash()];
+            let blocks = match agent.request_missing_blocks(head_hash, locators).await {
+                Ok(blocks) => blocks,
+                Err(e) => {
+                    debug!("Failed to request blocks from {:?}: {}", agent.peer.id(), e);
+                    continue;
+                }
+            };
+
+            if Self::push_blocks(blockchain, blocks) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Requests the given macro blocks from the peer and pushes them.
+    async fn push_macro_blocks(
+        blockchain: &Arc<RwLock<NanoBlockchain>>,
+        agent: &Arc<ConsensusAgent<N::PeerType>>,
+        hashes: Vec<(BlockHashType, Blake2bHash)>,
+    ) -> bool {
+        for (_, hash) in hashes {
+            if blockchain.read().contains(&hash, false) {
+                continue;
+            }
+
+            let block = match agent.request_block(hash).await {
+                Ok(Some(block)) if block.is_macro() => block,
+                Ok(_) => return false,
+                Err(e) => {
+                    debug!("Failed to request block from {:?}: {}", agent.peer.id(), e);
+                    return false;
+                }
+            };
+
+            if let Err(e) = blockchain.write().push_macro(block) {
+                debug!(
+                    "Failed to push macro block from {:?}: {}",
+                    agent.peer.id(),
+                    e
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Pushes the given blocks in order. Returns false if any of them was rejected.
+    fn push_blocks(blockchain: &Arc<RwLock<NanoBlockchain>>, blocks: Vec<Block>) -> bool {
+        let mut blockchain = blockchain.write();
+
+        for block in blocks {
+            if let Err(e) = blockchain.push(block) {
+                debug!("Failed to push block: {}", e);
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Pushes an announced block. If we don't know its predecessor, the missing blocks are
+    /// requested from the peer that announced it.
+    async fn push_block(
+        blockchain: &Arc<RwLock<NanoBlockchain>>,
+        agent: Option<Arc<ConsensusAgent<N::PeerType>>>,
+        block: Block,
+    ) -> Result<PushResult, PushError> {
+        let result = blockchain.write().push(block.clone());
+
+        match (result, agent) {
+            (Err(PushError::Orphan), Some(agent)) => {
+                let locators = vec![blockchain.read().head_hash()];
+                let blocks = agent
+                    .request_missing_blocks(block.hash(), locators)
+                    .await
+                    .map_err(|_| PushError::Orphan)?;
+
+                let mut blockchain = blockchain.write();
+                let mut result = Err(PushError::Orphan);
+                for block in blocks {
+                    result = blockchain.push(block);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                result
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+impl<N: Network> Future for NanoConsensus<N> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.sync_future.poll_unpin(cx).is_ready() {
+            panic!("The nano consensus should never complete");
+        }
+
+        Poll::Pending
+    }
+}
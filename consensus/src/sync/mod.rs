@@ -1,4 +1,5 @@
 pub mod block_queue;
 pub mod history;
 pub mod request_component;
+pub mod snapshot;
 mod sync_queue;
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use block::MacroBlock;
+use blockchain::{AbstractBlockchain, Blockchain, ExtendedTransaction, PushResult, CHUNK_SIZE};
+use network_interface::peer::Peer;
+use primitives::policy;
+use utils::math::CeilingDiv;
+
+use crate::consensus_agent::ConsensusAgent;
+use crate::error::SnapshotSyncError;
+use crate::messages::RequestBlockHashesFilter;
+
+/// The maximum number of election blocks that are requested between our election head and the
+/// block of the snapshot.
+const MAX_ELECTION_BLOCKS: u16 = 1000;
+
+/// Bootstraps the blockchain from the accounts snapshot that a peer keeps at its latest election
+/// block, instead of replaying the history since our election head. The election blocks up to the
+/// block of the snapshot and the histories of the transaction validity window before it are
+/// requested as well. Every chunk of the accounts tree is verified against the state root of the
+/// snapshot block as it is received, before the snapshot is applied with `push_snapshot`.
+/// Fails with `NoSnapshot` if the peer doesn't keep snapshots or took a new one in the meantime.
+pub async fn sync_snapshot<P: Peer>(
+    blockchain: &Arc<Blockchain>,
+    agent: &ConsensusAgent<P>,
+) -> Result<PushResult, SnapshotSyncError> {
+    // The first chunk tells us at which block the peer took its snapshot and how many chunks it
+    // consists of.
+    let first_chunk = agent
+        .request_accounts_snapshot_chunk(None, 0)
+        .await?
+        .ok_or(SnapshotSyncError::NoSnapshot)?;
+    let snapshot_hash = first_chunk.block_hash.clone();
+    let num_chunks = first_chunk.num_chunks;
+
+    // Request the election blocks following our election head up to the block of the snapshot.
+    let block_hashes = agent
+        .request_block_hashes(
+            vec![blockchain.election_head_hash()],
+            MAX_ELECTION_BLOCKS,
+            RequestBlockHashesFilter::ElectionOnly,
+        )
+        .await?;
+
+    let num_blocks = block_hashes
+        .hashes
+        .iter()
+        .position(|(_, hash)| *hash == snapshot_hash)
+        .ok_or(SnapshotSyncError::UnknownSnapshotBlock)?
+        + 1;
+
+    let mut election_blocks = Vec::with_capacity(num_blocks);
+    let mut history_lens = Vec::with_capacity(num_blocks);
+
+    for (_, hash) in block_hashes.hashes.into_iter().take(num_blocks) {
+        let epoch = agent.request_epoch(hash.clone()).await?;
+
+        if epoch.block.hash() != hash {
+            return Err(SnapshotSyncError::InvalidBlock);
+        }
+
+        election_blocks.push(epoch.block);
+        history_lens.push(epoch.history_len);
+    }
+
+    let snapshot_block = election_blocks.last().unwrap().clone();
+
+    // Request the histories of the transaction validity window. The histories of the epochs that
+    // we already know are taken from our own history store.
+    let snapshot_number = snapshot_block.header.block_number;
+    let mut histories = vec![];
+
+    for epoch_number in
+        Blockchain::snapshot_history_start(snapshot_number)..=policy::epoch_at(snapshot_number)
+    {
+        let index = election_blocks
+            .iter()
+            .position(|block| policy::epoch_at(block.header.block_number) == epoch_number);
+
+        let history = match index {
+            Some(i) => {
+                request_history(agent, epoch_number, &election_blocks[i], history_lens[i]).await?
+            }
+            None => blockchain
+                .history_store
+                .get_epoch_transactions(epoch_number, None),
+        };
+
+        histories.push(history);
+    }
+
+    // Request the remaining chunks of the snapshot and verify each of them.
+    let mut chunks = Vec::with_capacity(num_chunks as usize);
+    let mut next_chunk = Some(first_chunk);
+
+    for chunk_index in 0..num_chunks {
+        let mut snapshot_chunk = match next_chunk.take() {
+            Some(snapshot_chunk) => snapshot_chunk,
+            None => agent
+                .request_accounts_snapshot_chunk(Some(snapshot_hash.clone()), chunk_index)
+                .await?
+                .ok_or(SnapshotSyncError::NoSnapshot)?,
+        };
+
+        if snapshot_chunk.block_hash != snapshot_hash
+            || snapshot_chunk.chunk_index != chunk_index
+            || !Blockchain::verify_snapshot_chunk(
+                &mut snapshot_chunk.chunk,
+                &snapshot_block.header.state_root,
+            )
+        {
+            return Err(SnapshotSyncError::InvalidChunk(chunk_index));
+        }
+
+        chunks.push(snapshot_chunk.chunk);
+    }
+
+    info!(
+        "Received accounts snapshot at block #{} with {} chunks",
+        snapshot_number, num_chunks
+    );
+
+    Ok(blockchain.push_snapshot(election_blocks, histories, chunks)?)
+}
+
+/// Requests the history of the epoch of the given election block and verifies every chunk of it
+/// against the history root of the block.
+async fn request_history<P: Peer>(
+    agent: &ConsensusAgent<P>,
+    epoch_number: u32,
+    block: &MacroBlock,
+    history_len: u32,
+) -> Result<Vec<ExtendedTransaction>, SnapshotSyncError> {
+    let mut history = vec![];
+
+    for chunk_index in 0..(history_len as usize).ceiling_div(CHUNK_SIZE) {
+        let chunk = agent
+            .request_history_chunk(epoch_number, chunk_index)
+            .await?
+            .chunk
+            .ok_or(SnapshotSyncError::InvalidHistory(epoch_number))?;
+
+        if !chunk
+            .verify(block.header.history_root.clone(), history.len())
+            .unwrap_or(false)
+        {
+            return Err(SnapshotSyncError::InvalidHistory(epoch_number));
+        }
+
+        history.extend(chunk.history);
+    }
+
+    Ok(history)
+}
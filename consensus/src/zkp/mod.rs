@@ -0,0 +1,7 @@
+#[cfg(feature = "zkp-prover")]
+pub use prover::ZKPProver;
+pub use store::ZKPStore;
+
+#[cfg(feature = "zkp-prover")]
+mod prover;
+mod store;
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use thiserror::Error;
+use tokio::task::spawn_blocking;
+
+use block::{Block, MacroBlock};
+use blockchain::{AbstractBlockchain, Blockchain, BlockchainEvent};
+use nimiq_genesis::NetworkInfo;
+use nimiq_nano_primitives::{state_commitment, MacroBlock as NanoMacroBlock};
+use nimiq_nano_zkp::{NanoProof, NanoZKP, NanoZKPError};
+use primitives::policy;
+
+use crate::messages::ZKProof;
+use crate::zkp::ZKPStore;
+
+/// The reasons why the proof for an election block can't be created.
+#[derive(Debug, Error)]
+enum ProveError {
+    #[error("Election block without justification or validators")]
+    InvalidBlock,
+    #[error("Stored zk proof is malformed")]
+    MalformedProof,
+    #[error("Nano ZKP error: {0}")]
+    NanoZKP(#[from] NanoZKPError),
+}
+
+/// Creates a zero-knowledge proof for every election block on the main chain and persists it, so
+/// that it can be served to nano nodes. Each proof proves the whole chain from the genesis block
+/// up to its election block, so proofs are created one epoch after the other.
+///
+/// The proving keys must have been created (see `NanoZKP::setup`) in the working directory of the
+/// client. Note that creating a proof can easily take longer than an epoch.
+pub struct ZKPProver {
+    blockchain: Arc<Blockchain>,
+    store: Arc<ZKPStore>,
+}
+
+impl ZKPProver {
+    /// Creates a prover that persists its proofs to the given store, which is shared with the
+    /// request handlers of the consensus.
+    pub fn new(blockchain: Arc<Blockchain>, store: Arc<ZKPStore>) -> Self {
+        ZKPProver { blockchain, store }
+    }
+
+    /// Runs the prover. It first catches up with the current election block and then creates a
+    /// new proof whenever an epoch is finalized. This never returns.
+    pub async fn run(self) {
+        let mut events = self.blockchain.notifier.write().as_stream();
+
+        self.prove_missing_epochs().await;
+
+        while let Some(event) = events.next().await {
+            if let BlockchainEvent::EpochFinalized(_) = event {
+                self.prove_missing_epochs().await;
+            }
+        }
+    }
+
+    /// Creates the proofs for all election blocks after the last proven one, up to our current
+    /// election head. If the stored proof is malformed, it is removed and the proofs are created
+    /// again from the genesis block on.
+    async fn prove_missing_epochs(&self) {
+        let genesis_block = NetworkInfo::from_network_id(self.blockchain.network_id)
+            .genesis_block::<Block>()
+            .unwrap_macro();
+
+        loop {
+            let previous = self.store.get();
+
+            let previous_block_number = previous
+                .as_ref()
+                .map(|zkp| zkp.block.header.block_number)
+                .unwrap_or(genesis_block.header.block_number);
+
+            if previous_block_number >= self.blockchain.election_head().header.block_number {
+                return;
+            }
+
+            let block = match self.blockchain.get_block_at(
+                previous_block_number + policy::EPOCH_LENGTH,
+                true,
+                None,
+            ) {
+                Some(Block::Macro(block)) => block,
+                _ => {
+                    error!(
+                        "Missing election block after #{} for the zk proof",
+                        previous_block_number
+                    );
+                    return;
+                }
+            };
+
+            info!("Creating zk proof for block #{}", block.header.block_number);
+
+            let genesis_block = genesis_block.clone();
+            let block1 = block.clone();
+            let result =
+                spawn_blocking(move || Self::prove(&genesis_block, previous.as_ref(), block1))
+                    .await;
+
+            match result {
+                Ok(Ok(proof)) => {
+                    info!("Created zk proof for block #{}", block.header.block_number);
+                    self.store.put(&ZKProof::new(block, &proof));
+                }
+                Ok(Err(ProveError::MalformedProof)) => {
+                    warn!(
+                        "Stored zk proof for block #{} is malformed, proving again from genesis",
+                        previous_block_number
+                    );
+                    self.store.clear();
+                }
+                Ok(Err(e)) => {
+                    error!(
+                        "Failed to create zk proof for block #{}: {}",
+                        block.header.block_number, e
+                    );
+                    return;
+                }
+                Err(e) => {
+                    error!(
+                        "The zk prover panicked on block #{}: {}",
+                        block.header.block_number, e
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Creates the proof for an election block, given the proof for the previous election block.
+    /// If there is no previous proof, the previous election block is the genesis block.
+    fn prove(
+        genesis_block: &MacroBlock,
+        previous: Option<&ZKProof>,
+        block: MacroBlock,
+    ) -> Result<NanoProof, ProveError> {
+        let previous_block = previous.map(|zkp| &zkp.block).unwrap_or(genesis_block);
+
+        let initial_public_keys = Self::public_keys(previous_block)?;
+
+        let initial_header_hash = <[u8; 32]>::from(previous_block.hash());
+
+        let final_public_keys = Self::public_keys(&block)?;
+
+        let justification = block
+            .justification
+            .as_ref()
+            .ok_or(ProveError::InvalidBlock)?;
+
+        let nano_block = NanoMacroBlock {
+            block_number: block.header.block_number,
+            round_number: justification.round,
+            header_hash: <[u8; 32]>::from(block.hash()),
+            signature: justification.sig.signature.0.signature,
+            signer_bitmap: (0..policy::SLOTS as usize)
+                .map(|slot| justification.sig.signers.contains(slot))
+                .collect(),
+        };
+
+        // Every proof except the first one is created recursively from the previous proof.
+        let genesis_data = match previous {
+            Some(zkp) => {
+                let proof = zkp.nano_proof().ok_or(ProveError::MalformedProof)?;
+
+                let genesis_state = state_commitment(
+                    genesis_block.header.block_number,
+                    <[u8; 32]>::from(genesis_block.hash()),
+                    Self::public_keys(genesis_block)?,
+                );
+
+                Some((proof, genesis_state))
+            }
+            None => None,
+        };
+
+        Ok(NanoZKP::prove(
+            initial_public_keys,
+            initial_header_hash,
+            final_public_keys,
+            nano_block,
+            genesis_data,
+            false,
+            false,
+        )?)
+    }
+
+    /// Returns the public keys of the validators selected in the given election block.
+    fn public_keys(block: &MacroBlock) -> Result<Vec<ark_mnt6_753::G2Projective>, ProveError> {
+        Ok(block
+            .get_validators()
+            .ok_or(ProveError::InvalidBlock)?
+            .to_pks()
+            .iter()
+            .map(|pk| pk.public_key)
+            .collect())
+    }
+}
@@ -0,0 +1,45 @@
+use beserial::{Deserialize, Serialize};
+use database::{Database, Environment, ReadTransaction, WriteTransaction};
+
+use crate::messages::ZKProof;
+
+/// Persists the most recent zero-knowledge proof created by the ZKP prover, so that it survives
+/// restarts and can be served to nano nodes.
+#[derive(Debug)]
+pub struct ZKPStore {
+    env: Environment,
+    db: Database,
+}
+
+impl ZKPStore {
+    const ZKP_DB_NAME: &'static str = "ZKProof";
+    const ZKP_KEY: &'static str = "zkp";
+
+    pub fn new(env: Environment) -> Self {
+        let db = env.open_database(Self::ZKP_DB_NAME.to_string());
+        ZKPStore { env, db }
+    }
+
+    /// Gets the most recent proof. Returns None if no proof was stored yet.
+    pub fn get(&self) -> Option<ZKProof> {
+        let bytes: Vec<u8> = ReadTransaction::new(&self.env).get(&self.db, Self::ZKP_KEY)?;
+
+        ZKProof::deserialize_from_vec(&bytes).ok()
+    }
+
+    /// Stores a proof, replacing the previous one.
+    pub fn put(&self, proof: &ZKProof) {
+        let bytes = proof.serialize_to_vec();
+
+        let mut txn = WriteTransaction::new(&self.env);
+        txn.put(&self.db, Self::ZKP_KEY, &bytes);
+        txn.commit();
+    }
+
+    /// Removes the stored proof.
+    pub fn clear(&self) {
+        let mut txn = WriteTransaction::new(&self.env);
+        txn.remove(&self.db, Self::ZKP_KEY);
+        txn.commit();
+    }
+}
@@ -0,0 +1,137 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use beserial::Deserialize;
+use futures::task::{Context, Poll};
+use futures::Stream;
+
+use nimiq_block_production::{test_utils::*, BlockProducer};
+use nimiq_blockchain::{AbstractBlockchain, Blockchain, PushResult};
+use nimiq_bls::{KeyPair, SecretKey};
+use nimiq_consensus::consensus::Consensus;
+use nimiq_consensus::consensus_agent::ConsensusAgent;
+use nimiq_consensus::error::SnapshotSyncError;
+use nimiq_consensus::sync::request_component::HistorySyncStream;
+use nimiq_consensus::sync::snapshot::sync_snapshot;
+use nimiq_database::volatile::VolatileEnvironment;
+use nimiq_genesis::NetworkId;
+use nimiq_mempool::{Mempool, MempoolConfig};
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork, MockPeer};
+use nimiq_primitives::policy;
+
+pub struct MockHistorySyncStream<TNetwork: Network> {
+    network: Arc<TNetwork>,
+}
+
+impl<TNetwork: Network> HistorySyncStream<TNetwork::PeerType> for MockHistorySyncStream<TNetwork> {
+    fn add_peer(&self, _peer: Arc<TNetwork::PeerType>) {}
+}
+
+impl<TNetwork: Network> Stream for MockHistorySyncStream<TNetwork> {
+    type Item = Arc<ConsensusAgent<TNetwork::PeerType>>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Pending
+    }
+}
+
+/// Secret key of validator. Tests run with `network-primitives/src/genesis/unit-albatross.toml`
+const SECRET_KEY: &str =
+    "196ffdb1a8acc7cbd76a251aeac0600a1d68b3aba1eba823b5e4dc5dbdcdc730afa752c05ab4f6ef8518384ad514f403c5a088a22b17bf1bc14f8ff8decc2a512c0a200f68d7bdf5a319b30356fe8d1d75ef510aed7a8660968c216c328a0000";
+
+async fn new_consensus(hub: &mut MockHub, blockchain: Arc<Blockchain>) -> Consensus<MockNetwork> {
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default());
+    let net = Arc::new(hub.new_network());
+    Consensus::from_network(
+        blockchain.env.clone(),
+        blockchain,
+        mempool,
+        Arc::clone(&net),
+        Box::pin(MockHistorySyncStream {
+            network: Arc::clone(&net),
+        }),
+    )
+    .await
+}
+
+/// Creates a blockchain with a whole epoch, so that its head is an election block, and connects
+/// a second, empty blockchain to it. Returns both blockchains and an agent for the first one.
+async fn setup(
+    hub: &mut MockHub,
+    snapshots: bool,
+) -> (
+    Arc<Blockchain>,
+    Arc<Blockchain>,
+    ConsensusAgent<MockPeer>,
+    Vec<Consensus<MockNetwork>>,
+) {
+    let env1 = VolatileEnvironment::new(12).unwrap();
+    let mut blockchain1 = Blockchain::new(env1, NetworkId::UnitAlbatross).unwrap();
+    blockchain1.set_snapshots(snapshots);
+    let blockchain1 = Arc::new(blockchain1);
+
+    let keypair =
+        KeyPair::from(SecretKey::deserialize_from_vec(&hex::decode(SECRET_KEY).unwrap()).unwrap());
+    let producer = BlockProducer::new_without_mempool(Arc::clone(&blockchain1), keypair);
+    produce_macro_blocks(policy::BATCHES_PER_EPOCH as usize, &producer, &blockchain1);
+
+    let consensus1 = new_consensus(hub, Arc::clone(&blockchain1)).await;
+
+    let env2 = VolatileEnvironment::new(12).unwrap();
+    let blockchain2 = Arc::new(Blockchain::new(env2, NetworkId::UnitAlbatross).unwrap());
+    let consensus2 = new_consensus(hub, Arc::clone(&blockchain2)).await;
+
+    // Connect the two peers.
+    let mut stream = consensus2.network.subscribe_events();
+    consensus1.network.dial_mock(&consensus2.network);
+    stream.recv().await.unwrap();
+    tokio::time::delay_for(Duration::from_secs(1)).await;
+
+    let agent = ConsensusAgent::new(Arc::clone(&consensus2.network.get_peers()[0]));
+
+    (
+        blockchain1,
+        blockchain2,
+        agent,
+        vec![consensus1, consensus2],
+    )
+}
+
+#[tokio::test]
+async fn it_can_sync_a_snapshot_from_a_peer() {
+    let mut hub = MockHub::default();
+    let (blockchain1, blockchain2, agent, _consensus) = setup(&mut hub, true).await;
+
+    let result = sync_snapshot(&blockchain2, &agent).await.unwrap();
+    assert_eq!(result, PushResult::Extended);
+
+    assert_eq!(blockchain2.head_hash(), blockchain1.head_hash());
+    assert_eq!(
+        blockchain2.state().accounts.hash(None),
+        blockchain1.state().accounts.hash(None)
+    );
+    assert_eq!(
+        blockchain2
+            .history_store
+            .get_epoch_transactions(1, None)
+            .len(),
+        blockchain1
+            .history_store
+            .get_epoch_transactions(1, None)
+            .len()
+    );
+}
+
+#[tokio::test]
+async fn it_fails_if_the_peer_keeps_no_snapshot() {
+    let mut hub = MockHub::default();
+    let (_blockchain1, blockchain2, agent, _consensus) = setup(&mut hub, false).await;
+
+    assert!(matches!(
+        sync_snapshot(&blockchain2, &agent).await,
+        Err(SnapshotSyncError::NoSnapshot)
+    ));
+    assert_eq!(blockchain2.block_number(), 0);
+}
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use beserial::Deserialize;
+use futures::{Stream, StreamExt};
+
+use futures::task::{Context, Poll};
+use nimiq_block_production::{test_utils::*, BlockProducer};
+use nimiq_blockchain::{AbstractBlockchain, Blockchain};
+use nimiq_bls::{KeyPair, SecretKey};
+use nimiq_consensus::consensus::Consensus;
+use nimiq_consensus::consensus_agent::ConsensusAgent;
+use nimiq_consensus::sync::request_component::HistorySyncStream;
+use nimiq_database::volatile::VolatileEnvironment;
+use nimiq_genesis::NetworkId;
+use nimiq_hash::Blake2bHash;
+use nimiq_mempool::{Mempool, MempoolConfig};
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_primitives::policy;
+use std::pin::Pin;
+
+pub struct MockHistorySyncStream<TNetwork: Network> {
+    network: Arc<TNetwork>,
+}
+
+impl<TNetwork: Network> HistorySyncStream<TNetwork::PeerType> for MockHistorySyncStream<TNetwork> {
+    fn add_peer(&self, _peer: Arc<TNetwork::PeerType>) {}
+}
+
+impl<TNetwork: Network> Stream for MockHistorySyncStream<TNetwork> {
+    type Item = Arc<ConsensusAgent<TNetwork::PeerType>>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Pending
+    }
+}
+
+/// Secret key of validator. Tests run with `network-primitives/src/genesis/unit-albatross.toml`
+const SECRET_KEY: &str =
+    "196ffdb1a8acc7cbd76a251aeac0600a1d68b3aba1eba823b5e4dc5dbdcdc730afa752c05ab4f6ef8518384ad514f403c5a088a22b17bf1bc14f8ff8decc2a512c0a200f68d7bdf5a319b30356fe8d1d75ef510aed7a8660968c216c328a0000";
+
+async fn new_consensus(
+    hub: &mut MockHub,
+    blockchain: Arc<Blockchain>,
+    mempool: Arc<Mempool>,
+) -> Consensus<MockNetwork> {
+    let net = Arc::new(hub.new_network());
+    Consensus::from_network(
+        blockchain.env.clone(),
+        blockchain,
+        mempool,
+        Arc::clone(&net),
+        Box::pin(MockHistorySyncStream {
+            network: Arc::clone(&net),
+        }),
+    )
+    .await
+}
+
+fn epoch_tx_hashes(blockchain: &Blockchain, epoch_number: u32) -> Vec<Blake2bHash> {
+    blockchain
+        .history_store
+        .get_epoch_transactions(epoch_number, None)
+        .iter()
+        .map(|ext_tx| ext_tx.tx_hash())
+        .collect()
+}
+
+#[tokio::test]
+async fn it_proves_finalized_transactions() {
+    let mut hub = MockHub::default();
+
+    // Setup first peer.
+    let env1 = VolatileEnvironment::new(10).unwrap();
+    let blockchain1 = Arc::new(Blockchain::new(env1, NetworkId::UnitAlbatross).unwrap());
+    let mempool1 = Mempool::new(Arc::clone(&blockchain1), MempoolConfig::default());
+
+    let keypair =
+        KeyPair::from(SecretKey::deserialize_from_vec(&hex::decode(SECRET_KEY).unwrap()).unwrap());
+    let producer = BlockProducer::new(Arc::clone(&blockchain1), Arc::clone(&mempool1), keypair);
+
+    // Produce a whole epoch and one checkpoint block, followed by micro blocks that aren't
+    // finalized yet.
+    let num_macro_blocks = (policy::BATCHES_PER_EPOCH + 1) as usize;
+    produce_macro_blocks(num_macro_blocks, &producer, &blockchain1);
+    fill_micro_blocks(&producer, &blockchain1);
+
+    let consensus1 = new_consensus(&mut hub, Arc::clone(&blockchain1), mempool1).await;
+
+    // Setup second peer.
+    let env2 = VolatileEnvironment::new(10).unwrap();
+    let blockchain2 = Arc::new(Blockchain::new(env2, NetworkId::UnitAlbatross).unwrap());
+    let mempool2 = Mempool::new(Arc::clone(&blockchain2), MempoolConfig::default());
+    let consensus2 = new_consensus(&mut hub, blockchain2, mempool2).await;
+
+    // Connect the two peers.
+    let mut stream = consensus2.network.subscribe_events();
+    consensus1.network.dial_mock(&consensus2.network);
+    stream.recv().await.unwrap();
+    tokio::time::delay_for(Duration::from_secs(1)).await;
+
+    let agent = ConsensusAgent::new(Arc::clone(&consensus2.network.get_peers()[0]));
+
+    // Unknown transactions are ignored, but the request is still answered.
+    let proofs = agent
+        .request_transactions_proof(vec![Blake2bHash::default()], None)
+        .await
+        .expect("Should yield a response");
+    assert!(proofs.is_empty());
+
+    // Transactions of a finalized epoch are proven against its election block.
+    let hashes = epoch_tx_hashes(&blockchain1, 1);
+    assert!(!hashes.is_empty());
+
+    let proofs = agent
+        .request_transactions_proof(hashes.clone(), None)
+        .await
+        .expect("Should yield a response");
+    assert_eq!(proofs.len(), 1);
+
+    let election_block = blockchain1.election_head();
+    assert_eq!(proofs[0].block_hash, election_block.hash());
+    assert_eq!(proofs[0].proof.history.len(), hashes.len());
+    assert!(proofs[0]
+        .proof
+        .verify(election_block.header.history_root.clone())
+        .unwrap());
+
+    // Transactions of the current epoch are proven against the macro head, not against the head.
+    let hashes = epoch_tx_hashes(&blockchain1, 2);
+    assert!(!hashes.is_empty());
+
+    let proofs = agent
+        .request_transactions_proof(hashes, None)
+        .await
+        .expect("Should yield a response");
+    assert_eq!(proofs.len(), 1);
+
+    let macro_head = blockchain1.macro_head();
+    assert_ne!(macro_head.hash(), blockchain1.head_hash());
+    assert_eq!(proofs[0].block_hash, macro_head.hash());
+    assert!(proofs[0]
+        .proof
+        .verify(macro_head.header.history_root.clone())
+        .unwrap());
+}
@@ -2,7 +2,8 @@ use std::borrow::Cow;
 use std::io;
 
 use beserial::{Deserialize, Serialize};
-use nimiq_account::{AccountsTreeLeave, Receipts};
+use nimiq_account::{Account, AccountsTreeLeave, Receipts};
+use nimiq_tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
 use nimiq_tree_primitives::accounts_tree_node::AccountsTreeNode;
 use nimiq_tree_primitives::address_nibbles::AddressNibbles;
 
@@ -36,6 +37,26 @@ impl<A: AccountsTreeLeave> FromDatabaseValue for AccountsTreeNode<A> {
     }
 }
 
+impl<A: AccountsTreeLeave> IntoDatabaseValue for AccountsTreeChunk<A> {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize(&self, &mut bytes).unwrap();
+    }
+}
+
+impl<A: AccountsTreeLeave> FromDatabaseValue for AccountsTreeChunk<A> {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut cursor = io::Cursor::new(bytes);
+        Ok(Deserialize::deserialize(&mut cursor)?)
+    }
+}
+
 impl IntoDatabaseValue for Receipts {
     fn database_byte_size(&self) -> usize {
         self.serialized_size()
@@ -55,3 +76,23 @@ impl FromDatabaseValue for Receipts {
         Ok(Deserialize::deserialize(&mut cursor)?)
     }
 }
+
+impl IntoDatabaseValue for Account {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for Account {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut cursor = io::Cursor::new(bytes);
+        Ok(Deserialize::deserialize(&mut cursor)?)
+    }
+}
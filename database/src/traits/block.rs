@@ -1,7 +1,7 @@
 use std::io;
 
 use beserial::{Deserialize, Serialize};
-use nimiq_block::{Block, MicroBlock};
+use nimiq_block::{Block, MacroHeader, MicroBlock};
 
 use crate::{FromDatabaseValue, IntoDatabaseValue};
 
@@ -44,3 +44,23 @@ impl FromDatabaseValue for MicroBlock {
         Ok(Deserialize::deserialize(&mut cursor)?)
     }
 }
+
+impl IntoDatabaseValue for MacroHeader {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for MacroHeader {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut cursor = io::Cursor::new(bytes);
+        Ok(Deserialize::deserialize(&mut cursor)?)
+    }
+}
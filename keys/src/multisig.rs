@@ -20,6 +20,11 @@ pub struct RandomSecret(Scalar);
 
 impl RandomSecret {
     pub const SIZE: usize = 32;
+
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; RandomSecret::SIZE] {
+        self.0.to_bytes()
+    }
 }
 
 impl From<[u8; RandomSecret::SIZE]> for RandomSecret {
@@ -242,6 +247,20 @@ impl KeyPair {
 }
 
 impl PublicKey {
+    /// Aggregates the `public_keys` of a set of signers into the public key that verifies their
+    /// combined signature. The order of the keys matters, signers have to agree on it.
+    pub fn aggregate(public_keys: &[PublicKey]) -> PublicKey {
+        let public_keys_hash = hash_public_keys(public_keys);
+        let delinearized_pk_sum: EdwardsPoint = public_keys
+            .iter()
+            .map(|public_key| public_key.delinearize(&public_keys_hash))
+            .sum();
+
+        let mut public_key_bytes: [u8; PublicKey::SIZE] = [0u8; PublicKey::SIZE];
+        public_key_bytes.copy_from_slice(delinearized_pk_sum.compress().as_bytes());
+        PublicKey::from(public_key_bytes)
+    }
+
     fn to_edwards_point(&self) -> Option<EdwardsPoint> {
         let mut bits: [u8; PublicKey::SIZE] = [0u8; PublicKey::SIZE];
         bits.copy_from_slice(&self.as_bytes()[..PublicKey::SIZE]);
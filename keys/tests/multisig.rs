@@ -371,6 +371,15 @@ fn it_can_aggregate_public_keys() {
     }
 }
 
+#[test]
+fn it_can_aggregate_public_keys_without_signing() {
+    for vector in VECTORS.iter() {
+        let test = TestVector::from_str(vector);
+
+        assert_eq!(PublicKey::aggregate(&test.pub_keys), test.agg_pub_key);
+    }
+}
+
 #[test]
 fn it_can_finalize_signatures() {
     for vector in VECTORS.iter() {
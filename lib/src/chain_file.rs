@@ -0,0 +1,114 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::time::Instant;
+
+use nimiq_blockchain::{AbstractBlockchain, Blockchain};
+use nimiq_primitives::policy;
+
+use crate::config::config::{ClientConfig, ConsensusConfig};
+use crate::error::Error;
+
+/// Exports the blocks from `first_block` up to `last_block` (or the head if None) of the chain
+/// stored in the configured database to a chain file.
+pub fn export_chain(
+    config: ClientConfig,
+    path: &Path,
+    first_block: u32,
+    last_block: Option<u32>,
+) -> Result<(), Error> {
+    let blockchain = open_blockchain(config)?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    let num_blocks = blockchain.export_chain(&mut writer, first_block, last_block)?;
+
+    log::info!("Exported {} blocks to {}", num_blocks, path.display());
+    Ok(())
+}
+
+/// Imports a chain file into the chain stored in the configured database. All blocks are fully
+/// verified.
+pub fn import_chain(config: ClientConfig, path: &Path) -> Result<(), Error> {
+    let blockchain = open_blockchain(config)?;
+
+    log::info!(
+        "Importing blocks from {} - Head: #{}",
+        path.display(),
+        blockchain.block_number()
+    );
+
+    let start = Instant::now();
+    let mut reader = BufReader::new(File::open(path)?);
+    let num_blocks = blockchain.import_chain(&mut reader, |block_number, _| {
+        if policy::is_macro_block_at(block_number) {
+            log::info!(
+                "Imported up to block #{} ({:.1}s)",
+                block_number,
+                start.elapsed().as_secs_f64()
+            );
+        }
+    })?;
+
+    log::info!(
+        "Imported {} blocks in {:.1}s - Head: #{}",
+        num_blocks,
+        start.elapsed().as_secs_f64(),
+        blockchain.block_number()
+    );
+    Ok(())
+}
+
+/// Exports the latest snapshot of the accounts tree, together with the election blocks needed to
+/// verify it, to a snapshot file.
+pub fn export_snapshot(config: ClientConfig, path: &Path) -> Result<(), Error> {
+    let blockchain = open_blockchain(config)?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    let num_chunks = blockchain.export_snapshot(&mut writer)?;
+
+    log::info!(
+        "Exported snapshot with {} chunks to {}",
+        num_chunks,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Imports a snapshot file into the chain stored in the configured database. The election blocks
+/// are verified and the accounts tree is replaced with the one in the snapshot.
+pub fn import_snapshot(config: ClientConfig, path: &Path) -> Result<(), Error> {
+    let blockchain = open_blockchain(config)?;
+
+    let start = Instant::now();
+    let mut reader = BufReader::new(File::open(path)?);
+    let result = blockchain.import_snapshot(&mut reader)?;
+
+    log::info!(
+        "Imported snapshot in {:.1}s ({:?}) - Head: #{}",
+        start.elapsed().as_secs_f64(),
+        result,
+        blockchain.block_number()
+    );
+    Ok(())
+}
+
+/// Opens the blockchain without connecting to the network.
+fn open_blockchain(config: ClientConfig) -> Result<Blockchain, Error> {
+    if config.consensus == ConsensusConfig::Nano {
+        return Err(Error::config_error(
+            "Chain files can't be used with nano consensus",
+        ));
+    }
+
+    let archive = config.database.archive;
+    let snapshots = config.database.snapshots;
+    let environment =
+        config
+            .storage
+            .database(config.network_id, config.consensus, config.database)?;
+    let mut blockchain = Blockchain::new(environment, config.network_id)?;
+    blockchain.set_archive(archive);
+    blockchain.set_snapshots(snapshots);
+
+    Ok(blockchain)
+}
@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
+use parking_lot::RwLock;
+
 use nimiq_block::Block;
 use nimiq_blockchain::{AbstractBlockchain, Blockchain};
-use nimiq_consensus::{Consensus as AbstractConsensus, ConsensusProxy as AbstractConsensusProxy};
+use nimiq_consensus::{
+    Consensus as AbstractConsensus, ConsensusProxy as AbstractConsensusProxy,
+    NanoConsensus as AbstractNanoConsensus, NanoConsensusProxy as AbstractNanoConsensusProxy,
+};
 use nimiq_database::Environment;
 use nimiq_genesis::NetworkInfo;
 use nimiq_mempool::Mempool;
+use nimiq_nano_blockchain::NanoBlockchain;
 use nimiq_network_interface::network::Network as NetworkInterface;
 use nimiq_network_libp2p::{
     discovery::peer_contacts::{PeerContact, Services},
@@ -13,21 +19,29 @@ use nimiq_network_libp2p::{
 };
 use nimiq_utils::time::OffsetTime;
 
+#[cfg(feature = "zkp-prover")]
+use nimiq_consensus::zkp::ZKPProver;
 #[cfg(feature = "validator")]
-use nimiq_validator::validator::Validator as AbstractValidator;
+use nimiq_validator::validator::{
+    Validator as AbstractValidator, ValidatorProxy as AbstractValidatorProxy,
+};
 #[cfg(feature = "validator")]
 use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
 #[cfg(feature = "wallet")]
 use nimiq_wallet::WalletStore;
 
-use crate::config::config::ClientConfig;
+use crate::config::config::{ClientConfig, NetworkConfig as ClientNetworkConfig};
 use crate::error::Error;
 use nimiq_consensus::sync::history::HistorySync;
 
 /// Alias for the Consensus and Validator specialized over libp2p network
 pub type Consensus = AbstractConsensus<Network>;
 pub type ConsensusProxy = AbstractConsensusProxy<Network>;
+pub type NanoConsensus = AbstractNanoConsensus<Network>;
+pub type NanoConsensusProxy = AbstractNanoConsensusProxy<Network>;
 pub type Validator = AbstractValidator<Network, ValidatorNetworkImpl<Network>>;
+#[cfg(feature = "validator")]
+pub type ValidatorProxy = AbstractValidatorProxy<Network>;
 
 /// Holds references to the relevant structs. This is then Arc'd in `Client` and a nice API is
 /// exposed.
@@ -57,44 +71,7 @@ impl ClientInner {
     async fn from_config(
         config: ClientConfig,
     ) -> Result<(Self, Consensus, Option<Validator>), Error> {
-        // Get network info (i.e. which specific blokchain we're on)
-        if !config.network_id.is_albatross() {
-            return Err(Error::config_error(&format!(
-                "{} is not compatible with Albatross",
-                config.network_id
-            )));
-        }
-        let network_info = NetworkInfo::from_network_id(config.network_id);
-
-        // Initialize clock
-        let time = Arc::new(OffsetTime::new());
-
-        // Load identity keypair from file store
-        let identity_keypair = config.storage.identity_keypair()?;
-        log::info!("Identity public key: {:?}", identity_keypair.public());
-
-        // Generate peer contact from identity keypair and services/protocols
-        let mut peer_contact = PeerContact::new(
-            config.network.listen_addresses.clone(),
-            identity_keypair.public(),
-            Services::all(), // TODO
-            None,
-        );
-        peer_contact.set_current_time();
-
-        // Setup libp2p network
-        let mut network_config = NetworkConfig::new(
-            identity_keypair,
-            peer_contact,
-            network_info.genesis_hash().clone(),
-        );
-        if let Some(min_peers) = config.network.min_peers {
-            network_config.min_peers = min_peers;
-        }
-
-        log::debug!("listen_addresses = {:?}", config.network.listen_addresses);
-
-        let network = Arc::new(Network::new(Arc::clone(&time), network_config).await);
+        let network = init_network(&config).await?;
 
         // Start buffering network events as early as possible
         let nw_events = network.subscribe_events();
@@ -104,11 +81,16 @@ impl ClientInner {
         let validator_key = config.storage.validator_key()?;
 
         // Open database
+        let archive = config.database.archive;
+        let snapshots = config.database.snapshots;
         let environment =
             config
                 .storage
                 .database(config.network_id, config.consensus, config.database)?;
-        let blockchain = Arc::new(Blockchain::new(environment.clone(), config.network_id).unwrap());
+        let mut blockchain = Blockchain::new(environment.clone(), config.network_id).unwrap();
+        blockchain.set_archive(archive);
+        blockchain.set_snapshots(snapshots);
+        let blockchain = Arc::new(blockchain);
         let mempool = Mempool::new(Arc::clone(&blockchain), config.mempool);
 
         // Open wallet
@@ -126,16 +108,7 @@ impl ClientInner {
         )
         .await;
 
-        // Tell the network to listen on the given addresses
-        network
-            .listen_on_addresses(config.network.listen_addresses)
-            .await;
-
-        // Tell the network to connect to seed nodes
-        for seed in &config.network.seeds {
-            log::debug!("Dialing seed: {:?}", seed);
-            network.dial_address(seed.address.clone()).await?;
-        }
+        connect_network(&network, &config.network).await?;
 
         #[cfg(feature = "validator")]
         let validator = {
@@ -210,6 +183,67 @@ impl ClientInner {
     }
 }
 
+/// Sets up the libp2p network for the network ID given in the config.
+async fn init_network(config: &ClientConfig) -> Result<Arc<Network>, Error> {
+    // Get network info (i.e. which specific blokchain we're on)
+    if !config.network_id.is_albatross() {
+        return Err(Error::config_error(&format!(
+            "{} is not compatible with Albatross",
+            config.network_id
+        )));
+    }
+    let network_info = NetworkInfo::from_network_id(config.network_id);
+
+    // Initialize clock
+    let time = Arc::new(OffsetTime::new());
+
+    // Load identity keypair from file store
+    let identity_keypair = config.storage.identity_keypair()?;
+    log::info!("Identity public key: {:?}", identity_keypair.public());
+
+    // Generate peer contact from identity keypair and services/protocols
+    let mut peer_contact = PeerContact::new(
+        config.network.listen_addresses.clone(),
+        identity_keypair.public(),
+        Services::all(), // TODO
+        None,
+    );
+    peer_contact.set_current_time();
+
+    // Setup libp2p network
+    let mut network_config = NetworkConfig::new(
+        identity_keypair,
+        peer_contact,
+        network_info.genesis_hash().clone(),
+    );
+    if let Some(min_peers) = config.network.min_peers {
+        network_config.min_peers = min_peers;
+    }
+
+    log::debug!("listen_addresses = {:?}", config.network.listen_addresses);
+
+    Ok(Arc::new(Network::new(time, network_config).await))
+}
+
+/// Tells the network to listen on the configured addresses and to connect to the seed nodes.
+async fn connect_network(
+    network: &Arc<Network>,
+    config: &ClientNetworkConfig,
+) -> Result<(), Error> {
+    // Tell the network to listen on the given addresses
+    network
+        .listen_on_addresses(config.listen_addresses.clone())
+        .await;
+
+    // Tell the network to connect to seed nodes
+    for seed in &config.seeds {
+        log::debug!("Dialing seed: {:?}", seed);
+        network.dial_address(seed.address.clone()).await?;
+    }
+
+    Ok(())
+}
+
 /// Entry point for the Nimiq client API.
 ///
 /// This client object abstracts a complete Nimiq client. Many internal objects are exposed:
@@ -232,15 +266,40 @@ pub struct Client {
     inner: Arc<ClientInner>,
     consensus: Option<Consensus>,
     validator: Option<Validator>,
+    #[cfg(feature = "validator")]
+    validator_proxy: Option<ValidatorProxy>,
+    #[cfg(feature = "zkp-prover")]
+    zkp_prover: Option<ZKPProver>,
 }
 
 impl Client {
     pub async fn from_config(config: ClientConfig) -> Result<Self, Error> {
+        #[cfg(feature = "zkp-prover")]
+        let enable_zkp_prover = config.zkp_prover;
+
         let (inner, consensus, validator) = ClientInner::from_config(config).await?;
+
+        #[cfg(feature = "zkp-prover")]
+        let zkp_prover = if enable_zkp_prover {
+            Some(ZKPProver::new(
+                Arc::clone(&consensus.blockchain),
+                Arc::clone(&consensus.zkp_store),
+            ))
+        } else {
+            None
+        };
+
+        #[cfg(feature = "validator")]
+        let validator_proxy = validator.as_ref().map(|validator| validator.proxy());
+
         Ok(Client {
             inner: Arc::new(inner),
             consensus: Some(consensus),
             validator,
+            #[cfg(feature = "validator")]
+            validator_proxy,
+            #[cfg(feature = "zkp-prover")]
+            zkp_prover,
         })
     }
 
@@ -284,8 +343,86 @@ impl Client {
         self.validator.take()
     }
 
+    /// Returns a reference to the *Validator proxy* or `None`. This is still available after the
+    /// validator was taken.
+    #[cfg(feature = "validator")]
+    pub fn validator_proxy(&self) -> Option<ValidatorProxy> {
+        self.validator_proxy.clone()
+    }
+
+    /// Returns the *ZKP prover* or `None`.
+    #[cfg(feature = "zkp-prover")]
+    pub fn zkp_prover(&mut self) -> Option<ZKPProver> {
+        self.zkp_prover.take()
+    }
+
     /// Returns the database environment.
     pub fn environment(&self) -> Environment {
         self.inner.environment.clone()
     }
 }
+
+/// Entry point for a Nimiq nano client. A nano client syncs using a zero-knowledge proof and then
+/// only follows the head of the chain. It doesn't have a mempool, wallet or validator.
+pub struct NanoClient {
+    environment: Environment,
+    network: Arc<Network>,
+    consensus_proxy: NanoConsensusProxy,
+    consensus: Option<NanoConsensus>,
+}
+
+impl NanoClient {
+    pub async fn from_config(config: ClientConfig) -> Result<Self, Error> {
+        let network = init_network(&config).await?;
+
+        // Open database
+        let environment =
+            config
+                .storage
+                .database(config.network_id, config.consensus, config.database)?;
+        let blockchain = Arc::new(RwLock::new(NanoBlockchain::new_persistent(
+            environment.clone(),
+            config.network_id,
+        )));
+
+        let consensus = NanoConsensus::from_network(blockchain, Arc::clone(&network)).await;
+
+        connect_network(&network, &config.network).await?;
+
+        Ok(NanoClient {
+            environment,
+            network,
+            consensus_proxy: consensus.proxy(),
+            consensus: Some(consensus),
+        })
+    }
+
+    pub fn consensus(&mut self) -> Option<NanoConsensus> {
+        self.consensus.take()
+    }
+
+    /// Returns a reference to the *Nano consensus proxy*.
+    pub fn consensus_proxy(&self) -> NanoConsensusProxy {
+        self.consensus_proxy.clone()
+    }
+
+    /// Returns a reference to the *Network* stack
+    pub fn network(&self) -> Arc<Network> {
+        Arc::clone(&self.network)
+    }
+
+    /// Returns a reference to the nano blockchain
+    pub fn blockchain(&self) -> Arc<RwLock<NanoBlockchain>> {
+        Arc::clone(&self.consensus_proxy.blockchain)
+    }
+
+    /// Returns the blockchain head
+    pub fn blockchain_head(&self) -> Block {
+        self.consensus_proxy.blockchain.read().head()
+    }
+
+    /// Returns the database environment.
+    pub fn environment(&self) -> Environment {
+        self.environment.clone()
+    }
+}
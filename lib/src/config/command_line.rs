@@ -10,7 +10,7 @@ use crate::config::config_file::ConsensusType;
 
 /*lazy_static! {
     static ref VALID_LOG_LEVELS: [&'static str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
-    static ref VALID_CONSENSUS_TYPES: [&'static str; 2] = ["full", "macro-sync"];
+    static ref VALID_CONSENSUS_TYPES: [&'static str; 3] = ["full", "macro-sync", "nano"];
 }*/
 
 #[derive(Debug, StructOpt)]
@@ -53,7 +53,7 @@ pub struct CommandLine {
     #[structopt(long)]
     pub passive: bool,
 
-    /// Configure consensus type, one of full (default), or macro-sync
+    /// Configure consensus type, one of full (default), macro-sync, or nano
     ///
     /// # Examples
     ///
@@ -70,6 +70,70 @@ pub struct CommandLine {
     ///
     #[structopt(long)]
     pub network: Option<NetworkId>,
+
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Command {
+    /// Export the blocks of the chain to a file, without connecting to the network.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client export-chain chain.bin --from 1 --to 1000`
+    ///
+    ExportChain {
+        /// The file to write the blocks to.
+        file: PathBuf,
+
+        /// The number of the first block to export. The genesis block is never exported.
+        #[structopt(long, default_value = "1")]
+        from: u32,
+
+        /// The number of the last block to export. Default: The head of the chain
+        #[structopt(long)]
+        to: Option<u32>,
+    },
+
+    /// Import the blocks of a chain file, without connecting to the network. All blocks are fully
+    /// verified.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client import-chain chain.bin`
+    ///
+    ImportChain {
+        /// The file to read the blocks from.
+        file: PathBuf,
+    },
+
+    /// Export the latest snapshot of the accounts tree to a snapshot file. This is the snapshot
+    /// kept with `snapshots` enabled or, if the head of the chain is an election block, the
+    /// current accounts tree.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client export-snapshot snapshot.bin`
+    ///
+    ExportSnapshot {
+        /// The file to write the snapshot to.
+        file: PathBuf,
+    },
+
+    /// Import a snapshot file, without connecting to the network. The election blocks in the file
+    /// are verified and the accounts tree is replaced, skipping the history of these epochs except
+    /// for the transaction validity window. The head of the chain must be an election block.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client import-snapshot snapshot.bin`
+    ///
+    ImportSnapshot {
+        /// The file to read the snapshot from.
+        file: PathBuf,
+    },
 }
 
 impl CommandLine {
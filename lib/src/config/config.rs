@@ -15,6 +15,8 @@ use nimiq_database::{
     volatile::VolatileEnvironment,
     Environment,
 };
+#[cfg(feature = "rpc-server")]
+use nimiq_mempool::fee_estimation::FeePriority;
 use nimiq_mempool::{filter::Rules as MempoolRules, MempoolConfig};
 use nimiq_network_libp2p::{Keypair as IdentityKeypair, Multiaddr};
 use nimiq_primitives::networks::NetworkId;
@@ -22,6 +24,12 @@ use nimiq_utils::file_store::FileStore;
 #[cfg(feature = "validator")]
 use nimiq_utils::key_rng::SecureGenerate;
 
+#[cfg(feature = "rpc-server")]
+use nimiq_rpc_server::ip_network::IpNetwork;
+
+#[cfg(feature = "rpc-server")]
+use crate::config::config_file::RpcNamespace;
+
 use crate::{
     client::Client,
     config::{
@@ -37,7 +45,7 @@ use crate::{
 ///
 /// # Notes
 ///
-/// core-rs / Albatross is currently only supporting full consensus.
+/// core-rs / Albatross is currently only supporting full and nano consensus.
 ///
 /// # ToDo
 ///
@@ -47,6 +55,7 @@ use crate::{
 pub enum ConsensusConfig {
     Full,
     MacroSync,
+    Nano,
 }
 
 impl Default for ConsensusConfig {
@@ -208,21 +217,33 @@ pub struct DatabaseConfig {
     #[builder(default = "50 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 10
-    #[builder(default = "12")]
+    /// Max number of DBs. This covers all stores of a full node, including the optional ones.
+    /// Default: 20
+    #[builder(default = "20")]
     max_dbs: u32,
 
     /// Additional LMDB flags
     #[builder(default = "LmdbFlags::NOMETASYNC")]
     flags: LmdbFlags::Flags,
+
+    /// Keep the past states of all accounts. Whole account states are stored, so every block that
+    /// changes the staking contract adds a full copy of it. Default: false
+    #[builder(default)]
+    pub(crate) archive: bool,
+
+    /// Keep a snapshot of the accounts tree at the latest election block. Default: false
+    #[builder(default)]
+    pub(crate) snapshots: bool,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
             size: 50 * 1024 * 1024,
-            max_dbs: 12,
+            max_dbs: 20,
             flags: LmdbFlags::NOMETASYNC,
+            archive: false,
+            snapshots: false,
         }
     }
 }
@@ -240,6 +261,8 @@ impl From<config_file::DatabaseSettings> for DatabaseConfig {
             size: db_settings.size.unwrap_or(default.size),
             max_dbs: db_settings.max_dbs.unwrap_or(default.max_dbs),
             flags,
+            archive: db_settings.archive.unwrap_or(default.archive),
+            snapshots: db_settings.snapshots.unwrap_or(default.snapshots),
         }
     }
 }
@@ -418,14 +441,23 @@ pub struct RpcServerConfig {
     #[builder(default = "consts::RPC_DEFAULT_PORT")]
     pub port: u16,
 
-    /// TODO
+    /// If specified, allow cross-origin requests from these origins. Use `*` to allow any origin.
+    ///
     #[builder(setter(strip_option))]
     pub corsdomain: Option<Vec<String>>,
 
-    /// If specified, only allow connections from these IP addresses
+    /// If specified, only allow connections from these IP networks
     ///
     #[builder(setter(strip_option))]
-    pub allow_ips: Option<Vec<IpAddr>>,
+    pub allow_ips: Option<Vec<IpNetwork>>,
+
+    /// Also accept connections over websocket. This is needed to subscribe to streams, e.g. new
+    /// blocks.
+    ///
+    /// Default: `false`
+    ///
+    #[builder(default)]
+    pub enable_websocket: bool,
 
     /// If specified, only allow these RPC methods
     ///
@@ -435,6 +467,60 @@ pub struct RpcServerConfig {
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
+
+    /// If specified, require this bearer token. If credentials are specified as well, either of
+    /// them is accepted.
+    #[builder(setter(strip_option))]
+    pub token: Option<String>,
+
+    /// If specified, only serve the RPC methods of these namespaces
+    ///
+    #[builder(setter(strip_option))]
+    pub namespaces: Option<Vec<RpcNamespace>>,
+
+    /// If specified, the fee of transactions sent with the `send*` methods is estimated for this
+    /// priority if it is zero.
+    ///
+    #[builder(setter(strip_option))]
+    pub auto_fee: Option<FeePriority>,
+
+    /// Additional endpoints with their own credentials and access rights, e.g. a public read-only
+    /// endpoint next to a private one that can sign transactions.
+    ///
+    #[builder(default)]
+    pub endpoints: Vec<RpcEndpointConfig>,
+}
+
+/// An additional endpoint of the RPC server. It shares the CORS, IP and websocket settings of the
+/// RPC server, but listens on its own port and has its own credentials and access rights.
+#[cfg(feature = "rpc-server")]
+#[derive(Debug, Clone)]
+pub struct RpcEndpointConfig {
+    /// The name of the endpoint, used in log messages.
+    pub name: String,
+
+    /// Bind the endpoint to the specified IP address.
+    ///
+    /// Default: `127.0.0.1`
+    ///
+    pub bind_to: Option<IpAddr>,
+
+    /// Bind the endpoint to the specified port. This must differ from the ports of the RPC server
+    /// and the other endpoints.
+    pub port: u16,
+
+    /// If specified, only serve the RPC methods of these namespaces
+    pub namespaces: Option<Vec<RpcNamespace>>,
+
+    /// If specified, only allow these RPC methods
+    pub allowed_methods: Option<Vec<String>>,
+
+    /// If specified, require HTTP basic auth with these credentials
+    pub credentials: Option<Credentials>,
+
+    /// If specified, require this bearer token. If credentials are specified as well, either of
+    /// them is accepted.
+    pub token: Option<String>,
 }
 
 #[cfg(feature = "metrics-server")]
@@ -535,6 +621,15 @@ pub struct ClientConfig {
     #[cfg(feature = "metrics-server")]
     #[builder(default)]
     pub metrics_server: Option<MetricsServerConfig>,
+
+    /// Determines whether the client creates zero-knowledge proofs for the election blocks and
+    /// serves them to nano nodes.
+    ///
+    /// Default is `false`
+    ///
+    #[cfg(feature = "zkp-prover")]
+    #[builder(default)]
+    pub zkp_prover: bool,
 }
 
 impl ClientConfig {
@@ -593,6 +688,13 @@ impl ClientConfigBuilder {
         self.consensus(ConsensusConfig::MacroSync)
     }
 
+    /// Sets the client to sync as a nano node. It syncs to the most recent election block using
+    /// a zero-knowledge proof and then only follows the head of the chain.
+    ///
+    pub fn nano(&mut self) -> &mut Self {
+        self.consensus(ConsensusConfig::Nano)
+    }
+
     /*
     /// Sets the reverse proxy configuration. You need to set this if you run your node behind
     /// a reverse proxy.
@@ -626,6 +728,7 @@ impl ClientConfigBuilder {
         self.mempool = Some(MempoolConfig {
             filter_rules,
             filter_limit,
+            ..Default::default()
         });
         self
     }
@@ -674,6 +777,16 @@ impl ClientConfigBuilder {
         // Configure network
         self.network_id(config_file.consensus.network);
 
+        // Configure ZKP prover
+        #[cfg(feature = "zkp-prover")]
+        self.zkp_prover(config_file.consensus.zkp_prover);
+        #[cfg(not(feature = "zkp-prover"))]
+        if config_file.consensus.zkp_prover {
+            log::warn!(
+                "Client is compiled without the ZKP prover and thus can't create zk proofs."
+            );
+        }
+
         // Configure storage config.
         let mut file_storage = FileStorageConfig::default();
         if let Some(path) = config_file.database.path.as_ref() {
@@ -708,34 +821,66 @@ impl ClientConfigBuilder {
                 let allow_ips = if rpc_config.allowip.is_empty() {
                     None
                 } else {
-                    let result = rpc_config
+                    let networks = rpc_config
                         .allowip
                         .iter()
                         .map(|s| {
-                            s.parse::<IpAddr>()
-                                .map_err(|e| Error::config_error(format!("Invalid IP: {}", e)))
+                            s.parse::<IpNetwork>().map_err(|e| {
+                                Error::config_error(format!("Invalid IP network {}: {}", s, e))
+                            })
                         })
-                        .collect::<Result<Vec<IpAddr>, Error>>();
-                    Some(result?)
+                        .collect::<Result<Vec<IpNetwork>, Error>>()?;
+
+                    Some(networks)
                 };
 
-                let credentials = match (&rpc_config.username, &rpc_config.password) {
-                    (Some(u), Some(p)) => Some(Credentials::new(u.clone(), p.clone())),
-                    (None, None) => None,
-                    _ => {
-                        return Err(Error::config_error(
-                            "Either both username and password have to be set or none.",
-                        ))
+                let credentials =
+                    Self::rpc_credentials(&rpc_config.username, &rpc_config.password)?;
+
+                let mut ports = std::collections::HashSet::new();
+                let port = rpc_config.port.unwrap_or(consts::RPC_DEFAULT_PORT);
+                ports.insert(port);
+
+                let mut endpoints = vec![];
+                for endpoint in &rpc_config.endpoints {
+                    if !ports.insert(endpoint.port) {
+                        return Err(Error::config_error(format!(
+                            "RPC endpoint {} uses port {}, which is already in use by the RPC server",
+                            endpoint.name, endpoint.port
+                        )));
                     }
-                };
+
+                    endpoints.push(RpcEndpointConfig {
+                        name: endpoint.name.clone(),
+                        bind_to: endpoint
+                            .bind
+                            .as_ref()
+                            .and_then(|addr| addr.into_ip_address()),
+                        port: endpoint.port,
+                        namespaces: Self::rpc_namespaces(&endpoint.namespaces),
+                        allowed_methods: Some(endpoint.methods.clone()),
+                        credentials: Self::rpc_credentials(&endpoint.username, &endpoint.password)?,
+                        token: endpoint.token.clone(),
+                    });
+                }
 
                 self.rpc_server = Some(Some(RpcServerConfig {
                     bind_to,
-                    port: rpc_config.port.unwrap_or(consts::RPC_DEFAULT_PORT),
+                    port,
                     corsdomain: Some(rpc_config.corsdomain.clone()),
                     allow_ips,
                     allowed_methods: Some(rpc_config.methods.clone()),
                     credentials,
+                    token: rpc_config.token.clone(),
+                    enable_websocket: rpc_config.websocket,
+                    namespaces: Self::rpc_namespaces(&rpc_config.namespaces),
+                    auto_fee: rpc_config
+                        .auto_fee
+                        .as_ref()
+                        .map(|priority| priority.parse::<FeePriority>())
+                        .transpose()
+                        .map_err(Error::config_error)?,
+                    endpoints,
                 }));
             }
         }
@@ -776,6 +921,32 @@ impl ClientConfigBuilder {
         Ok(self)
     }
 
+    /// Parses the RPC credentials from a config file. Either both username and password are set
+    /// or none.
+    #[cfg(feature = "rpc-server")]
+    fn rpc_credentials(
+        username: &Option<String>,
+        password: &Option<String>,
+    ) -> Result<Option<Credentials>, Error> {
+        match (username, password) {
+            (Some(u), Some(p)) => Ok(Some(Credentials::new(u.clone(), p.clone()))),
+            (None, None) => Ok(None),
+            _ => Err(Error::config_error(
+                "Either both username and password have to be set or none.",
+            )),
+        }
+    }
+
+    /// All namespaces are served if none are listed.
+    #[cfg(feature = "rpc-server")]
+    fn rpc_namespaces(namespaces: &[RpcNamespace]) -> Option<Vec<RpcNamespace>> {
+        if namespaces.is_empty() {
+            None
+        } else {
+            Some(namespaces.to_vec())
+        }
+    }
+
     /// Applies settings from the command line
     pub fn command_line(&mut self, command_line: &CommandLine) -> Result<&mut Self, Error> {
         // Set consensus type
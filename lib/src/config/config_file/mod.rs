@@ -10,7 +10,7 @@ use thiserror::Error;
 
 use nimiq_mempool::{
     filter::{MempoolFilter, Rules as MempoolRules},
-    MempoolConfig,
+    MempoolConfig, DEFAULT_SIZE_LIMIT, DEFAULT_TRANSACTIONS_PER_SENDER_MAX,
 };
 use nimiq_peer_address::{address, protocol}; // TODO: probably not needed anymore
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
@@ -178,6 +178,8 @@ pub struct ConsensusSettings {
     pub consensus_type: ConsensusType,
     #[serde(default)]
     pub network: Network,
+    #[serde(default)]
+    pub zkp_prover: bool,
 }
 
 #[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
@@ -185,6 +187,7 @@ pub struct ConsensusSettings {
 pub enum ConsensusType {
     Full,
     MacroSync,
+    Nano,
 }
 
 impl Default for ConsensusType {
@@ -204,6 +207,7 @@ impl FromStr for ConsensusType {
         Ok(match s.to_lowercase().as_str() {
             "full" => Self::Full,
             "macro-sync" => Self::MacroSync,
+            "nano" => Self::Nano,
             _ => return Err(ConsensusTypeParseError(s.to_string())),
         })
     }
@@ -214,6 +218,7 @@ impl From<ConsensusType> for config::ConsensusConfig {
         match consensus_type {
             ConsensusType::Full => Self::Full,
             ConsensusType::MacroSync => Self::MacroSync,
+            ConsensusType::Nano => Self::Nano,
         }
     }
 }
@@ -277,6 +282,46 @@ pub struct RpcServerSettings {
     pub methods: Vec<String>,
     pub username: Option<String>,
     pub password: Option<String>,
+    pub token: Option<String>,
+    #[serde(default)]
+    pub websocket: bool,
+    #[serde(default)]
+    pub namespaces: Vec<RpcNamespace>,
+    pub auto_fee: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "endpoint")]
+    pub endpoints: Vec<RpcEndpointSettings>,
+}
+
+/// An additional endpoint of the RPC server. It listens on its own port and has its own
+/// credentials and access rights.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcEndpointSettings {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_string_option")]
+    #[serde(default)]
+    pub bind: Option<address::NetAddress>,
+    pub port: u16,
+    #[serde(default)]
+    pub namespaces: Vec<RpcNamespace>,
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub token: Option<String>,
+}
+
+/// The namespaces of the RPC methods.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcNamespace {
+    Blockchain,
+    Consensus,
+    Mempool,
+    Network,
+    Validator,
+    Wallet,
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -384,6 +429,8 @@ pub struct DatabaseSettings {
     pub size: Option<usize>,
     pub max_dbs: Option<u32>,
     pub no_lmdb_sync: Option<bool>,
+    pub archive: Option<bool>,
+    pub snapshots: Option<bool>,
 }
 
 impl Default for DatabaseSettings {
@@ -391,8 +438,10 @@ impl Default for DatabaseSettings {
         DatabaseSettings {
             path: None,
             size: Some(1024 * 1024 * 50),
-            max_dbs: Some(12),
+            max_dbs: Some(20),
             no_lmdb_sync: None,
+            archive: None,
+            snapshots: None,
         }
     }
 }
@@ -402,6 +451,8 @@ impl Default for DatabaseSettings {
 pub struct MempoolSettings {
     pub filter: Option<MempoolFilterSettings>,
     pub blacklist_limit: Option<usize>,
+    pub size_limit: Option<usize>,
+    pub max_transactions_per_sender: Option<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -449,6 +500,10 @@ impl From<MempoolSettings> for MempoolConfig {
                 .blacklist_limit
                 .unwrap_or(MempoolFilter::DEFAULT_BLACKLIST_SIZE),
             filter_rules: mempool.filter.map(MempoolRules::from).unwrap_or_default(),
+            size_limit: mempool.size_limit.unwrap_or(DEFAULT_SIZE_LIMIT),
+            transactions_per_sender_max: mempool
+                .max_transactions_per_sender
+                .unwrap_or(DEFAULT_TRANSACTIONS_PER_SENDER_MAX),
         }
     }
 }
@@ -24,6 +24,12 @@ pub enum Error {
     #[error("Consensus error: {0}")]
     Consensus(#[from] nimiq_consensus::Error),
 
+    #[error("Blockchain error: {0}")]
+    Blockchain(#[from] nimiq_blockchain::BlockchainError),
+
+    #[error("Chain file error: {0}")]
+    ChainFile(#[from] nimiq_blockchain::ChainFileError),
+
     #[error("Config file parsing error: {0}")]
     Toml(#[from] toml::de::Error),
 
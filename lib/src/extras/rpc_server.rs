@@ -1,73 +1,155 @@
 use std::{collections::HashSet, iter::FromIterator, sync::Arc};
 
 use nimiq_rpc_server::dispatchers::*;
+use nimiq_rpc_server::server::{RpcAccess, RpcServer, RpcServerConfig as ServerConfig};
+use nimiq_rpc_server::transaction_tracker::TransactionTracker;
 
 use nimiq_jsonrpc_core::Credentials;
-use nimiq_jsonrpc_server::{AllowListDispatcher, Config, ModularDispatcher, Server as _Server};
+use nimiq_jsonrpc_server::{AllowListDispatcher, ModularDispatcher};
 
+use nimiq_blockchain::Blockchain;
 use nimiq_wallet::WalletStore;
 
 use crate::client::Client;
-use crate::config::config::RpcServerConfig;
+use crate::config::config::{RpcEndpointConfig, RpcServerConfig};
+use crate::config::config_file::RpcNamespace;
 use crate::config::consts::default_bind;
 use crate::error::Error;
 
-pub type Server = _Server<AllowListDispatcher<ModularDispatcher>>;
+pub type Server = RpcServer<AllowListDispatcher<ModularDispatcher>>;
 
-pub fn initialize_rpc_server(
+/// The namespaces served by an RPC endpoint. All namespaces are served if none are configured.
+pub struct EndpointNamespaces(pub Option<Vec<RpcNamespace>>);
+
+impl EndpointNamespaces {
+    pub fn serves(&self, namespace: RpcNamespace) -> bool {
+        self.0
+            .as_ref()
+            .map(|namespaces| namespaces.contains(&namespace))
+            .unwrap_or(true)
+    }
+
+    /// Creates the wallet dispatcher of an endpoint, if it serves the wallet namespace. Each
+    /// endpoint has its own unlocked wallets, so that a wallet unlocked on one endpoint can't be
+    /// used to sign transactions on another one.
+    pub fn wallet_dispatcher(
+        &self,
+        wallet_store: &Arc<WalletStore>,
+        blockchain: Option<Arc<Blockchain>>,
+    ) -> Option<WalletDispatcher> {
+        if self.serves(RpcNamespace::Wallet) {
+            Some(WalletDispatcher::new(Arc::clone(wallet_store), blockchain))
+        } else {
+            None
+        }
+    }
+}
+
+/// Creates the RPC server and an additional server for every configured endpoint. All servers
+/// share the CORS, IP and websocket settings and the transaction tracker, but each endpoint has its
+/// own unlocked wallets.
+pub fn initialize_rpc_servers(
     client: &Client,
     config: RpcServerConfig,
     wallet_store: Arc<WalletStore>,
-) -> Result<Server, Error> {
-    let ip = config.bind_to.unwrap_or_else(default_bind);
-    log::info!("Initializing RPC server: {}:{}", ip, config.port);
-
-    // Configure RPC server
-    let basic_auth = config.credentials.map(|credentials| Credentials {
-        username: credentials.username,
-        password: credentials.password,
-    });
-
-    let allowed_methods = config.allowed_methods.unwrap_or_default();
-    let allowed_methods = if allowed_methods.is_empty() {
-        None
-    } else {
-        Some(HashSet::from_iter(allowed_methods))
+) -> Result<Vec<Server>, Error> {
+    let allowed_networks = config.allow_ips.filter(|networks| !networks.is_empty());
+
+    let default_endpoint = RpcEndpointConfig {
+        name: "default".to_owned(),
+        bind_to: config.bind_to,
+        port: config.port,
+        namespaces: config.namespaces,
+        allowed_methods: config.allowed_methods,
+        credentials: config.credentials,
+        token: config.token,
     };
 
-    // TODO: Pass this to the rpc server config
-    let _corsdomain = config.corsdomain.unwrap_or_default();
+    // The transactions sent through any endpoint are followed by the same tracker.
+    let tracker = TransactionTracker::new(client.consensus_proxy());
+
+    let mut servers = vec![];
+
+    for endpoint in std::iter::once(default_endpoint).chain(config.endpoints) {
+        let ip = endpoint.bind_to.unwrap_or_else(default_bind);
+        log::info!(
+            "Initializing RPC server ({}): {}:{}",
+            endpoint.name,
+            ip,
+            endpoint.port
+        );
+
+        // Configure RPC server
+        let basic_auth = endpoint.credentials.map(|credentials| Credentials {
+            username: credentials.username,
+            password: credentials.password,
+        });
 
-    let mut dispatcher = ModularDispatcher::default();
+        let allowed_methods = endpoint.allowed_methods.unwrap_or_default();
+        let allowed_methods = if allowed_methods.is_empty() {
+            None
+        } else {
+            Some(HashSet::from_iter(allowed_methods))
+        };
 
-    /*
-    #[cfg(feature = "validator")]
-    {
-        if let Some(validator) = client.validator() {
-            dispatcher.add(BlockProductionDispatcher::new(validator));
+        let namespaces = EndpointNamespaces(endpoint.namespaces);
+        let serves = |namespace: RpcNamespace| namespaces.serves(namespace);
+
+        // Only endpoints serving the wallet namespace can unlock wallets and sign with them.
+        let wallet_dispatcher =
+            namespaces.wallet_dispatcher(&wallet_store, Some(client.blockchain()));
+        let unlocked_wallets = wallet_dispatcher
+            .as_ref()
+            .map(|wallet_dispatcher| Arc::clone(&wallet_dispatcher.unlocked_wallets));
+
+        let mut dispatcher = ModularDispatcher::default();
+
+        #[cfg(feature = "validator")]
+        {
+            if serves(RpcNamespace::Validator) {
+                if let Some(validator) = client.validator_proxy() {
+                    dispatcher.add(ValidatorDispatcher::new(validator));
+                }
+            }
+        }
+
+        if serves(RpcNamespace::Network) {
+            dispatcher.add(NetworkDispatcher::new(client.network()));
+        }
+        if serves(RpcNamespace::Blockchain) {
+            dispatcher.add(BlockchainDispatcher::new(
+                client.blockchain(),
+                Some(client.mempool()),
+            ));
         }
+        if serves(RpcNamespace::Consensus) {
+            dispatcher.add(ConsensusDispatcher::new(
+                client.consensus_proxy(),
+                unlocked_wallets,
+                config.auto_fee,
+                Arc::clone(&tracker),
+            ));
+        }
+        if let Some(wallet_dispatcher) = wallet_dispatcher {
+            dispatcher.add(wallet_dispatcher);
+        }
+        if serves(RpcNamespace::Mempool) {
+            dispatcher.add(MempoolDispatcher::new(client.mempool()));
+        }
+
+        servers.push(Server::new(
+            ServerConfig {
+                bind_to: (ip, endpoint.port).into(),
+                enable_websocket: config.enable_websocket,
+                access: RpcAccess::new(allowed_networks.clone(), basic_auth, endpoint.token),
+                allowed_origins: config
+                    .corsdomain
+                    .clone()
+                    .filter(|origins| !origins.is_empty()),
+            },
+            AllowListDispatcher::new(dispatcher, allowed_methods),
+        )?);
     }
-    */
-
-    //dispatcher.add(NetworkDispatcher::new(client.consensus()));
-    let wallet_dispatcher = WalletDispatcher::new(wallet_store);
-    let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
-
-    dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
-    dispatcher.add(ConsensusDispatcher::new(
-        client.consensus_proxy(),
-        Some(unlocked_wallets),
-    ));
-    dispatcher.add(wallet_dispatcher);
-    dispatcher.add(MempoolDispatcher::new(client.mempool()));
-
-    Ok(Server::new(
-        Config {
-            bind_to: (config.bind_to.unwrap_or_else(default_bind), config.port).into(),
-            enable_websocket: false,
-            ip_whitelist: None,
-            basic_auth,
-        },
-        AllowListDispatcher::new(dispatcher, allowed_methods),
-    ))
+
+    Ok(servers)
 }
@@ -1,3 +1,4 @@
+pub mod chain_file;
 pub mod client;
 pub mod config;
 pub mod error;
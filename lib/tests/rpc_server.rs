@@ -0,0 +1,57 @@
+#![cfg(all(feature = "rpc-server", feature = "wallet"))]
+
+use std::sync::Arc;
+
+use nimiq_database::volatile::VolatileEnvironment;
+use nimiq_lib::config::config_file::RpcNamespace;
+use nimiq_lib::extras::rpc_server::EndpointNamespaces;
+use nimiq_wallet::WalletStore;
+
+fn wallet_store() -> Arc<WalletStore> {
+    let env = VolatileEnvironment::new(10).unwrap();
+    Arc::new(WalletStore::new(env))
+}
+
+#[test]
+fn all_namespaces_are_served_by_default() {
+    let namespaces = EndpointNamespaces(None);
+
+    assert!(namespaces.serves(RpcNamespace::Blockchain));
+    assert!(namespaces.serves(RpcNamespace::Consensus));
+    assert!(namespaces.serves(RpcNamespace::Wallet));
+    assert!(namespaces
+        .wallet_dispatcher(&wallet_store(), None)
+        .is_some());
+}
+
+#[test]
+fn only_configured_namespaces_are_served() {
+    let namespaces = EndpointNamespaces(Some(vec![
+        RpcNamespace::Blockchain,
+        RpcNamespace::Consensus,
+    ]));
+
+    assert!(namespaces.serves(RpcNamespace::Blockchain));
+    assert!(namespaces.serves(RpcNamespace::Consensus));
+    assert!(!namespaces.serves(RpcNamespace::Mempool));
+    assert!(!namespaces.serves(RpcNamespace::Wallet));
+
+    // Without the wallet namespace, the endpoint can't unlock wallets.
+    assert!(namespaces
+        .wallet_dispatcher(&wallet_store(), None)
+        .is_none());
+}
+
+#[test]
+fn endpoints_dont_share_unlocked_wallets() {
+    let wallet_store = wallet_store();
+    let namespaces = EndpointNamespaces(Some(vec![RpcNamespace::Wallet]));
+
+    let dispatcher1 = namespaces.wallet_dispatcher(&wallet_store, None).unwrap();
+    let dispatcher2 = namespaces.wallet_dispatcher(&wallet_store, None).unwrap();
+
+    assert!(!Arc::ptr_eq(
+        &dispatcher1.unlocked_wallets,
+        &dispatcher2.unlocked_wallets
+    ));
+}
@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use beserial::Serialize;
+use block::{MicroBlock, MicroBody, MicroHeader};
+use blockchain::AbstractBlockchain;
+use primitives::coin::Coin;
+use transaction::Transaction;
+
+use crate::Mempool;
+
+/// The number of recent micro blocks whose fees are considered by default.
+pub const DEFAULT_FEE_ESTIMATION_BLOCKS: u32 = 20;
+
+/// How quickly a transaction should be included in a block.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeePriority {
+    Fast,
+    Normal,
+    Slow,
+}
+
+impl FeePriority {
+    /// The number of blocks within which a transaction with this priority should be included.
+    fn target_blocks(self) -> usize {
+        match self {
+            FeePriority::Fast => 1,
+            FeePriority::Normal => 3,
+            FeePriority::Slow => 10,
+        }
+    }
+
+    /// The percentile of the minimum fees of recent blocks that a transaction with this priority
+    /// should pay.
+    fn percentile(self) -> f64 {
+        match self {
+            FeePriority::Fast => 0.9,
+            FeePriority::Normal => 0.5,
+            FeePriority::Slow => 0.1,
+        }
+    }
+}
+
+impl FromStr for FeePriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fast" => Ok(FeePriority::Fast),
+            "normal" => Ok(FeePriority::Normal),
+            "slow" => Ok(FeePriority::Slow),
+            _ => Err(format!("Invalid fee priority: {}", s)),
+        }
+    }
+}
+
+/// Suggested fees per byte, in Luna.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimate {
+    pub fast: f64,
+    pub normal: f64,
+    pub slow: f64,
+}
+
+impl FeeEstimate {
+    pub fn fee_per_byte(&self, priority: FeePriority) -> f64 {
+        match priority {
+            FeePriority::Fast => self.fast,
+            FeePriority::Normal => self.normal,
+            FeePriority::Slow => self.slow,
+        }
+    }
+
+    /// Returns the suggested fee for a transaction of `size` bytes.
+    pub fn fee(&self, priority: FeePriority, size: usize) -> Coin {
+        Coin::from_u64_unchecked((self.fee_per_byte(priority) * size as f64).ceil() as u64)
+    }
+}
+
+impl Mempool {
+    /// Estimates the fees per byte needed to be included in a block soon.
+    ///
+    /// A transaction has to outbid the transactions in the mempool that fill the blocks before
+    /// it, assuming that blocks are filled with the transactions paying the most per byte. It also
+    /// has to pay the minimum fee per byte of recent blocks that were (nearly) full, since their
+    /// producers didn't accept less. Finally, it has to pay the minimum fee per byte that the
+    /// filter of this mempool accepts.
+    pub fn estimate_fees(&self, num_blocks: u32) -> FeeEstimate {
+        let max_size = MicroBlock::MAX_SIZE - MicroHeader::SIZE - MicroBody::get_metadata_size(0);
+        let mempool_fees = self.mempool_fees_per_byte();
+        let block_fees = self.block_min_fees_per_byte(num_blocks, max_size);
+        let min_fee_per_byte = self.state.read().filter.min_fee_per_byte();
+
+        let estimate = |priority: FeePriority| {
+            Self::fee_per_byte_at(&mempool_fees, priority.target_blocks() * max_size)
+                .max(Self::percentile(&block_fees, priority.percentile()))
+                .max(min_fee_per_byte)
+        };
+
+        FeeEstimate {
+            fast: estimate(FeePriority::Fast),
+            normal: estimate(FeePriority::Normal),
+            slow: estimate(FeePriority::Slow),
+        }
+    }
+
+    /// Estimates the fee that `transaction` needs to pay to be included in a block soon with the
+    /// given `priority`. Besides the estimated fee per byte, this considers all minimum fees that
+    /// the filter of this mempool requires for the transaction. The fee of `transaction` itself
+    /// is ignored.
+    pub fn estimate_fee(
+        &self,
+        priority: FeePriority,
+        transaction: &Transaction,
+        num_blocks: u32,
+    ) -> Coin {
+        let estimate = self
+            .estimate_fees(num_blocks)
+            .fee(priority, transaction.serialized_size());
+
+        // XXX Like the filter, this classifies accounts with zero balance as non-existent.
+        let creates_recipient = self
+            .blockchain
+            .get_account(&transaction.recipient)
+            .balance()
+            .is_zero();
+        let min_fee = self
+            .state
+            .read()
+            .filter
+            .min_fee(transaction, creates_recipient);
+
+        estimate.max(min_fee)
+    }
+
+    /// Returns the fee per byte and size of the transactions in the mempool, sorted descending by
+    /// fee per byte.
+    fn mempool_fees_per_byte(&self) -> Vec<(f64, usize)> {
+        self.state
+            .read()
+            .transactions_sorted_fee
+            .iter()
+            .rev()
+            .map(|tx| (tx.fee_per_byte(), tx.serialized_size()))
+            .collect()
+    }
+
+    /// Returns the fee per byte that is needed to outbid the transactions in the mempool that
+    /// fill `size` bytes. If they don't fill that many bytes, no fee is needed.
+    fn fee_per_byte_at(fees: &[(f64, usize)], size: usize) -> f64 {
+        let mut total_size = 0;
+        for (fee_per_byte, tx_size) in fees {
+            total_size += tx_size;
+            if total_size > size {
+                return *fee_per_byte;
+            }
+        }
+        0.0
+    }
+
+    /// Returns the minimum fee per byte of each of the last `num_blocks` micro blocks, sorted
+    /// ascending. Blocks that had room for more transactions didn't require any fee.
+    fn block_min_fees_per_byte(&self, num_blocks: u32, max_size: usize) -> Vec<f64> {
+        let head_height = self.blockchain.block_number();
+
+        let mut fees = Vec::new();
+        for height in (head_height + 1).saturating_sub(num_blocks)..=head_height {
+            let block = match self.blockchain.get_block_at(height, true, None) {
+                Some(block) => block,
+                None => continue,
+            };
+            let transactions = match block.transactions() {
+                Some(transactions) => transactions,
+                // Macro blocks don't contain transactions.
+                None => continue,
+            };
+
+            let size: usize = transactions.iter().map(|tx| tx.serialized_size()).sum();
+            let min_fee_per_byte = if max_size.saturating_sub(size) < Transaction::MIN_SIZE {
+                transactions
+                    .iter()
+                    .map(|tx| tx.fee_per_byte())
+                    .fold(f64::INFINITY, f64::min)
+            } else {
+                0.0
+            };
+            fees.push(min_fee_per_byte);
+        }
+
+        fees.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        fees
+    }
+
+    fn percentile(values: &[f64], percentile: f64) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        let index = ((values.len() - 1) as f64 * percentile).round() as usize;
+        values[index]
+    }
+}
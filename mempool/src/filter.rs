@@ -1,3 +1,4 @@
+use beserial::Serialize;
 use collections::LimitHashSet;
 use nimiq_hash::Blake2bHash;
 use primitives::coin::Coin;
@@ -47,6 +48,49 @@ impl MempoolFilter {
          )
     }
 
+    /// Returns the minimum fee per byte that any transaction has to pay.
+    pub fn min_fee_per_byte(&self) -> f64 {
+        self.rules.tx_fee_per_byte
+    }
+
+    /// Returns the minimum fee that `tx` has to pay to be accepted. `creates_recipient` tells
+    /// whether the transaction creates its recipient account. Rules that don't depend on the fee
+    /// are ignored.
+    pub fn min_fee(&self, tx: &Transaction, creates_recipient: bool) -> Coin {
+        let size = tx.serialized_size();
+        let fee_for_size = |fee_per_byte: f64| {
+            Coin::from_u64_unchecked((fee_per_byte * size as f64).ceil() as u64)
+        };
+
+        let mut min_fee = self
+            .rules
+            .tx_fee
+            .max(fee_for_size(self.rules.tx_fee_per_byte));
+
+        if let Some(missing_value) = self.rules.tx_value_total.checked_sub(tx.value) {
+            min_fee = min_fee.max(missing_value);
+        }
+
+        // Contract creations have to pay either fee, unless their value is high enough.
+        if tx.flags.contains(TransactionFlags::CONTRACT_CREATION)
+            && tx.value < self.rules.contract_value
+        {
+            min_fee = min_fee.max(
+                self.rules
+                    .contract_fee
+                    .min(fee_for_size(self.rules.contract_fee_per_byte)),
+            );
+        }
+
+        if creates_recipient {
+            min_fee = min_fee
+                .max(self.rules.creation_fee)
+                .max(fee_for_size(self.rules.creation_fee_per_byte));
+        }
+
+        min_fee
+    }
+
     pub fn accepts_recipient_balance(
         &self,
         tx: &Transaction,
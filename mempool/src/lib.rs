@@ -28,6 +28,7 @@ use utils::observer::{weak_listener, Notifier};
 
 use crate::filter::{MempoolFilter, Rules};
 
+pub mod fee_estimation;
 pub mod filter;
 
 pub struct Mempool {
@@ -35,6 +36,8 @@ pub struct Mempool {
     pub notifier: RwLock<Notifier<'static, MempoolEvent>>,
     state: RwLock<MempoolState>,
     mut_lock: Mutex<()>,
+    size_limit: usize,
+    transactions_per_sender_max: u32,
 }
 
 struct MempoolState {
@@ -42,6 +45,7 @@ struct MempoolState {
     transactions_by_sender: HashMap<Address, BTreeSet<Arc<Transaction>>>,
     transactions_by_recipient: HashMap<Address, BTreeSet<Arc<Transaction>>>,
     transactions_sorted_fee: BTreeSet<Arc<Transaction>>, // sorted by fee, ascending
+    total_size: usize, // serialized size of all transactions, in bytes
     filter: MempoolFilter,
 }
 
@@ -57,6 +61,11 @@ pub enum MempoolEvent {
 pub struct MempoolConfig {
     pub filter_rules: Rules,
     pub filter_limit: usize,
+    /// Maximum total serialized size of all transactions in the mempool, in bytes. If it is exceeded, the
+    /// transactions with the lowest fee per byte are evicted.
+    pub size_limit: usize,
+    /// Maximum number of transactions per sender.
+    pub transactions_per_sender_max: u32,
 }
 
 impl Default for MempoolConfig {
@@ -64,6 +73,8 @@ impl Default for MempoolConfig {
         MempoolConfig {
             filter_rules: Rules::default(),
             filter_limit: MempoolFilter::DEFAULT_BLACKLIST_SIZE,
+            size_limit: DEFAULT_SIZE_LIMIT,
+            transactions_per_sender_max: DEFAULT_TRANSACTIONS_PER_SENDER_MAX,
         }
     }
 }
@@ -78,9 +89,12 @@ impl Mempool {
                 transactions_by_sender: HashMap::new(),
                 transactions_by_recipient: HashMap::new(),
                 transactions_sorted_fee: BTreeSet::new(),
+                total_size: 0,
                 filter: MempoolFilter::new(config.filter_rules, config.filter_limit),
             }),
             mut_lock: Mutex::new(()),
+            size_limit: config.size_limit,
+            transactions_per_sender_max: config.transactions_per_sender_max,
         });
 
         // register listener to blockchain through weak reference
@@ -109,6 +123,9 @@ impl Mempool {
         // Transactions that are invalidated by the new transaction are stored here.
         let mut txs_to_remove = Vec::new();
 
+        // The pending transaction that the new transaction replaces, if any.
+        let replaced_tx;
+
         {
             let state = self.state.upgradable_read();
 
@@ -133,8 +150,46 @@ impl Mempool {
                 return ReturnCode::Invalid;
             }
 
-            // Check limit for free transactions.
             let txs_by_sender_opt = state.transactions_by_sender.get(&transaction.sender);
+
+            // Check if the transaction replaces a pending transaction. Replacements must pay a strictly
+            // higher fee than the transaction they replace.
+            replaced_tx = txs_by_sender_opt.and_then(|transactions| {
+                transactions
+                    .iter()
+                    .find(|tx| Self::is_replacement(tx, &transaction))
+                    .cloned()
+            });
+            if let Some(ref tx) = replaced_tx {
+                if transaction.fee <= tx.fee {
+                    return ReturnCode::ReplacementFeeTooLow;
+                }
+            }
+
+            // Check if there is enough space left in the mempool. If not, the new transaction must
+            // pay a higher fee per byte than the transactions that need to be evicted to make room for it.
+            let tx_size = transaction.serialized_size();
+            let mut size_after = state.total_size + tx_size;
+            let mut count_after = state.transactions_sorted_fee.len() + 1;
+            if let Some(ref tx) = replaced_tx {
+                size_after -= tx.serialized_size();
+                count_after -= 1;
+            }
+            let mut lowest_fee_iter = state
+                .transactions_sorted_fee
+                .iter()
+                .filter(|tx| replaced_tx.as_ref() != Some(*tx));
+            while size_after > self.size_limit || count_after > SIZE_MAX {
+                match lowest_fee_iter.next() {
+                    Some(tx) if transaction.cmp(tx) == Ordering::Greater => {
+                        size_after -= tx.serialized_size();
+                        count_after -= 1;
+                    }
+                    _ => return ReturnCode::MempoolFull,
+                }
+            }
+
+            // Check limit for free transactions.
             if transaction.fee_per_byte() < TRANSACTION_RELAY_FEE_MIN {
                 let mut num_free_tx = 0;
                 if let Some(transactions) = txs_by_sender_opt {
@@ -221,6 +276,7 @@ impl Mempool {
 
             // Re-check all transactions for this sender in fee/byte order against the sender account state.
             // Adding high fee transactions may thus invalidate low fee transactions in the set.
+            // The replaced transaction is skipped as it will be removed from the mempool.
             let empty_btree; // XXX Only needed to get an empty BTree iterator
             let mut tx_count = 0;
             let mut tx_iter = match txs_by_sender_opt {
@@ -229,7 +285,8 @@ impl Mempool {
                     empty_btree = BTreeSet::new();
                     empty_btree.iter()
                 }
-            };
+            }
+            .filter(|tx| replaced_tx.as_ref() != Some(*tx));
 
             // First apply all transactions with a higher fee/byte.
             // These are not affected by the new transaction and should never fail to apply.
@@ -251,8 +308,8 @@ impl Mempool {
             }
 
             // If we are already at the transaction limit, reject the new transaction.
-            if tx_count >= TRANSACTIONS_PER_SENDER_MAX {
-                return ReturnCode::FeeTooLow;
+            if tx_count >= self.transactions_per_sender_max {
+                return ReturnCode::SenderLimitReached;
             }
 
             // Now, check the new transaction.
@@ -279,7 +336,7 @@ impl Mempool {
             // Finally, check the remaining transactions with lower fee/byte and evict them if necessary.
             // tx_opt already contains the first lower/fee byte transaction to check (if there is one remaining).
             while let Some(tx) = tx_opt {
-                if tx_count < TRANSACTIONS_PER_SENDER_MAX {
+                if tx_count < self.transactions_per_sender_max {
                     if sender_account
                         .commit_outgoing_transaction(tx, block_height, timestamp)
                         .is_ok()
@@ -295,6 +352,11 @@ impl Mempool {
             }
         }
 
+        // The replaced transaction is removed together with the invalidated ones.
+        if let Some(tx) = replaced_tx {
+            txs_to_remove.push(tx);
+        }
+
         let tx_arc = Arc::new(transaction);
 
         let mut removed_transactions;
@@ -311,12 +373,8 @@ impl Mempool {
             // Rename variable.
             removed_transactions = txs_to_remove;
 
-            // Remove the lowest fee transaction if mempool max size is reached.
-            if state.transactions_sorted_fee.len() > SIZE_MAX {
-                let tx = state.transactions_sorted_fee.iter().next().unwrap().clone();
-                Self::remove_transaction(&mut state, &tx);
-                removed_transactions.push(tx);
-            }
+            // Remove the lowest fee transactions if the mempool limits are exceeded.
+            removed_transactions.extend(self.evict_lowest_fee_transactions(&mut state));
         }
 
         // Drop the lock on blockchain::push
@@ -655,6 +713,7 @@ impl Mempool {
                     sender_account,
                     block_height,
                     timestamp,
+                    self.transactions_per_sender_max,
                     existing_txs,
                     &restored_txs,
                 );
@@ -670,18 +729,7 @@ impl Mempool {
             }
 
             // Evict lowest fee transactions if the mempool has grown too large.
-            let size = state.transactions_sorted_fee.len();
-            if size > SIZE_MAX {
-                let mut txs_to_remove = Vec::with_capacity(size - SIZE_MAX);
-                let mut iter = state.transactions_sorted_fee.iter();
-                for _ in 0..size - SIZE_MAX {
-                    txs_to_remove.push(iter.next().unwrap().clone());
-                }
-                for tx in txs_to_remove.iter() {
-                    Self::remove_transaction(&mut state, tx);
-                }
-                removed_transactions.extend(txs_to_remove);
-            }
+            removed_transactions.extend(self.evict_lowest_fee_transactions(&mut state));
         }
 
         // Notify listeners.
@@ -698,7 +746,39 @@ impl Mempool {
         }
     }
 
+    /// Evicts the transactions with the lowest fee per byte until the mempool is within its size limits again.
+    /// Returns the evicted transactions.
+    fn evict_lowest_fee_transactions(&self, state: &mut MempoolState) -> Vec<Arc<Transaction>> {
+        let mut evicted_transactions = Vec::new();
+
+        while state.total_size > self.size_limit || state.transactions_sorted_fee.len() > SIZE_MAX {
+            let tx = match state.transactions_sorted_fee.iter().next() {
+                Some(tx) => tx.clone(),
+                None => break,
+            };
+            Self::remove_transaction(state, &tx);
+            evicted_transactions.push(tx);
+        }
+
+        evicted_transactions
+    }
+
+    /// Checks if `new_tx` is a replacement of the pending transaction `old_tx`, i.e. both transactions are
+    /// identical except for the fee (and thus the proof).
+    fn is_replacement(old_tx: &Transaction, new_tx: &Transaction) -> bool {
+        old_tx.sender == new_tx.sender
+            && old_tx.sender_type == new_tx.sender_type
+            && old_tx.recipient == new_tx.recipient
+            && old_tx.recipient_type == new_tx.recipient_type
+            && old_tx.value == new_tx.value
+            && old_tx.validity_start_height == new_tx.validity_start_height
+            && old_tx.flags == new_tx.flags
+            && old_tx.data == new_tx.data
+            && old_tx.fee != new_tx.fee
+    }
+
     fn add_transaction(state: &mut MempoolState, hash: Blake2bHash, tx: Arc<Transaction>) {
+        state.total_size += tx.serialized_size();
         state.transactions_by_hash.insert(hash, tx.clone());
         state.transactions_sorted_fee.insert(tx.clone());
 
@@ -716,7 +796,9 @@ impl Mempool {
     }
 
     fn remove_transaction(state: &mut MempoolState, tx: &Transaction) {
-        state.transactions_by_hash.remove(&tx.hash());
+        if state.transactions_by_hash.remove(&tx.hash()).is_some() {
+            state.total_size -= tx.serialized_size();
+        }
         state.transactions_sorted_fee.remove(tx);
 
         let mut remove_key = false;
@@ -742,6 +824,7 @@ impl Mempool {
         mut sender_account: Account,
         block_height: u32,
         timestamp: u64,
+        transactions_per_sender_max: u32,
         old_txs: &BTreeSet<Arc<Transaction>>,
         new_txs: &BTreeSet<&'a Transaction>,
     ) -> (Vec<&'a Transaction>, Vec<Arc<Transaction>>) {
@@ -765,7 +848,7 @@ impl Mempool {
             };
 
             if new_is_next {
-                if tx_count < TRANSACTIONS_PER_SENDER_MAX {
+                if tx_count < transactions_per_sender_max {
                     let tx = new_tx.unwrap();
                     if sender_account
                         .commit_outgoing_transaction(*tx, block_height, timestamp)
@@ -778,7 +861,7 @@ impl Mempool {
                 new_tx = iter_new.next_back();
             } else {
                 let tx = old_tx.unwrap();
-                if tx_count < TRANSACTIONS_PER_SENDER_MAX {
+                if tx_count < transactions_per_sender_max {
                     if sender_account
                         .commit_outgoing_transaction(tx, block_height, timestamp)
                         .is_ok()
@@ -805,13 +888,29 @@ pub enum ReturnCode {
     Accepted,
     Known,
     Filtered,
+    /// The mempool is full and the transaction doesn't pay a higher fee per byte than the transactions that
+    /// would need to be evicted.
+    MempoolFull,
+    /// The sender already has the maximum number of pending transactions with a higher fee per byte.
+    SenderLimitReached,
+    /// The transaction replaces a pending transaction, but doesn't pay a strictly higher fee.
+    ///
+    /// Only a transaction that is identical to a pending one except for its fee (and thus its proof)
+    /// replaces it. Sharing the sender and the validity window isn't enough, since a sender commonly
+    /// sends several transactions valid from the same block. A transaction that changes anything
+    /// else, e.g. the recipient or the value, is treated as a new transaction and doesn't evict the
+    /// pending one.
+    ReplacementFeeTooLow,
 }
 
 /// Fee threshold in sat/byte below which transactions are considered "free".
 const TRANSACTION_RELAY_FEE_MIN: f64 = 1f64;
 
-/// Maximum number of transactions per sender.
-const TRANSACTIONS_PER_SENDER_MAX: u32 = 500;
+/// Default maximum number of transactions per sender.
+pub const DEFAULT_TRANSACTIONS_PER_SENDER_MAX: u32 = 500;
+
+/// Default maximum total size of all transactions in the mempool, in bytes.
+pub const DEFAULT_SIZE_LIMIT: usize = 12_000_000;
 
 /// Maximum number of "free" transactions per sender.
 const FREE_TRANSACTIONS_PER_SENDER_MAX: u32 = 10;
@@ -1,11 +1,12 @@
 use std::convert::TryFrom;
 
+use beserial::Serialize;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_keys::Address;
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
 use nimiq_mempool::filter::{MempoolFilter, Rules};
 use nimiq_primitives::coin::Coin;
 use nimiq_primitives::networks::NetworkId;
-use nimiq_transaction::Transaction;
+use nimiq_transaction::{SignatureProof, Transaction};
 
 #[test]
 fn it_can_blacklist_transactions() {
@@ -47,3 +48,48 @@ fn it_accepts_and_rejects_transactions() {
     tx.fee = Coin::try_from(1).unwrap();
     assert!(f.accepts_transaction(&tx));
 }
+
+#[test]
+fn it_computes_the_min_fee() {
+    let keypair = KeyPair::generate_default_csprng();
+    let mut tx = Transaction::new_basic(
+        Address::from(&keypair.public),
+        Address::from([213u8; Address::SIZE]),
+        Coin::try_from(100).unwrap(),
+        Coin::ZERO,
+        0,
+        NetworkId::Main,
+    );
+    let signature_proof =
+        SignatureProof::from(keypair.public, keypair.sign(&tx.serialize_content()));
+    tx.proof = signature_proof.serialize_to_vec();
+    let size = tx.serialized_size() as u64;
+
+    // Without rules, no fee is needed.
+    let f: MempoolFilter = Default::default();
+    assert_eq!(f.min_fee(&tx, true), Coin::ZERO);
+
+    // The highest of the minimum fees applies.
+    let mut s: Rules = Rules::default();
+    s.tx_fee = Coin::try_from(10).unwrap();
+    s.tx_fee_per_byte = 2.0;
+    let f = MempoolFilter::new(s.clone(), MempoolFilter::DEFAULT_BLACKLIST_SIZE);
+    assert_eq!(f.min_fee_per_byte(), 2.0);
+    assert_eq!(f.min_fee(&tx, false), Coin::try_from(2 * size).unwrap());
+
+    // The fee has to make up for a value that is too low.
+    s.tx_value_total = Coin::try_from(100 + 3 * size).unwrap();
+    let f = MempoolFilter::new(s.clone(), MempoolFilter::DEFAULT_BLACKLIST_SIZE);
+    assert_eq!(f.min_fee(&tx, false), Coin::try_from(3 * size).unwrap());
+
+    // Creating the recipient might cost more.
+    s.creation_fee = Coin::try_from(5 * size).unwrap();
+    let f = MempoolFilter::new(s, MempoolFilter::DEFAULT_BLACKLIST_SIZE);
+    assert_eq!(f.min_fee(&tx, false), Coin::try_from(3 * size).unwrap());
+    assert_eq!(f.min_fee(&tx, true), Coin::try_from(5 * size).unwrap());
+
+    // The computed fee is accepted.
+    tx.fee = f.min_fee(&tx, true);
+    assert!(f.accepts_transaction(&tx));
+    assert!(f.accepts_recipient_balance(&tx, Coin::ZERO, tx.value));
+}
@@ -8,6 +8,8 @@ use nimiq_database::WriteTransaction;
 use nimiq_hash::Hash;
 use nimiq_keys::Address;
 use nimiq_keys::{KeyPair, SecureGenerate};
+use nimiq_mempool::fee_estimation::{FeePriority, DEFAULT_FEE_ESTIMATION_BLOCKS};
+use nimiq_mempool::filter::Rules;
 use nimiq_mempool::{Mempool, MempoolConfig, ReturnCode};
 use nimiq_primitives::coin::Coin;
 use nimiq_primitives::networks::NetworkId;
@@ -291,3 +293,325 @@ fn reject_free_tx_beyond_limit() {
         }
     }
 }
+
+#[test]
+fn replace_tx_with_higher_fee() {
+    let env = VolatileEnvironment::new(10).unwrap();
+
+    let blockchain = Arc::new(Blockchain::new(env.clone(), NetworkId::UnitAlbatross).unwrap());
+
+    let mempool = Mempool::new(blockchain.clone(), MempoolConfig::default());
+
+    let keypair_a = KeyPair::generate_default_csprng();
+
+    let address_a = Address::from(&keypair_a.public);
+
+    let address_b = Address::from([2u8; Address::SIZE]);
+
+    // Give a reward to address_a.
+    let reward = Inherent {
+        ty: InherentType::Reward,
+        target: address_a.clone(),
+        value: Coin::from_u64_unchecked(10000),
+        data: vec![],
+    };
+
+    let mut txn = WriteTransaction::new(&env);
+
+    blockchain
+        .state()
+        .accounts
+        .commit(&mut txn, &[], &[reward], 1, 1)
+        .unwrap();
+
+    txn.commit();
+
+    let create_tx = |fee: u64| {
+        let mut tx = Transaction::new_basic(
+            address_a.clone(),
+            address_b.clone(),
+            Coin::from_u64_unchecked(10),
+            Coin::from_u64_unchecked(fee),
+            1,
+            NetworkId::UnitAlbatross,
+        );
+
+        let signature_proof =
+            SignatureProof::from(keypair_a.public, keypair_a.sign(&tx.serialize_content()));
+
+        tx.proof = signature_proof.serialize_to_vec();
+        tx
+    };
+
+    let tx1 = create_tx(200);
+    let hash1 = tx1.hash();
+    assert_eq!(mempool.push_transaction(tx1), ReturnCode::Accepted);
+
+    // A replacement with a lower fee is rejected.
+    assert_eq!(
+        mempool.push_transaction(create_tx(100)),
+        ReturnCode::ReplacementFeeTooLow
+    );
+    assert!(mempool.contains(&hash1));
+
+    // A replacement with a higher fee evicts the original transaction.
+    let tx2 = create_tx(300);
+    let hash2 = tx2.hash();
+    assert_eq!(mempool.push_transaction(tx2), ReturnCode::Accepted);
+    assert!(!mempool.contains(&hash1));
+    assert!(mempool.contains(&hash2));
+}
+
+#[test]
+fn replacement_only_changes_the_fee() {
+    let env = VolatileEnvironment::new(10).unwrap();
+
+    let blockchain = Arc::new(Blockchain::new(env.clone(), NetworkId::UnitAlbatross).unwrap());
+
+    let mempool = Mempool::new(blockchain.clone(), MempoolConfig::default());
+
+    let keypair_a = KeyPair::generate_default_csprng();
+
+    let address_a = Address::from(&keypair_a.public);
+
+    let address_b = Address::from([2u8; Address::SIZE]);
+
+    let address_c = Address::from([3u8; Address::SIZE]);
+
+    // Give a reward to address_a.
+    let reward = Inherent {
+        ty: InherentType::Reward,
+        target: address_a.clone(),
+        value: Coin::from_u64_unchecked(10000),
+        data: vec![],
+    };
+
+    let mut txn = WriteTransaction::new(&env);
+
+    blockchain
+        .state()
+        .accounts
+        .commit(&mut txn, &[], &[reward], 1, 1)
+        .unwrap();
+
+    txn.commit();
+
+    let create_tx = |recipient: &Address, value: u64, fee: u64| {
+        let mut tx = Transaction::new_basic(
+            address_a.clone(),
+            recipient.clone(),
+            Coin::from_u64_unchecked(value),
+            Coin::from_u64_unchecked(fee),
+            1,
+            NetworkId::UnitAlbatross,
+        );
+
+        let signature_proof =
+            SignatureProof::from(keypair_a.public, keypair_a.sign(&tx.serialize_content()));
+
+        tx.proof = signature_proof.serialize_to_vec();
+        tx
+    };
+
+    let tx1 = create_tx(&address_b, 10, 200);
+    let hash1 = tx1.hash();
+    assert_eq!(mempool.push_transaction(tx1), ReturnCode::Accepted);
+
+    // A transaction with the same sender and validity window but another value isn't a
+    // replacement, so it doesn't need to pay a higher fee and both are kept.
+    let tx2 = create_tx(&address_b, 20, 100);
+    let hash2 = tx2.hash();
+    assert_eq!(mempool.push_transaction(tx2), ReturnCode::Accepted);
+
+    // Neither is one to another recipient, even if it pays a higher fee.
+    let tx3 = create_tx(&address_c, 10, 300);
+    let hash3 = tx3.hash();
+    assert_eq!(mempool.push_transaction(tx3), ReturnCode::Accepted);
+
+    assert!(mempool.contains(&hash1));
+    assert!(mempool.contains(&hash2));
+    assert!(mempool.contains(&hash3));
+}
+
+#[test]
+fn evict_lowest_fee_tx_when_full() {
+    let env = VolatileEnvironment::new(10).unwrap();
+
+    let blockchain = Arc::new(Blockchain::new(env.clone(), NetworkId::UnitAlbatross).unwrap());
+
+    // Only leave room for two basic transactions.
+    let config = MempoolConfig {
+        size_limit: 2 * Transaction::MIN_SIZE,
+        ..Default::default()
+    };
+
+    let mempool = Mempool::new(blockchain.clone(), config);
+
+    let keypair_a = KeyPair::generate_default_csprng();
+
+    let address_a = Address::from(&keypair_a.public);
+
+    let address_b = Address::from([2u8; Address::SIZE]);
+
+    // Give a reward to address_a.
+    let reward = Inherent {
+        ty: InherentType::Reward,
+        target: address_a.clone(),
+        value: Coin::from_u64_unchecked(100000),
+        data: vec![],
+    };
+
+    let mut txn = WriteTransaction::new(&env);
+
+    blockchain
+        .state()
+        .accounts
+        .commit(&mut txn, &[], &[reward], 1, 1)
+        .unwrap();
+
+    txn.commit();
+
+    let create_tx = |value: u64, fee: u64| {
+        let mut tx = Transaction::new_basic(
+            address_a.clone(),
+            address_b.clone(),
+            Coin::from_u64_unchecked(value),
+            Coin::from_u64_unchecked(fee),
+            1,
+            NetworkId::UnitAlbatross,
+        );
+
+        let signature_proof =
+            SignatureProof::from(keypair_a.public, keypair_a.sign(&tx.serialize_content()));
+
+        tx.proof = signature_proof.serialize_to_vec();
+        tx
+    };
+
+    let tx1 = create_tx(1, 1000);
+    let hash1 = tx1.hash();
+    assert_eq!(mempool.push_transaction(tx1), ReturnCode::Accepted);
+
+    let tx2 = create_tx(2, 2000);
+    let hash2 = tx2.hash();
+    assert_eq!(mempool.push_transaction(tx2), ReturnCode::Accepted);
+
+    // The mempool is full, a transaction paying less than all pending ones is rejected.
+    assert_eq!(
+        mempool.push_transaction(create_tx(3, 500)),
+        ReturnCode::MempoolFull
+    );
+
+    // A transaction paying more evicts the lowest fee transaction.
+    let tx3 = create_tx(4, 3000);
+    let hash3 = tx3.hash();
+    assert_eq!(mempool.push_transaction(tx3), ReturnCode::Accepted);
+    assert!(!mempool.contains(&hash1));
+    assert!(mempool.contains(&hash2));
+    assert!(mempool.contains(&hash3));
+}
+
+#[test]
+fn estimate_fees() {
+    let env = VolatileEnvironment::new(10).unwrap();
+
+    let blockchain = Arc::new(Blockchain::new(env.clone(), NetworkId::UnitAlbatross).unwrap());
+
+    let mempool = Mempool::new(blockchain.clone(), MempoolConfig::default());
+
+    // Nothing is pending, so no fee is needed.
+    let estimate = mempool.estimate_fees(DEFAULT_FEE_ESTIMATION_BLOCKS);
+    assert_eq!(estimate.fast, 0.0);
+    assert_eq!(estimate.slow, 0.0);
+
+    let keypairs = vec![
+        KeyPair::generate_default_csprng(),
+        KeyPair::generate_default_csprng(),
+    ];
+
+    let address_b = Address::from([2u8; Address::SIZE]);
+
+    // Give a reward to the senders.
+    let rewards: Vec<Inherent> = keypairs
+        .iter()
+        .map(|keypair| Inherent {
+            ty: InherentType::Reward,
+            target: Address::from(&keypair.public),
+            value: Coin::from_u64_unchecked(100_000_000),
+            data: vec![],
+        })
+        .collect();
+
+    let mut txn = WriteTransaction::new(&env);
+
+    blockchain
+        .state()
+        .accounts
+        .commit(&mut txn, &[], &rewards, 1, 1)
+        .unwrap();
+
+    txn.commit();
+
+    // Fill more than one, but less than three blocks.
+    for keypair in &keypairs {
+        for i in 1..=400 {
+            let mut tx = Transaction::new_basic(
+                Address::from(&keypair.public),
+                address_b.clone(),
+                Coin::from_u64_unchecked(i),
+                Coin::from_u64_unchecked(i * 1000),
+                1,
+                NetworkId::UnitAlbatross,
+            );
+
+            let signature_proof =
+                SignatureProof::from(keypair.public, keypair.sign(&tx.serialize_content()));
+
+            tx.proof = signature_proof.serialize_to_vec();
+            assert_eq!(mempool.push_transaction(tx), ReturnCode::Accepted);
+        }
+    }
+
+    let estimate = mempool.estimate_fees(DEFAULT_FEE_ESTIMATION_BLOCKS);
+    assert!(estimate.fast > 0.0);
+    assert_eq!(estimate.normal, 0.0);
+    assert_eq!(estimate.slow, 0.0);
+}
+
+#[test]
+fn estimate_fees_respects_the_filter() {
+    let env = VolatileEnvironment::new(10).unwrap();
+
+    let blockchain = Arc::new(Blockchain::new(env, NetworkId::UnitAlbatross).unwrap());
+
+    let mut filter_rules = Rules::default();
+    filter_rules.tx_fee_per_byte = 2.0;
+    filter_rules.creation_fee = Coin::from_u64_unchecked(100_000);
+    let config = MempoolConfig {
+        filter_rules,
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, config);
+
+    // Nothing is pending, but the filter requires a fee.
+    let estimate = mempool.estimate_fees(DEFAULT_FEE_ESTIMATION_BLOCKS);
+    assert_eq!(estimate.fast, 2.0);
+    assert_eq!(estimate.slow, 2.0);
+
+    // Sending to a new account requires the creation fee.
+    let keypair = KeyPair::generate_default_csprng();
+    let mut tx = Transaction::new_basic(
+        Address::from(&keypair.public),
+        Address::from([2u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1),
+        Coin::ZERO,
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    let signature_proof =
+        SignatureProof::from(keypair.public, keypair.sign(&tx.serialize_content()));
+    tx.proof = signature_proof.serialize_to_vec();
+
+    let fee = mempool.estimate_fee(FeePriority::Normal, &tx, DEFAULT_FEE_ESTIMATION_BLOCKS);
+    assert_eq!(fee, Coin::from_u64_unchecked(100_000));
+}
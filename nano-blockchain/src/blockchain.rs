@@ -2,6 +2,7 @@ use std::sync::{Arc, RwLock};
 
 use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain::ChainInfo;
+use nimiq_database::Environment;
 use nimiq_genesis::NetworkInfo;
 use nimiq_primitives::networks::NetworkId;
 use nimiq_primitives::slots::Validators;
@@ -41,21 +42,61 @@ impl NanoBlockchain {
 
     /// Creates a new blockchain with a given network ID and genesis block.
     pub fn with_genesis(network_id: NetworkId, genesis_block: Block) -> Self {
-        let time = Arc::new(OffsetTime::new());
+        Self::with_chain_store(network_id, genesis_block, ChainStore::new())
+    }
 
-        let chain_info = ChainInfo::new(genesis_block.clone(), true);
+    /// Creates a new blockchain from a given network ID that is persisted to the given database
+    /// environment. If the environment already contains an election block, the blockchain starts
+    /// at that block instead of the genesis block. Only the election blocks and the latest proof
+    /// are persisted, so the blocks after the last election block are synced again on restart.
+    pub fn new_persistent(env: Environment, network_id: NetworkId) -> Self {
+        let network_info = NetworkInfo::from_network_id(network_id);
+        let genesis_block = network_info.genesis_block::<Block>();
+        Self::with_genesis_persistent(env, network_id, genesis_block)
+    }
+
+    /// Creates a new blockchain with a given network ID and genesis block that is persisted to the
+    /// given database environment.
+    pub fn with_genesis_persistent(
+        env: Environment,
+        network_id: NetworkId,
+        genesis_block: Block,
+    ) -> Self {
+        Self::with_chain_store(network_id, genesis_block, ChainStore::new_persistent(env))
+    }
+
+    /// Creates a new blockchain with a given network ID, genesis block and chain store. If the
+    /// chain store contains an election block more recent than the genesis block, that block becomes
+    /// the head of the chain.
+    ///
+    /// The chain infos are not persisted, not even by a persistent chain store. After a restart,
+    /// the chain starts at the last stored election block and any checkpoint or micro blocks that
+    /// followed it must be pushed again.
+    fn with_chain_store(
+        network_id: NetworkId,
+        genesis_block: Block,
+        mut chain_store: ChainStore,
+    ) -> Self {
+        let time = Arc::new(OffsetTime::new());
 
-        let mut chain_store = ChainStore::new();
+        // Get the block to start at. This is either the last stored election block or the genesis
+        // block.
+        let head = match chain_store.get_election_block() {
+            Some(block) if block.block_number() > genesis_block.block_number() => block,
+            _ => genesis_block.clone(),
+        };
 
-        chain_store.put_chain_info(chain_info);
+        // We only store the latest election block with its body, so we can't resume syncing the
+        // micro blocks of the batch that followed it. Start again at the election block.
+        chain_store.put_chain_info(ChainInfo::new(head.clone(), true));
 
         NanoBlockchain {
             network_id,
             time,
-            head: genesis_block.clone(),
-            macro_head: genesis_block.clone().unwrap_macro(),
-            election_head: genesis_block.clone().unwrap_macro(),
-            current_validators: genesis_block.validators(),
+            macro_head: head.clone().unwrap_macro(),
+            election_head: head.clone().unwrap_macro(),
+            current_validators: head.validators(),
+            head,
             genesis_block,
             chain_store: RwLock::new(chain_store),
         }
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
 
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
 use nimiq_block::{Block, MacroHeader};
 use nimiq_blockchain::ChainInfo;
+use nimiq_database::cursor::ReadCursor;
+use nimiq_database::{Database, DatabaseFlags, Environment, ReadTransaction, WriteTransaction};
 use nimiq_hash::Blake2bHash;
+use nimiq_nano_zkp::NanoProof;
 use nimiq_primitives::policy;
 
 /// A struct that stores the blocks for the blockchain.
@@ -14,18 +19,75 @@ pub struct ChainStore {
     height_idx: HashMap<u32, Vec<Blake2bHash>>,
     // A store of election block headers indexed by their epoch number.
     election_db: HashMap<u32, MacroHeader>,
+    // The database that the election blocks are persisted to, if any.
+    persistent: Option<PersistentStore>,
+}
+
+/// The databases backing a persistent ChainStore. Only the election blocks and the proof for the
+/// last one are persisted, since a node always restarts at its last election block. The chain infos
+/// of the current batch are kept in memory only.
+#[derive(Debug)]
+struct PersistentStore {
+    env: Environment,
+    // A database of election block headers indexed by their epoch number.
+    election_db: Database,
+    // A database containing the last election block (including its body) and the last verified
+    // zero-knowledge proof.
+    meta_db: Database,
 }
 
 impl ChainStore {
+    const ELECTION_DB_NAME: &'static str = "NanoElectionHeaders";
+    const META_DB_NAME: &'static str = "NanoMeta";
+
+    const ELECTION_BLOCK_KEY: &'static str = "election_block";
+    const ZKP_KEY: &'static str = "zkp";
+
     /// Creates a new ChainStore.
     pub fn new() -> Self {
         ChainStore {
             chain_db: HashMap::new(),
             height_idx: HashMap::new(),
             election_db: HashMap::new(),
+            persistent: None,
         }
     }
 
+    /// Creates a new ChainStore that is backed by the given database environment. Any election
+    /// headers already stored in the environment are loaded.
+    pub fn new_persistent(env: Environment) -> Self {
+        let persistent = PersistentStore {
+            election_db: env.open_database_with_flags(
+                Self::ELECTION_DB_NAME.to_string(),
+                DatabaseFlags::UINT_KEYS,
+            ),
+            meta_db: env.open_database(Self::META_DB_NAME.to_string()),
+            env,
+        };
+
+        let mut store = ChainStore::new();
+
+        {
+            let txn = ReadTransaction::new(&persistent.env);
+
+            let mut cursor = txn.cursor(&persistent.election_db);
+            let mut pos: Option<(u32, MacroHeader)> = cursor.first();
+
+            while let Some((epoch_number, header)) = pos {
+                store.election_db.insert(epoch_number, header);
+                pos = cursor.next();
+            }
+        }
+
+        store.persistent = Some(persistent);
+        store
+    }
+
+    /// Returns true if the contents of the ChainStore are persisted to a database.
+    pub fn is_persistent(&self) -> bool {
+        self.persistent.is_some()
+    }
+
     /// Gets a chain info by its hash. Returns None if the chain info doesn't exist.
     pub fn get_chain_info(&self, hash: &Blake2bHash) -> Option<&ChainInfo> {
         self.chain_db.get(hash)
@@ -102,8 +164,62 @@ impl ChainStore {
 
     /// Adds an election block header to the ChainStore.
     pub fn put_election(&mut self, header: MacroHeader) {
-        self.election_db
-            .insert(policy::epoch_at(header.block_number), header);
+        let epoch_number = policy::epoch_at(header.block_number);
+
+        // Persist the election header, if we have a database.
+        if let Some(persistent) = &self.persistent {
+            let mut txn = WriteTransaction::new(&persistent.env);
+            txn.put_reserve(&persistent.election_db, &epoch_number, &header);
+            txn.commit();
+        }
+
+        self.election_db.insert(epoch_number, header);
+    }
+
+    /// Gets the last election block that was stored, including its body. Returns None if the
+    /// ChainStore is not persistent or no election block was stored yet.
+    pub fn get_election_block(&self) -> Option<Block> {
+        let persistent = self.persistent.as_ref()?;
+
+        ReadTransaction::new(&persistent.env).get(&persistent.meta_db, Self::ELECTION_BLOCK_KEY)
+    }
+
+    /// Stores the last election block, including its body, together with the zero-knowledge proof
+    /// for it, if any. The body is necessary to restore the validators of the current epoch after
+    /// a restart. A proof stored for a previous election block is removed, so that the stored
+    /// proof always matches the stored block. This is a no-op if the ChainStore is not persistent.
+    pub fn put_election_block(&mut self, block: &Block, proof: Option<&NanoProof>) {
+        assert!(block.is_election());
+
+        if let Some(persistent) = &self.persistent {
+            let mut txn = WriteTransaction::new(&persistent.env);
+            txn.put_reserve(&persistent.meta_db, Self::ELECTION_BLOCK_KEY, block);
+
+            match proof {
+                Some(proof) => {
+                    let mut bytes = Vec::new();
+                    proof
+                        .serialize(&mut bytes)
+                        .expect("Couldn't serialize the zk proof!");
+
+                    txn.put(&persistent.meta_db, Self::ZKP_KEY, &bytes);
+                }
+                None => txn.remove(&persistent.meta_db, Self::ZKP_KEY),
+            }
+
+            txn.commit();
+        }
+    }
+
+    /// Gets the zero-knowledge proof for the stored election block. Returns None if the ChainStore
+    /// is not persistent or there is no proof for that block.
+    pub fn get_zkp(&self) -> Option<NanoProof> {
+        let persistent = self.persistent.as_ref()?;
+
+        let bytes: Vec<u8> =
+            ReadTransaction::new(&persistent.env).get(&persistent.meta_db, Self::ZKP_KEY)?;
+
+        NanoProof::deserialize(&mut &bytes[..]).ok()
     }
 
     /// Clears the ChainStore of all blocks (except the election blocks). This can be used at the
@@ -119,6 +235,9 @@ mod tests {
     use rand::prelude::*;
 
     use nimiq_block::{MicroBlock, MicroBody, MicroHeader, MicroJustification};
+    use nimiq_database::volatile::VolatileEnvironment;
+    use nimiq_genesis::NetworkInfo;
+    use nimiq_primitives::networks::NetworkId;
 
     use super::*;
 
@@ -227,4 +346,52 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn persistent_store_is_reloaded() {
+        // Create a block.
+        let mut data = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut data);
+        let hash = Blake2bHash::from(data);
+
+        let block = Block::Micro(MicroBlock {
+            header: MicroHeader {
+                version: random(),
+                block_number: 1,
+                view_number: random(),
+                timestamp: random(),
+                parent_hash: hash.clone(),
+                seed: Default::default(),
+                extra_data: vec![],
+                state_root: hash.clone(),
+                body_root: hash.clone(),
+                history_root: hash.clone(),
+            },
+            justification: None,
+            body: None,
+        });
+
+        let election_block =
+            NetworkInfo::from_network_id(NetworkId::UnitAlbatross).genesis_block::<Block>();
+
+        let env = VolatileEnvironment::new(10).unwrap();
+
+        // Store an election block and a chain info in a persistent chain store.
+        let mut store = ChainStore::new_persistent(env.clone());
+        assert!(store.is_persistent());
+        store.put_election_block(&election_block, None);
+        store.put_election(election_block.unwrap_macro_ref().header.clone());
+        store.put_chain_info(ChainInfo::new(block, true));
+        drop(store);
+
+        // Only the election block is loaded when opening the chain store again.
+        let store = ChainStore::new_persistent(env);
+        assert_eq!(
+            store.get_election_block().map(|block| block.hash()),
+            Some(election_block.hash())
+        );
+        assert!(store.get_election(0).is_some());
+        assert!(store.get_zkp().is_none());
+        assert!(store.get_chain_info_at(1).is_none());
+    }
 }
@@ -1,5 +1,6 @@
 pub use blockchain::NanoBlockchain;
 pub use chain_store::ChainStore;
+pub use error::NanoError;
 
 pub(crate) mod abstract_blockchain;
 pub(crate) mod blockchain;
@@ -126,6 +126,10 @@ impl NanoBlockchain {
 
                 self.current_validators = macro_block.get_validators();
 
+                // Store the election block, so that we can restart at this block. We have no proof
+                // for it, so this also removes the proof for the previous election block.
+                chain_store_w.put_election_block(&chain_info.head, None);
+
                 // Store the election block header.
                 chain_store_w.put_election(macro_block.header.clone());
             }
@@ -64,7 +64,7 @@ impl NanoBlockchain {
             final_block_number,
             final_header_hash,
             final_public_keys,
-            proof,
+            proof.clone(),
         );
 
         if verify_result.is_err() || !verify_result.unwrap() {
@@ -92,6 +92,9 @@ impl NanoBlockchain {
         // Store the election block header.
         chain_store_w.put_election(block.unwrap_macro_ref().header.clone());
 
+        // Store the election block and the proof, so that we can restart at this block.
+        chain_store_w.put_election_block(&block, Some(&proof));
+
         // Update the blockchain.
         self.head = block.clone();
 
@@ -193,6 +196,10 @@ impl NanoBlockchain {
 
             self.current_validators = block.validators();
 
+            // Store the election block, so that we can restart at this block. We have no proof for
+            // it, so this also removes the proof for the previous election block.
+            chain_store_w.put_election_block(&block, None);
+
             // Store the election block header.
             chain_store_w.put_election(block.unwrap_macro().header);
         }
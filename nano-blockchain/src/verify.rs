@@ -1,7 +1,12 @@
 use nimiq_account::Account;
-use nimiq_blockchain::{AbstractBlockchain, HistoryTreeProof};
-use nimiq_hash::Blake2bHash;
+use nimiq_blockchain::{AbstractBlockchain, ExtendedTransaction, HistoryTreeProof};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_primitives::policy;
+use nimiq_tree_primitives::accounts_proof::AccountsProof;
 use nimiq_tree_primitives::accounts_tree_chunk::AccountsTreeChunk;
+use nimiq_tree_primitives::accounts_tree_node::AccountsTreeNode;
+use nimiq_tree_primitives::address_nibbles::AddressNibbles;
 
 use crate::blockchain::NanoBlockchain;
 use crate::error::NanoError;
@@ -35,6 +40,89 @@ impl NanoBlockchain {
         Ok(())
     }
 
+    /// Verify a Merkle proof for a set of accounts. It checks if the proof is valid for the Accounts
+    /// Tree at the block with the given hash and returns the accounts for the given addresses, in
+    /// the same order. Accounts that don't exist are returned as `Account::INITIAL`, like in the
+    /// Accounts Tree. It returns an error if the proof doesn't prove all of the addresses.
+    pub fn check_accounts_proof(
+        &self,
+        block_hash: Blake2bHash,
+        mut accounts_proof: AccountsProof<Account>,
+        addresses: &[Address],
+    ) -> Result<Vec<Account>, NanoError> {
+        // Get the block.
+        let block = self
+            .get_block(&block_hash, false, None)
+            .ok_or(NanoError::MissingBlock)?;
+
+        // An empty proof can't be valid.
+        if accounts_proof.nodes().is_empty() {
+            return Err(NanoError::WrongProof);
+        }
+
+        // Check the root of the accounts proof against the state root.
+        if &accounts_proof.root_hash() != block.state_root() {
+            return Err(NanoError::WrongProof);
+        }
+
+        // Verify the accounts proof.
+        if !accounts_proof.verify() {
+            return Err(NanoError::WrongProof);
+        }
+
+        addresses
+            .iter()
+            .map(|address| {
+                Ok(Self::account_from_proof(&accounts_proof, address)?.unwrap_or(Account::INITIAL))
+            })
+            .collect()
+    }
+
+    /// Gets an account from a verified accounts proof. It follows the path to the account from the
+    /// root of the proof and returns None if the path proves that the account doesn't exist. It
+    /// returns an error if a node on the path is missing from the proof.
+    fn account_from_proof(
+        accounts_proof: &AccountsProof<Account>,
+        address: &Address,
+    ) -> Result<Option<Account>, NanoError> {
+        let prefix = AddressNibbles::from(address);
+
+        // The root is the last node of the proof.
+        let mut node = accounts_proof.nodes().last().ok_or(NanoError::WrongProof)?;
+
+        loop {
+            match node {
+                AccountsTreeNode::TerminalNode {
+                    prefix: node_prefix,
+                    account,
+                } => {
+                    return Ok(if node_prefix == &prefix {
+                        Some(account.clone())
+                    } else {
+                        None
+                    });
+                }
+                AccountsTreeNode::BranchNode { .. } => {
+                    // If the branch diverges from the address, the account doesn't exist.
+                    if !node.prefix().is_prefix_of(&prefix) {
+                        return Ok(None);
+                    }
+
+                    let child_prefix = match node.get_child_prefix(&prefix) {
+                        Some(child_prefix) => child_prefix,
+                        None => return Ok(None),
+                    };
+
+                    node = accounts_proof
+                        .nodes()
+                        .iter()
+                        .find(|node| node.prefix() == &child_prefix)
+                        .ok_or(NanoError::WrongProof)?;
+                }
+            }
+        }
+    }
+
     /// Verify a Merkle proof for a transaction. It checks if the transaction is part of the History
     /// Tree at the block with the given hash. It returns Ok if the proof is valid.
     pub fn check_tx(
@@ -57,4 +145,98 @@ impl NanoBlockchain {
 
         Ok(())
     }
+
+    /// Verify a Merkle proof for a set of transactions, as served by full nodes. It checks if the
+    /// proof is valid for the History Tree at the block with the given hash and returns the proven
+    /// extended transactions. Past epochs are proven against their election block, which doesn't
+    /// need to be in the chain store as long as we know its header.
+    pub fn check_transactions_proof(
+        &self,
+        block_hash: &Blake2bHash,
+        proof: &HistoryTreeProof,
+    ) -> Result<Vec<ExtendedTransaction>, NanoError> {
+        // An empty proof doesn't prove anything.
+        let first_ext_tx = proof.history.first().ok_or(NanoError::WrongProof)?;
+
+        // Get the history root, either from the block or from the election headers.
+        let history_root = match self.get_block(block_hash, false, None) {
+            Some(block) => block.history_root().clone(),
+            None => self
+                .chain_store
+                .read()
+                .unwrap()
+                .get_election(policy::epoch_at(first_ext_tx.block_number))
+                .filter(|header| header.hash::<Blake2bHash>() == *block_hash)
+                .map(|header| header.history_root.clone())
+                .ok_or(NanoError::MissingBlock)?,
+        };
+
+        // Verify the History Tree proof.
+        if !proof.verify(history_root).unwrap_or(false) {
+            return Err(NanoError::WrongProof);
+        }
+
+        Ok(proof.history.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use nimiq_account::BasicAccount;
+    use nimiq_accounts::Accounts;
+    use nimiq_database::volatile::VolatileEnvironment;
+    use nimiq_database::{ReadTransaction, WriteTransaction};
+    use nimiq_primitives::coin::Coin;
+
+    use super::*;
+
+    #[test]
+    fn it_can_get_accounts_from_proof() {
+        let env = VolatileEnvironment::new(10).unwrap();
+        let accounts = Accounts::new(env.clone());
+
+        let address1 = Address::from([1u8; Address::SIZE]);
+        let address2 = Address::from([2u8; Address::SIZE]);
+        let address3 = Address::from([3u8; Address::SIZE]);
+
+        let account1 = Account::Basic(BasicAccount {
+            balance: Coin::try_from(25).unwrap(),
+        });
+        let account2 = Account::Basic(BasicAccount {
+            balance: Coin::try_from(1322).unwrap(),
+        });
+
+        let mut txn = WriteTransaction::new(&env);
+        accounts.init(
+            &mut txn,
+            vec![
+                (address1.clone(), account1.clone()),
+                (address2.clone(), account2.clone()),
+            ],
+        );
+        txn.commit();
+
+        // Prove an existing and a missing account.
+        let txn = ReadTransaction::new(&env);
+        let mut proof = accounts.get_accounts_proof(&txn, &[address1.clone(), address3.clone()]);
+        assert!(proof.verify());
+        assert_eq!(proof.root_hash(), accounts.hash(None));
+
+        assert_eq!(
+            NanoBlockchain::account_from_proof(&proof, &address1),
+            Ok(Some(account1))
+        );
+        assert_eq!(
+            NanoBlockchain::account_from_proof(&proof, &address3),
+            Ok(None)
+        );
+
+        // The second account is not part of the proof, so the proof can't tell if it exists.
+        assert_eq!(
+            NanoBlockchain::account_from_proof(&proof, &address2),
+            Err(NanoError::WrongProof)
+        );
+    }
 }
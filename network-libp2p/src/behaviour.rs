@@ -10,6 +10,7 @@ use libp2p::{
     },
     identify::{Identify, IdentifyEvent},
     kad::{store::MemoryStore, Kademlia, KademliaEvent},
+    ping::{Ping, PingEvent, PingFailure},
     swarm::{NetworkBehaviourAction, NetworkBehaviourEventProcess, PollParameters},
     NetworkBehaviour,
 };
@@ -36,12 +37,15 @@ use crate::{
 pub type NimiqNetworkBehaviourError = EitherError<
     EitherError<
         EitherError<
-            EitherError<EitherError<DiscoveryError, ConnectionPoolError>, MessageError>,
-            std::io::Error,
+            EitherError<
+                EitherError<EitherError<DiscoveryError, ConnectionPoolError>, MessageError>,
+                std::io::Error,
+            >,
+            GossipsubHandlerError,
         >,
-        GossipsubHandlerError,
+        ReadOneError,
     >,
-    ReadOneError,
+    PingFailure,
 >;
 
 #[derive(Debug)]
@@ -52,6 +56,7 @@ pub enum NimiqEvent {
     Identify(IdentifyEvent),
     Discovery(DiscoveryEvent),
     Peers(ConnectionPoolEvent),
+    Ping(PingEvent),
 }
 
 impl From<NetworkEvent<Peer>> for NimiqEvent {
@@ -90,6 +95,12 @@ impl From<DiscoveryEvent> for NimiqEvent {
     }
 }
 
+impl From<PingEvent> for NimiqEvent {
+    fn from(event: PingEvent) -> Self {
+        Self::Ping(event)
+    }
+}
+
 #[derive(NetworkBehaviour)]
 #[behaviour(out_event = "NimiqEvent", poll_method = "poll_event")]
 pub struct NimiqBehaviour {
@@ -100,6 +111,7 @@ pub struct NimiqBehaviour {
     pub kademlia: Kademlia<MemoryStore>,
     pub gossipsub: Gossipsub,
     pub identify: Identify,
+    pub ping: Ping,
 
     #[behaviour(ignore)]
     peer_contact_book: Arc<RwLock<PeerContactBook>>,
@@ -147,6 +159,8 @@ impl NimiqBehaviour {
             public_key,
         );
 
+        let ping = Ping::new(config.ping);
+
         let params = PeerScoreParams::default();
         let thresholds = PeerScoreThresholds::default();
         let update_scores = tokio::time::interval(params.decay_interval);
@@ -162,6 +176,7 @@ impl NimiqBehaviour {
             kademlia,
             gossipsub,
             identify,
+            ping,
             peer_contact_book,
             update_scores,
             events: VecDeque::new(),
@@ -169,6 +184,10 @@ impl NimiqBehaviour {
         }
     }
 
+    pub fn peer_contact_book(&self) -> &Arc<RwLock<PeerContactBook>> {
+        &self.peer_contact_book
+    }
+
     fn poll_event<T>(
         &mut self,
         cx: &mut Context,
@@ -247,3 +266,10 @@ impl NetworkBehaviourEventProcess<ConnectionPoolEvent> for NimiqBehaviour {
         self.emit_event(event);
     }
 }
+
+impl NetworkBehaviourEventProcess<PingEvent> for NimiqBehaviour {
+    fn inject_event(&mut self, event: PingEvent) {
+        log::trace!("NimiqBehaviour::inject_event: {:?}", event);
+        self.emit_event(event);
+    }
+}
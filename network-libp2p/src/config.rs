@@ -1,7 +1,10 @@
+use std::num::NonZeroU32;
+
 use libp2p::{
     gossipsub::{GossipsubConfig, GossipsubConfigBuilder},
     identity::Keypair,
     kad::KademliaConfig,
+    ping::PingConfig,
 };
 
 use nimiq_hash::Blake2bHash;
@@ -22,9 +25,13 @@ pub struct Config {
     pub message: MessageConfig,
     pub kademlia: KademliaConfig,
     pub gossipsub: GossipsubConfig,
+    pub ping: PingConfig,
 }
 
 impl Config {
+    /// The number of consecutive failed pings after which the connection to a peer is closed.
+    pub const PING_MAX_FAILURES: u32 = 3;
+
     pub fn new(keypair: Keypair, peer_contact: PeerContact, genesis_hash: Blake2bHash) -> Self {
         // Hardcoding the minimum number of peers in mesh network before adding more
         // TODO: Maybe change this to a mesh limits configuration argument of this function
@@ -34,6 +41,14 @@ impl Config {
             .build()
             .expect("Invalid Gossipsub config");
 
+        // Pings are used to measure the latency to our peers and to detect dead connections. A
+        // single failed ping (e.g. on a congested connection) doesn't close the connection, only
+        // several consecutive failures do. Pings don't keep otherwise idle connections alive, this
+        // is left to the connection pool.
+        let ping_config = PingConfig::new()
+            .with_max_failures(NonZeroU32::new(Self::PING_MAX_FAILURES).unwrap())
+            .with_keep_alive(false);
+
         Self {
             keypair,
             peer_contact,
@@ -41,6 +56,7 @@ impl Config {
             message: MessageConfig::default(),
             kademlia: KademliaConfig::default(),
             gossipsub: gossipsub_config,
+            ping: ping_config,
             min_peers: 5,
         }
     }
@@ -24,4 +24,4 @@ pub use libp2p::{self, core::network::NetworkInfo, identity::Keypair, Multiaddr,
 
 pub use config::Config;
 pub use error::NetworkError;
-pub use network::Network;
+pub use network::{Network, PeerInfo};
@@ -1,6 +1,11 @@
 #![allow(dead_code)]
 
-use std::{collections::HashMap, pin::Pin, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use bytes::{buf::BufExt, Bytes};
@@ -13,7 +18,9 @@ use futures::{
 use libp2p::{
     core,
     core::{
-        connection::ConnectionLimits, muxing::StreamMuxerBox, network::NetworkInfo,
+        connection::{ConnectedPoint, ConnectionLimits},
+        muxing::StreamMuxerBox,
+        network::NetworkInfo,
         transport::Boxed,
     },
     dns,
@@ -24,6 +31,7 @@ use libp2p::{
     identity::Keypair,
     kad::{GetRecordOk, KademliaEvent, QueryId, QueryResult, Quorum, Record},
     noise,
+    ping::{PingEvent, PingSuccess},
     swarm::{NetworkBehaviourAction, NotifyHandler, SwarmBuilder, SwarmEvent},
     tcp, websocket, yamux, Multiaddr, PeerId, Swarm, Transport,
 };
@@ -45,7 +53,7 @@ use nimiq_utils::time::OffsetTime;
 use crate::{
     behaviour::{NimiqBehaviour, NimiqEvent, NimiqNetworkBehaviourError},
     connection_pool::behaviour::ConnectionPoolEvent,
-    discovery::handler::HandlerInEvent,
+    discovery::{handler::HandlerInEvent, peer_contacts::PeerContactInfo},
     message::peer::Peer,
     Config, NetworkError,
 };
@@ -101,6 +109,33 @@ pub(crate) enum NetworkAction {
     ListenOnAddresses {
         listen_addresses: Vec<Multiaddr>,
     },
+    PeerInfos {
+        output: oneshot::Sender<Vec<PeerInfo>>,
+    },
+    DisconnectPeer {
+        peer_id: PeerId,
+    },
+    BanPeer {
+        peer_id: PeerId,
+    },
+    UnbanPeer {
+        peer_id: PeerId,
+    },
+}
+
+/// Information about a connected peer, e.g. to debug connectivity.
+#[derive(Clone, Debug)]
+pub struct PeerInfo {
+    pub peer_id: PeerId,
+    /// The endpoint of our connection to the peer. This tells the remote address and whether we
+    /// dialed the peer or it dialed us.
+    pub endpoint: ConnectedPoint,
+    /// The peer contact the peer announced, if we know it.
+    pub contact: Option<Arc<PeerContactInfo>>,
+    /// The last round-trip time measured by pinging the peer.
+    pub latency: Option<Duration>,
+    /// The gossipsub topics the peer is subscribed to.
+    pub topics: Vec<TopicHash>,
 }
 
 struct TaskState {
@@ -109,6 +144,9 @@ struct TaskState {
     gossip_topics: HashMap<TopicHash, (mpsc::Sender<(GossipsubMessage, MessageId, PeerId)>, bool)>,
     incoming_listeners: HashMap<Multiaddr, Multiaddr>,
     is_connected: bool,
+    peer_endpoints: HashMap<PeerId, ConnectedPoint>,
+    peer_latencies: HashMap<PeerId, Duration>,
+    peer_topics: HashMap<PeerId, HashSet<TopicHash>>,
 }
 
 impl Default for TaskState {
@@ -119,6 +157,9 @@ impl Default for TaskState {
             gossip_topics: HashMap::new(),
             incoming_listeners: HashMap::new(),
             is_connected: false,
+            peer_endpoints: HashMap::new(),
+            peer_latencies: HashMap::new(),
+            peer_topics: HashMap::new(),
         }
     }
 }
@@ -275,6 +316,11 @@ impl Network {
                 endpoint,
                 num_established,
             } => {
+                state
+                    .peer_endpoints
+                    .entry(peer_id)
+                    .or_insert_with(|| endpoint.clone());
+
                 if let Some(listen_addr) = state
                     .incoming_listeners
                     .get(&endpoint.get_remote_address().clone())
@@ -343,7 +389,18 @@ impl Network {
                 state.incoming_listeners.remove(&send_back_addr);
             }
 
-            //SwarmEvent::ConnectionClosed { .. } => {},
+            SwarmEvent::ConnectionClosed {
+                peer_id,
+                num_established,
+                ..
+            } => {
+                if num_established == 0 {
+                    state.peer_endpoints.remove(&peer_id);
+                    state.peer_latencies.remove(&peer_id);
+                    state.peer_topics.remove(&peer_id);
+                }
+            }
+
             SwarmEvent::Behaviour(event) => {
                 match event {
                     NimiqEvent::Message(event) => {
@@ -421,9 +478,13 @@ impl Network {
                         }
                         GossipsubEvent::Subscribed { peer_id, topic } => {
                             tracing::debug!(peer_id = ?peer_id, topic = ?topic, "peer subscribed to topic");
+                            state.peer_topics.entry(peer_id).or_default().insert(topic);
                         }
                         GossipsubEvent::Unsubscribed { peer_id, topic } => {
                             tracing::debug!(peer_id = ?peer_id, topic = ?topic, "peer unsubscribed");
+                            if let Some(topics) = state.peer_topics.get_mut(&peer_id) {
+                                topics.remove(&topic);
+                            }
                         }
                     },
                     NimiqEvent::Identify(event) => {
@@ -469,6 +530,17 @@ impl Network {
                             }
                         }
                     }
+                    NimiqEvent::Ping(PingEvent { peer, result }) => match result {
+                        Ok(PingSuccess::Ping { rtt }) => {
+                            state.peer_latencies.insert(peer, rtt);
+                        }
+                        Ok(PingSuccess::Pong) => {}
+                        Err(e) => {
+                            // The connection is only closed by the ping handler after
+                            // `Config::PING_MAX_FAILURES` consecutive failures.
+                            tracing::debug!(peer_id = ?peer, error = ?e, "ping failed");
+                        }
+                    },
                 }
             }
             _ => {}
@@ -586,6 +658,38 @@ impl Network {
                         .expect("Failed to listen on provided address");
                 }
             }
+            NetworkAction::PeerInfos { output } => {
+                let peer_contact_book = swarm.peer_contact_book().read();
+
+                let peer_infos = state
+                    .peer_endpoints
+                    .iter()
+                    .map(|(peer_id, endpoint)| PeerInfo {
+                        peer_id: *peer_id,
+                        endpoint: endpoint.clone(),
+                        contact: peer_contact_book.get(peer_id),
+                        latency: state.peer_latencies.get(peer_id).copied(),
+                        topics: state
+                            .peer_topics
+                            .get(peer_id)
+                            .map(|topics| topics.iter().cloned().collect())
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+
+                output.send(peer_infos).ok();
+            }
+            NetworkAction::DisconnectPeer { peer_id } => {
+                // Workaround to trigger a peer disconnection
+                Swarm::ban_peer_id(swarm, peer_id);
+                Swarm::unban_peer_id(swarm, peer_id);
+            }
+            NetworkAction::BanPeer { peer_id } => {
+                Swarm::ban_peer_id(swarm, peer_id);
+            }
+            NetworkAction::UnbanPeer { peer_id } => {
+                Swarm::unban_peer_id(swarm, peer_id);
+            }
         }
 
         Ok(())
@@ -601,6 +705,44 @@ impl Network {
         Ok(output_rx.await?)
     }
 
+    /// Returns information about all peers we are connected to.
+    pub async fn peer_infos(&self) -> Result<Vec<PeerInfo>, NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .clone()
+            .send(NetworkAction::PeerInfos { output: output_tx })
+            .await?;
+        Ok(output_rx.await?)
+    }
+
+    /// Closes all connections to a peer. The peer may connect again.
+    pub async fn disconnect_peer(&self, peer_id: PeerId) -> Result<(), NetworkError> {
+        self.action_tx
+            .clone()
+            .send(NetworkAction::DisconnectPeer { peer_id })
+            .await?;
+        Ok(())
+    }
+
+    /// Closes all connections to a peer and refuses new connections from and to it until it is
+    /// unbanned. Bans are not persisted.
+    pub async fn ban_peer(&self, peer_id: PeerId) -> Result<(), NetworkError> {
+        self.action_tx
+            .clone()
+            .send(NetworkAction::BanPeer { peer_id })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn unban_peer(&self, peer_id: PeerId) -> Result<(), NetworkError> {
+        self.action_tx
+            .clone()
+            .send(NetworkAction::UnbanPeer { peer_id })
+            .await?;
+        Ok(())
+    }
+
     pub async fn listen_on_addresses(&self, listen_addresses: Vec<Multiaddr>) {
         self.action_tx
             .clone()
@@ -830,13 +972,14 @@ impl NetworkInterface for Network {
 
 #[cfg(test)]
 mod tests {
-    use std::{sync::Arc, time::Duration};
+    use std::{num::NonZeroU32, sync::Arc, time::Duration};
 
     use futures::{Stream, StreamExt};
     use libp2p::{
         gossipsub::GossipsubConfigBuilder,
         identity::Keypair,
         multiaddr::{multiaddr, Multiaddr},
+        ping::PingConfig,
         swarm::KeepAlive,
         PeerId,
     };
@@ -912,6 +1055,8 @@ mod tests {
             message: Default::default(),
             kademlia: Default::default(),
             gossipsub,
+            ping: PingConfig::new()
+                .with_max_failures(NonZeroU32::new(Config::PING_MAX_FAILURES).unwrap()),
         }
     }
 
@@ -973,14 +1118,24 @@ mod tests {
     }
 
     async fn create_connected_networks() -> (Network, Network) {
-        tracing::debug!("creating connected test networks:");
         let addr1 = multiaddr![Memory(thread_rng().gen::<u64>())];
         let addr2 = multiaddr![Memory(thread_rng().gen::<u64>())];
 
-        let net1 = Network::new(Arc::new(OffsetTime::new()), network_config(addr1.clone())).await;
+        create_connected_networks_with_config(network_config(addr1), network_config(addr2)).await
+    }
+
+    async fn create_connected_networks_with_config(
+        config1: Config,
+        config2: Config,
+    ) -> (Network, Network) {
+        tracing::debug!("creating connected test networks:");
+        let addr1 = config1.peer_contact.addresses[0].clone();
+        let addr2 = config2.peer_contact.addresses[0].clone();
+
+        let net1 = Network::new(Arc::new(OffsetTime::new()), config1).await;
         net1.listen_on_addresses(vec![addr1.clone()]).await;
 
-        let net2 = Network::new(Arc::new(OffsetTime::new()), network_config(addr2.clone())).await;
+        let net2 = Network::new(Arc::new(OffsetTime::new()), config2).await;
         net2.listen_on_addresses(vec![addr2.clone()]).await;
 
         tracing::debug!(address = ?addr1, peer_id = ?net1.local_peer_id, "Network 1");
@@ -1084,6 +1239,36 @@ mod tests {
         assert_eq!(msg2.id, 420);
     }
 
+    #[tokio::test]
+    async fn connection_survives_a_failed_ping() {
+        let addr1 = multiaddr![Memory(thread_rng().gen::<u64>())];
+        let addr2 = multiaddr![Memory(thread_rng().gen::<u64>())];
+
+        // The pings of the first network time out immediately.
+        let mut config1 = network_config(addr1);
+        config1.ping = config1.ping.with_timeout(Duration::from_nanos(1));
+
+        let (net1, net2) =
+            create_connected_networks_with_config(config1, network_config(addr2)).await;
+
+        // Give the first ping enough time to fail.
+        tokio::time::delay_for(Duration::from_secs(1)).await;
+
+        assert_eq!(net1.get_peers().len(), 1);
+        assert_eq!(net2.get_peers().len(), 1);
+
+        // The peers can still talk to each other.
+        let peer2 = net1.get_peer(*net2.local_peer_id()).unwrap();
+        let peer1 = net2.get_peer(*net1.local_peer_id()).unwrap();
+
+        let mut msgs = peer1.receive::<TestMessage>();
+
+        peer2.send(&TestMessage { id: 4711 }).await.unwrap();
+
+        let msg = msgs.next().await.unwrap();
+        assert_eq!(msg.id, 4711);
+    }
+
     fn assert_peer_left(event: &NetworkEvent<Peer>, peer_id: &PeerId) {
         if let NetworkEvent::PeerLeft(peer) = event {
             assert_eq!(&peer.id, peer_id);
@@ -39,10 +39,10 @@ pub struct InactiveStake {
 /// lost rewards or parked by a specific slash inherent. This is necessary to be able to revert
 /// slash inherents.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
-struct SlashReceipt {
-    newly_parked: bool,
-    newly_disabled: bool,
-    newly_lost_rewards: bool,
+pub struct SlashReceipt {
+    pub newly_parked: bool,
+    pub newly_disabled: bool,
+    pub newly_lost_rewards: bool,
 }
 
 /// The struct representing the staking contract. The staking contract is a special contract that
@@ -1,18 +1,29 @@
+use std::fs;
+use std::path::PathBuf;
+
 use anyhow::{bail, Error};
+use beserial::{Deserialize, Serialize};
 use futures::stream::StreamExt;
 use structopt::StructOpt;
 
+use nimiq_hash::Blake2bHash;
 use nimiq_jsonrpc_core::Credentials;
-use nimiq_keys::Address;
+use nimiq_keys::{Address, PublicKey};
 use nimiq_primitives::account::ValidatorId;
 use nimiq_primitives::coin::Coin;
+use nimiq_primitives::networks::NetworkId;
 use nimiq_rpc_client::Client;
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     consensus::ConsensusInterface,
-    types::{BlockNumberOrHash, ValidityStartHeight},
+    mempool::MempoolInterface,
+    types::{BlockNumberOrHash, HistoryFilter, TransactionDirection, ValidityStartHeight},
     wallet::WalletInterface,
 };
+use nimiq_transaction::{
+    account::htlc_contract::{AnyHash, HashAlgorithm},
+    Transaction,
+};
 
 #[derive(Debug, StructOpt)]
 struct Opt {
@@ -42,7 +53,48 @@ enum Command {
     },
 
     /// Lists the current stakes from the staking contract.
-    Stakes {},
+    Stakes {
+        /// List the stakes after this block instead. Requires the node to run in archive mode.
+        #[structopt(long)]
+        block: Option<u32>,
+    },
+
+    /// Lists the most recent transactions and inherents of an address.
+    History {
+        address: Address,
+
+        /// Only show `incoming` or `outgoing` transactions. Inherents are incoming.
+        #[structopt(short, long)]
+        direction: Option<TransactionDirection>,
+
+        /// Continue after this transaction hash or before this block number.
+        #[structopt(long)]
+        start_after: Option<BlockNumberOrHash>,
+
+        /// Only show transactions from this block number on.
+        #[structopt(long)]
+        min_block: Option<u32>,
+
+        /// Only show transactions up to this block number.
+        #[structopt(long)]
+        max_block: Option<u32>,
+
+        /// Only show transactions from this timestamp (in milliseconds) on.
+        #[structopt(long)]
+        min_timestamp: Option<u64>,
+
+        /// Only show transactions up to this timestamp (in milliseconds).
+        #[structopt(long)]
+        max_timestamp: Option<u64>,
+
+        /// The maximum number of transactions and inherents to look at.
+        #[structopt(short, long)]
+        max: Option<u16>,
+
+        /// Only show the number of transactions and inherents.
+        #[structopt(short, long)]
+        count: bool,
+    },
 
     /// Follow the head of the blockchain.
     Follow {
@@ -51,12 +103,156 @@ enum Command {
         block: bool,
     },
 
+    /// Follow changes to the staking contract. If no filters are given, all changes are shown.
+    StakingEvents {
+        /// Only show changes affecting these validators.
+        #[structopt(short, long = "validator")]
+        validator_ids: Vec<ValidatorId>,
+
+        /// Only show changes affecting these stakers.
+        #[structopt(short, long = "staker")]
+        staker_addresses: Vec<Address>,
+    },
+
     /// Show wallet accounts and their balances.
     Account(AccountCommand),
 
     /// Create, sign and send transactions.
     #[structopt(name = "tx")]
     Transaction(TransactionCommand),
+
+    /// Inspect the transactions pending in the mempool.
+    Mempool(MempoolCommand),
+
+    /// Manage multisig accounts and sign transactions with them.
+    Multisig(MultisigCommand),
+}
+
+#[derive(Debug, StructOpt)]
+enum MempoolCommand {
+    /// Shows the number of pending transactions grouped by fee per byte.
+    Info {},
+
+    /// Lists the pending transactions, sorted descending by fee per byte.
+    Content {
+        /// Show the full transactions instead of only their hashes.
+        #[structopt(short = "t")]
+        include_transactions: bool,
+    },
+
+    /// Shows a single pending transaction.
+    Tx {
+        /// The hash of the transaction.
+        hash: Blake2bHash,
+    },
+
+    /// Suggests fees per byte for fast, normal and slow inclusion in a block.
+    Fees {
+        /// The number of recent micro blocks to consider.
+        #[structopt(short = "n", long)]
+        num_blocks: Option<u32>,
+    },
+
+    /// Follows transactions being added to and removed from the mempool.
+    Follow {
+        /// Only show transactions sent from or to these addresses. If omitted, all transactions are shown.
+        addresses: Vec<Address>,
+    },
+}
+
+/// Signing a transaction from a multisig account takes three rounds between the signers:
+///
+///  1. Every signer creates a commitment with `commit` and shares the commitment (but not the
+///     random secret) with the others.
+///  2. Every signer creates a partial signature with `sign` and shares it with the others.
+///  3. One signer combines the partial signatures with `combine`.
+#[derive(Debug, StructOpt)]
+enum MultisigCommand {
+    /// Adds a multisig account controlled by `min_signatures` out of the given public keys.
+    New {
+        min_signatures: u8,
+
+        public_keys: Vec<PublicKey>,
+    },
+
+    /// Lists the multisig accounts.
+    List {},
+
+    /// Shows the participants of a multisig account.
+    Get { address: Address },
+
+    /// Creates an unsigned transaction from a multisig account to a basic `recipient` and outputs
+    /// it as hex string.
+    Tx {
+        /// The multisig account.
+        multisig: Address,
+
+        recipient: Address,
+
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        /// The absolute block height from which the transaction is valid. All signers must sign
+        /// the same transaction, so a relative height can't be used.
+        #[structopt(short, long)]
+        validity_start_height: u32,
+
+        #[structopt(short, long, default_value = "devalbatross")]
+        network: NetworkId,
+    },
+
+    /// Creates a commitment for a new signing round.
+    Commit {},
+
+    /// Creates a partial signature for a transaction from a multisig account.
+    Sign {
+        /// The multisig account.
+        multisig: Address,
+
+        /// Our wallet. It must be unlocked.
+        wallet: Address,
+
+        /// The transaction as hex string, e.g. created with `multisig tx`.
+        raw_tx: String,
+
+        /// Our random secret from `commit`.
+        random_secret: String,
+
+        /// The public keys of all signers in this round.
+        #[structopt(short, long = "signer")]
+        signers: Vec<PublicKey>,
+
+        /// The commitments of all signers in this round.
+        #[structopt(short, long = "commitment")]
+        commitments: Vec<String>,
+    },
+
+    /// Combines the partial signatures of all signers and outputs the signed transaction.
+    Combine {
+        /// The multisig account.
+        multisig: Address,
+
+        /// The transaction as hex string.
+        raw_tx: String,
+
+        /// The public keys of all signers in this round.
+        #[structopt(short, long = "signer")]
+        signers: Vec<PublicKey>,
+
+        /// The commitments of all signers in this round.
+        #[structopt(short, long = "commitment")]
+        commitments: Vec<String>,
+
+        /// The partial signatures of all signers in this round.
+        #[structopt(short, long = "partial-signature")]
+        partial_signatures: Vec<String>,
+
+        /// Send the signed transaction instead of only showing it.
+        #[structopt(long)]
+        send: bool,
+    },
 }
 
 #[derive(Debug, StructOpt)]
@@ -75,6 +271,53 @@ enum AccountCommand {
 
         key_data: String,
     },
+    /// Imports the accounts of a BIP39 or legacy mnemonic, e.g. from the web wallet.
+    ImportMnemonic {
+        #[structopt(short = "P", long)]
+        password: Option<String>,
+
+        /// Stop deriving accounts after this many consecutive accounts without history.
+        #[structopt(long)]
+        gap_limit: Option<u32>,
+
+        /// The password protecting the seed of a BIP39 mnemonic.
+        #[structopt(long)]
+        bip39_password: Option<String>,
+
+        /// The words of the mnemonic.
+        words: Vec<String>,
+    },
+    /// Exports accounts into a password encrypted backup file.
+    Export {
+        /// Passphrase of the accounts that aren't unlocked.
+        #[structopt(short = "P", long)]
+        password: Option<String>,
+
+        /// Password to encrypt the backup with.
+        #[structopt(short = "B", long)]
+        backup_password: String,
+
+        /// The backup file to write.
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        /// Accounts to export. If none are given, all accounts are exported.
+        addresses: Vec<Address>,
+    },
+    /// Imports the accounts of a backup file.
+    ImportBackup {
+        /// Passphrase to lock the imported accounts with.
+        #[structopt(short = "P", long)]
+        password: Option<String>,
+
+        /// Password the backup is encrypted with.
+        #[structopt(short = "B", long)]
+        backup_password: String,
+
+        /// The backup file to read.
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
     Lock {
         address: Address,
     },
@@ -87,6 +330,10 @@ enum AccountCommand {
     /// Queries the account state (e.g. account balance for basic accounts).
     Get {
         address: Address,
+
+        /// Query the state after this block instead. Requires the node to run in archive mode.
+        #[structopt(long)]
+        block: Option<u32>,
     },
 }
 
@@ -194,6 +441,226 @@ enum TransactionCommand {
         #[structopt(long = "dry")]
         dry: bool,
     },
+
+    /// Locks funds from the wallet `wallet` in a new HTLC contract.
+    Htlc {
+        /// The funds will be sent from this wallet.
+        wallet: Address,
+
+        /// This address can resolve the contract after the timeout.
+        htlc_sender: Address,
+
+        /// This address can redeem the funds by presenting the pre-image.
+        htlc_recipient: Address,
+
+        /// The hash root of the contract, as hex string.
+        hash_root: AnyHash,
+
+        /// The block height after which the HTLC sender can resolve the contract.
+        timeout: u64,
+
+        /// The amount of NIM to lock in the contract.
+        value: Coin,
+
+        /// The number of times the pre-image was hashed to get the hash root.
+        #[structopt(long, default_value = "1")]
+        hash_count: u8,
+
+        /// The hash algorithm used to compute the hash root (`blake2b` or `sha256`).
+        #[structopt(long, default_value = "blake2b", parse(try_from_str = parse_hash_algorithm))]
+        hash_algorithm: HashAlgorithm,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long, default_value)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Redeems funds from a HTLC contract by presenting the pre-image. The wallet `wallet` must be
+    /// the HTLC recipient.
+    RedeemHtlc {
+        /// The HTLC recipient's wallet.
+        wallet: Address,
+
+        /// The address of the HTLC contract.
+        contract_address: Address,
+
+        /// The funds will be sent to this address.
+        recipient: Address,
+
+        /// The pre-image of the hash root, as hex string.
+        pre_image: AnyHash,
+
+        /// The hash root of the contract, as hex string.
+        hash_root: AnyHash,
+
+        /// The amount of NIM to redeem.
+        value: Coin,
+
+        /// The number of times the pre-image was hashed to get the hash root.
+        #[structopt(long, default_value = "1")]
+        hash_count: u8,
+
+        /// The hash algorithm used to compute the hash root (`blake2b` or `sha256`).
+        #[structopt(long, default_value = "blake2b", parse(try_from_str = parse_hash_algorithm))]
+        hash_algorithm: HashAlgorithm,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long, default_value)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Resolves a HTLC contract after its timeout. The wallet `wallet` must be the HTLC sender.
+    ResolveHtlc {
+        /// The HTLC sender's wallet.
+        wallet: Address,
+
+        /// The address of the HTLC contract.
+        contract_address: Address,
+
+        /// The funds will be sent to this address.
+        recipient: Address,
+
+        /// The amount of NIM to withdraw.
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long, default_value)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Signs a transaction that resolves a HTLC contract before its timeout and outputs the
+    /// signature as hex string. The HTLC sender and HTLC recipient must sign the same transaction,
+    /// so use an absolute validity start height.
+    SignEarlyHtlc {
+        /// The wallet of either the HTLC sender or the HTLC recipient.
+        wallet: Address,
+
+        /// The address of the HTLC contract.
+        contract_address: Address,
+
+        /// The funds will be sent to this address.
+        recipient: Address,
+
+        /// The amount of NIM to withdraw.
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long)]
+        validity_start_height: ValidityStartHeight,
+    },
+
+    /// Resolves a HTLC contract before its timeout using the signatures of both the HTLC sender and
+    /// the HTLC recipient (see `sign-early-htlc`).
+    EarlyResolveHtlc {
+        /// The address of the HTLC contract.
+        contract_address: Address,
+
+        /// The funds will be sent to this address.
+        recipient: Address,
+
+        /// The signature of the HTLC sender, as hex string.
+        htlc_sender_signature: String,
+
+        /// The signature of the HTLC recipient, as hex string.
+        htlc_recipient_signature: String,
+
+        /// The amount of NIM to withdraw.
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Locks funds from the wallet `wallet` in a new vesting contract. The funds are released in
+    /// `num_steps` equal parts, every `time_step` starting at `start_time + time_step`.
+    Vesting {
+        /// The funds will be sent from this wallet.
+        wallet: Address,
+
+        /// The owner of the funds in the contract.
+        owner: Address,
+
+        start_time: u64,
+
+        time_step: u64,
+
+        num_steps: u32,
+
+        /// The amount of NIM to lock in the contract.
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long, default_value)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Withdraws released funds from a vesting contract. The wallet `wallet` must be the owner.
+    RedeemVesting {
+        /// The owner's wallet.
+        wallet: Address,
+
+        /// The address of the vesting contract.
+        contract_address: Address,
+
+        /// The funds will be sent to this address.
+        recipient: Address,
+
+        /// The amount of NIM to withdraw.
+        value: Coin,
+
+        #[structopt(short, long, default_value = "0")]
+        fee: Coin,
+
+        #[structopt(short, long, default_value)]
+        validity_start_height: ValidityStartHeight,
+
+        /// Don't actually send the transaction, but output the transaction as hex string.
+        #[structopt(long = "dry")]
+        dry: bool,
+    },
+
+    /// Shows the status of a transaction sent over RPC. Expired transactions are resubmitted if
+    /// their wallet is unlocked.
+    Status { hash: Blake2bHash },
+
+    /// Lists the transactions sent over RPC that haven't been included in a block yet.
+    Pending {
+        /// Only list the transactions sent from this address.
+        sender: Option<Address>,
+    },
 }
 
 impl Command {
@@ -227,11 +694,60 @@ impl Command {
                 println!("{:#?}", block)
             }
 
-            Command::Stakes {} => {
-                let stakes = client.blockchain.list_stakes().await?;
+            Command::Stakes { block } => {
+                let stakes = client.blockchain.list_stakes(block).await?;
                 println!("{:#?}", stakes);
             }
 
+            Command::History {
+                address,
+                direction,
+                start_after,
+                min_block,
+                max_block,
+                min_timestamp,
+                max_timestamp,
+                max,
+                count,
+            } => {
+                let (start_after_hash, start_after_block_number) = match start_after {
+                    Some(BlockNumberOrHash::Hash(hash)) => (Some(hash), None),
+                    Some(BlockNumberOrHash::Number(number)) => (None, Some(number)),
+                    None => (None, None),
+                };
+                let filter = HistoryFilter {
+                    direction: direction.unwrap_or_default(),
+                    start_after_hash,
+                    start_after_block_number,
+                    min_block_number: min_block,
+                    max_block_number: max_block,
+                    min_timestamp,
+                    max_timestamp,
+                };
+
+                if count {
+                    let count = client
+                        .blockchain
+                        .count_transactions_by_address(address, Some(filter))
+                        .await?;
+                    println!("{}", count);
+                } else {
+                    let transactions = client
+                        .blockchain
+                        .get_transactions_by_address(address.clone(), Some(filter.clone()), max)
+                        .await?;
+                    println!("{:#?}", transactions);
+
+                    if filter.direction != TransactionDirection::Outgoing {
+                        let inherents = client
+                            .blockchain
+                            .get_inherents_by_address(address, Some(filter), max)
+                            .await?;
+                        println!("{:#?}", inherents);
+                    }
+                }
+            }
+
             Command::Follow { block: show_block } => {
                 let mut stream = client.blockchain.head_subscribe().await?;
 
@@ -245,6 +761,20 @@ impl Command {
                 }
             }
 
+            Command::StakingEvents {
+                validator_ids,
+                staker_addresses,
+            } => {
+                let mut stream = client
+                    .blockchain
+                    .subscribe_for_staking_events(validator_ids, staker_addresses)
+                    .await?;
+
+                while let Some(event) = stream.next().await {
+                    println!("{:#?}", event);
+                }
+            }
+
             Command::Account(command) => {
                 match command {
                     AccountCommand::List { short } => {
@@ -254,7 +784,7 @@ impl Command {
                                 println!("{}", address.to_user_friendly_address());
                             } else {
                                 let account =
-                                    client.blockchain.get_account(address.clone()).await?;
+                                    client.blockchain.get_account(address.clone(), None).await?;
                                 println!("{}: {:#?}", address.to_user_friendly_address(), account);
                             }
                         }
@@ -270,6 +800,55 @@ impl Command {
                         println!("{}", address);
                     }
 
+                    AccountCommand::ImportMnemonic {
+                        password,
+                        gap_limit,
+                        bip39_password,
+                        words,
+                    } => {
+                        let addresses = client
+                            .wallet
+                            .import_mnemonic(words.join(" "), password, gap_limit, bip39_password)
+                            .await?;
+                        for address in &addresses {
+                            println!("{}", address.to_user_friendly_address());
+                        }
+                    }
+
+                    AccountCommand::Export {
+                        password,
+                        backup_password,
+                        file,
+                        addresses,
+                    } => {
+                        let addresses = if addresses.is_empty() {
+                            None
+                        } else {
+                            Some(addresses)
+                        };
+                        let backup = client
+                            .wallet
+                            .export_accounts(addresses, password, backup_password)
+                            .await?;
+                        fs::write(&file, hex::decode(&backup)?)?;
+                        println!("Wrote backup to {}", file.display());
+                    }
+
+                    AccountCommand::ImportBackup {
+                        password,
+                        backup_password,
+                        file,
+                    } => {
+                        let backup = hex::encode(fs::read(&file)?);
+                        let addresses = client
+                            .wallet
+                            .import_accounts(backup, backup_password, password)
+                            .await?;
+                        for address in &addresses {
+                            println!("{}", address.to_user_friendly_address());
+                        }
+                    }
+
                     AccountCommand::Lock { address } => {
                         client.wallet.lock_account(address).await?;
                     }
@@ -284,8 +863,8 @@ impl Command {
                             .await?;
                     }
 
-                    AccountCommand::Get { address } => {
-                        let account = client.blockchain.get_account(address).await?;
+                    AccountCommand::Get { address, block } => {
+                        let account = client.blockchain.get_account(address, block).await?;
                         println!("{:#?}", account);
                     }
                 }
@@ -468,6 +1047,435 @@ impl Command {
                         println!("{}", txid);
                     }
                 }
+
+                TransactionCommand::Htlc {
+                    wallet,
+                    htlc_sender,
+                    htlc_recipient,
+                    hash_root,
+                    hash_count,
+                    hash_algorithm,
+                    timeout,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_new_htlc_transaction(
+                                wallet,
+                                htlc_sender,
+                                htlc_recipient,
+                                hash_root,
+                                hash_count,
+                                hash_algorithm,
+                                timeout,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_new_htlc_transaction(
+                                wallet,
+                                htlc_sender,
+                                htlc_recipient,
+                                hash_root,
+                                hash_count,
+                                hash_algorithm,
+                                timeout,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::RedeemHtlc {
+                    wallet,
+                    contract_address,
+                    recipient,
+                    pre_image,
+                    hash_root,
+                    hash_count,
+                    hash_algorithm,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_redeem_regular_htlc_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                pre_image,
+                                hash_root,
+                                hash_count,
+                                hash_algorithm,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_redeem_regular_htlc_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                pre_image,
+                                hash_root,
+                                hash_count,
+                                hash_algorithm,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::ResolveHtlc {
+                    wallet,
+                    contract_address,
+                    recipient,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_redeem_timeout_htlc_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_redeem_timeout_htlc_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::SignEarlyHtlc {
+                    wallet,
+                    contract_address,
+                    recipient,
+                    value,
+                    fee,
+                    validity_start_height,
+                } => {
+                    let signature = client
+                        .consensus
+                        .sign_redeem_early_htlc_transaction(
+                            wallet,
+                            contract_address,
+                            recipient,
+                            value,
+                            fee,
+                            validity_start_height,
+                        )
+                        .await?;
+                    println!("{}", signature);
+                }
+
+                TransactionCommand::EarlyResolveHtlc {
+                    contract_address,
+                    recipient,
+                    htlc_sender_signature,
+                    htlc_recipient_signature,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_redeem_early_htlc_transaction(
+                                contract_address,
+                                recipient,
+                                htlc_sender_signature,
+                                htlc_recipient_signature,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_redeem_early_htlc_transaction(
+                                contract_address,
+                                recipient,
+                                htlc_sender_signature,
+                                htlc_recipient_signature,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::Vesting {
+                    wallet,
+                    owner,
+                    start_time,
+                    time_step,
+                    num_steps,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_new_vesting_transaction(
+                                wallet,
+                                owner,
+                                start_time,
+                                time_step,
+                                num_steps,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_new_vesting_transaction(
+                                wallet,
+                                owner,
+                                start_time,
+                                time_step,
+                                num_steps,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::RedeemVesting {
+                    wallet,
+                    contract_address,
+                    recipient,
+                    value,
+                    fee,
+                    validity_start_height,
+                    dry,
+                } => {
+                    if dry {
+                        let tx = client
+                            .consensus
+                            .create_redeem_vesting_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", tx);
+                    } else {
+                        let txid = client
+                            .consensus
+                            .send_redeem_vesting_transaction(
+                                wallet,
+                                contract_address,
+                                recipient,
+                                value,
+                                fee,
+                                validity_start_height,
+                            )
+                            .await?;
+                        println!("{}", txid);
+                    }
+                }
+
+                TransactionCommand::Status { hash } => {
+                    let status = client.consensus.get_transaction_status(hash).await?;
+                    println!("{:#?}", status);
+                }
+
+                TransactionCommand::Pending { sender } => {
+                    for hash in client.consensus.list_pending_transactions(sender).await? {
+                        println!("{}", hash);
+                    }
+                }
+            },
+
+            Command::Multisig(command) => match command {
+                MultisigCommand::New {
+                    min_signatures,
+                    public_keys,
+                } => {
+                    let account = client
+                        .wallet
+                        .create_multisig_account(min_signatures, public_keys)
+                        .await?;
+                    println!("{:#?}", account);
+                }
+
+                MultisigCommand::List {} => {
+                    for address in client.wallet.list_multisig_accounts().await? {
+                        println!("{}", address.to_user_friendly_address());
+                    }
+                }
+
+                MultisigCommand::Get { address } => {
+                    let account = client.wallet.get_multisig_account(address).await?;
+                    println!("{:#?}", account);
+                }
+
+                MultisigCommand::Tx {
+                    multisig,
+                    recipient,
+                    value,
+                    fee,
+                    validity_start_height,
+                    network,
+                } => {
+                    let transaction = Transaction::new_basic(
+                        multisig,
+                        recipient,
+                        value,
+                        fee,
+                        validity_start_height,
+                        network,
+                    );
+                    println!("{}", hex::encode(transaction.serialize_to_vec()));
+                }
+
+                MultisigCommand::Commit {} => {
+                    let commitment = client.wallet.create_multisig_commitment().await?;
+                    println!("{:#?}", commitment);
+                }
+
+                MultisigCommand::Sign {
+                    multisig,
+                    wallet,
+                    raw_tx,
+                    random_secret,
+                    signers,
+                    commitments,
+                } => {
+                    let partial_signature = client
+                        .wallet
+                        .partially_sign_multisig_transaction(
+                            multisig,
+                            wallet,
+                            raw_tx,
+                            signers,
+                            commitments,
+                            random_secret,
+                        )
+                        .await?;
+                    println!("{}", partial_signature);
+                }
+
+                MultisigCommand::Combine {
+                    multisig,
+                    raw_tx,
+                    signers,
+                    commitments,
+                    partial_signatures,
+                    send,
+                } => {
+                    let proof = client
+                        .wallet
+                        .combine_multisig_signatures(
+                            multisig,
+                            signers,
+                            commitments,
+                            partial_signatures,
+                        )
+                        .await?;
+
+                    let mut transaction = Transaction::deserialize_from_vec(&hex::decode(raw_tx)?)?;
+                    transaction.proof = hex::decode(proof)?;
+                    let raw_tx = hex::encode(transaction.serialize_to_vec());
+
+                    if send {
+                        let txid = client.consensus.send_raw_transaction(raw_tx).await?;
+                        println!("{}", txid);
+                    } else {
+                        println!("{}", raw_tx);
+                    }
+                }
+            },
+
+            Command::Mempool(command) => match command {
+                MempoolCommand::Info {} => {
+                    let info = client.mempool.mempool().await?;
+                    println!("{:#?}", info);
+                }
+
+                MempoolCommand::Content {
+                    include_transactions,
+                } => {
+                    let content = client.mempool.mempool_content(include_transactions).await?;
+                    println!("{:#?}", content);
+                }
+
+                MempoolCommand::Tx { hash } => {
+                    let transaction = client.mempool.get_mempool_transaction(hash).await?;
+                    println!("{:#?}", transaction);
+                }
+
+                MempoolCommand::Fees { num_blocks } => {
+                    let estimate = client.mempool.estimate_fees(num_blocks).await?;
+                    println!("{:#?}", estimate);
+                }
+
+                MempoolCommand::Follow { addresses } => {
+                    let mut stream = client
+                        .mempool
+                        .subscribe_for_mempool_transactions(addresses)
+                        .await?;
+
+                    while let Some(event) = stream.next().await {
+                        println!("{:#?}", event);
+                    }
+                }
             },
         }
 
@@ -475,6 +1483,14 @@ impl Command {
     }
 }
 
+fn parse_hash_algorithm(s: &str) -> Result<HashAlgorithm, Error> {
+    match s.to_lowercase().as_str() {
+        "blake2b" => Ok(HashAlgorithm::Blake2b),
+        "sha256" => Ok(HashAlgorithm::Sha256),
+        _ => bail!("Unknown hash algorithm: {}", s),
+    }
+}
+
 async fn run_app(opt: Opt) -> Result<(), Error> {
     let url = opt
         .url
@@ -4,8 +4,12 @@ use futures::stream::BoxStream;
 use nimiq_account::Account;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
+use nimiq_primitives::account::ValidatorId;
 
-use crate::types::{Block, Inherent, SlashedSlots, Slot, Stakes, Transaction};
+use crate::types::{
+    Block, HistoryFilter, Inherent, RawTransactionInfo, SlashedSlots, Slot, Stakes, StakingEvent,
+    Transaction, TransactionReceipt,
+};
 
 #[cfg_attr(
     feature = "proxy",
@@ -44,7 +48,10 @@ pub trait BlockchainInterface {
     // TODO: Previously called `slot_state`. Where is this used?
     async fn get_slashed_slots(&mut self) -> Result<SlashedSlots, Self::Error>;
 
-    async fn get_raw_transaction_info(&mut self, raw_tx: String) -> Result<(), Self::Error>;
+    async fn get_raw_transaction_info(
+        &mut self,
+        raw_tx: String,
+    ) -> Result<RawTransactionInfo, Self::Error>;
 
     async fn get_transaction_by_hash(
         &mut self,
@@ -61,24 +68,60 @@ pub trait BlockchainInterface {
         batch_number: u32,
     ) -> Result<Vec<Inherent>, Self::Error>;
 
-    async fn get_transaction_receipt(&mut self, hash: Blake2bHash) -> Result<(), Self::Error>;
+    async fn get_transaction_receipt(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<TransactionReceipt, Self::Error>;
 
+    /// Returns the hashes of the most recent transactions and inherents of an address that pass
+    /// the filter.
     async fn get_transaction_hashes_by_address(
         &mut self,
         address: Address,
+        filter: Option<HistoryFilter>,
         max: Option<u16>,
     ) -> Result<Vec<Blake2bHash>, Self::Error>;
 
+    /// Returns the transactions among the most recent `max` transactions and inherents of an
+    /// address that pass the filter. The inherents are returned by `get_inherents_by_address`.
     async fn get_transactions_by_address(
         &mut self,
         address: Address,
+        filter: Option<HistoryFilter>,
         max: Option<u16>,
     ) -> Result<Vec<Transaction>, Self::Error>;
 
-    async fn list_stakes(&mut self) -> Result<Stakes, Self::Error>;
+    /// Returns the inherents targeting an address among its most recent `max` incoming
+    /// transactions and inherents that pass the filter.
+    async fn get_inherents_by_address(
+        &mut self,
+        address: Address,
+        filter: Option<HistoryFilter>,
+        max: Option<u16>,
+    ) -> Result<Vec<Inherent>, Self::Error>;
+
+    /// Returns the number of transactions and inherents of an address that pass the filter.
+    async fn count_transactions_by_address(
+        &mut self,
+        address: Address,
+        filter: Option<HistoryFilter>,
+    ) -> Result<usize, Self::Error>;
+
+    async fn list_stakes(&mut self, block_number: Option<u32>) -> Result<Stakes, Self::Error>;
 
     #[stream]
     async fn head_subscribe(&mut self) -> Result<BoxStream<'static, Blake2bHash>, Self::Error>;
 
-    async fn get_account(&mut self, address: Address) -> Result<Account, Self::Error>;
+    #[stream]
+    async fn subscribe_for_staking_events(
+        &mut self,
+        validator_ids: Vec<ValidatorId>,
+        staker_addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, StakingEvent>, Self::Error>;
+
+    async fn get_account(
+        &mut self,
+        address: Address,
+        block_number: Option<u32>,
+    ) -> Result<Account, Self::Error>;
 }
@@ -4,8 +4,9 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::account::ValidatorId;
 use nimiq_primitives::coin::Coin;
+use nimiq_transaction::account::htlc_contract::{AnyHash, HashAlgorithm};
 
-use crate::types::ValidityStartHeight;
+use crate::types::{TransactionStatus, ValidityStartHeight};
 
 #[cfg_attr(
     feature = "proxy",
@@ -19,6 +20,19 @@ pub trait ConsensusInterface {
 
     async fn send_raw_transaction(&mut self, raw_tx: String) -> Result<Blake2bHash, Self::Error>;
 
+    /// Returns the status of a transaction sent over RPC since the node was started.
+    async fn get_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<TransactionStatus, Self::Error>;
+
+    /// Returns the hashes of the transactions sent over RPC that haven't been included in a block
+    /// yet, optionally only those sent from `sender`.
+    async fn list_pending_transactions(
+        &mut self,
+        sender: Option<Address>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
     async fn create_basic_transaction(
         &mut self,
         wallet: Address,
@@ -246,4 +260,159 @@ pub trait ConsensusInterface {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Self::Error>;
+
+    async fn create_new_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        htlc_sender: Address,
+        htlc_recipient: Address,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        timeout: u64,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_new_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        htlc_sender: Address,
+        htlc_recipient: Address,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        timeout: u64,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    async fn create_redeem_regular_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        pre_image: AnyHash,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_redeem_regular_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        pre_image: AnyHash,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    async fn create_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    /// Signs a transaction that resolves a HTLC before its timeout. Both the HTLC sender and the
+    /// HTLC recipient have to sign the same transaction, so an absolute validity start height
+    /// should be used. Returns the signature proof as hex string.
+    async fn sign_redeem_early_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn create_redeem_early_htlc_transaction(
+        &mut self,
+        contract_address: Address,
+        recipient: Address,
+        htlc_sender_signature: String,
+        htlc_recipient_signature: String,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_redeem_early_htlc_transaction(
+        &mut self,
+        contract_address: Address,
+        recipient: Address,
+        htlc_sender_signature: String,
+        htlc_recipient_signature: String,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    async fn create_new_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_new_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    async fn create_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Self::Error>;
+
+    async fn send_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Self::Error>;
 }
@@ -7,4 +7,7 @@ pub enum Error {
 
     #[error("Invalid block number or hash: {0}")]
     InvalidBlockNumberOrHash(String),
+
+    #[error("Invalid transaction direction: {0}")]
+    InvalidTransactionDirection(String),
 }
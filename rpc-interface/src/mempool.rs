@@ -1,6 +1,12 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+
+use crate::types::{
+    FeeEstimate, HashOrTx, MempoolInfo, MempoolTransaction, MempoolTransactionEvent,
+};
 
 #[cfg_attr(
     feature = "proxy",
@@ -10,12 +16,30 @@ use nimiq_hash::Blake2bHash;
 pub trait MempoolInterface {
     type Error;
 
-    async fn get_transaction(&mut self, txid: Blake2bHash) -> Result<Option<()>, Self::Error>;
+    async fn get_transaction(
+        &mut self,
+        txid: Blake2bHash,
+    ) -> Result<Option<MempoolTransaction>, Self::Error>;
+
+    async fn mempool_content(
+        &mut self,
+        include_transactions: bool,
+    ) -> Result<Vec<HashOrTx>, Self::Error>;
+
+    async fn mempool(&mut self) -> Result<MempoolInfo, Self::Error>;
 
-    async fn mempool_content(&mut self, include_transactions: bool)
-        -> Result<Vec<()>, Self::Error>;
+    /// Estimates the fees per byte needed to be included in a block soon, based on the mempool
+    /// and the last `num_blocks` micro blocks.
+    async fn estimate_fees(&mut self, num_blocks: Option<u32>) -> Result<FeeEstimate, Self::Error>;
 
-    async fn mempool(&mut self) -> Result<(), Self::Error>;
+    async fn get_mempool_transaction(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<MempoolTransaction, Self::Error>;
 
-    async fn get_mempool_transaction(&mut self) -> Result<(), Self::Error>;
+    #[stream]
+    async fn subscribe_for_mempool_transactions(
+        &mut self,
+        addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, MempoolTransactionEvent>, Self::Error>;
 }
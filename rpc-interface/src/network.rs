@@ -17,4 +17,12 @@ pub trait NetworkInterface {
     async fn get_peer_list(&mut self) -> Result<Vec<Peer>, Self::Error>;
 
     async fn get_peer_state(&mut self, peer_id: String) -> Result<Peer, Self::Error>;
+
+    async fn dial_address(&mut self, address: String) -> Result<(), Self::Error>;
+
+    async fn disconnect_peer(&mut self, peer_id: String) -> Result<(), Self::Error>;
+
+    async fn ban_peer(&mut self, peer_id: String) -> Result<(), Self::Error>;
+
+    async fn unban_peer(&mut self, peer_id: String) -> Result<(), Self::Error>;
 }
@@ -9,16 +9,25 @@ use std::{
 use serde::{Deserialize, Serialize};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 
+use beserial::Serialize as BeSerialize;
 use nimiq_block::{TendermintProof, ViewChangeProof};
 use nimiq_blockchain::{AbstractBlockchain, Blockchain};
+pub use nimiq_blockchain::{StakingEvent, StakingEventKind};
 use nimiq_bls::{CompressedPublicKey, CompressedSignature};
 use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_keys::Address;
+use nimiq_keys::{Address, PublicKey};
 use nimiq_primitives::policy;
 use nimiq_primitives::slots::Validators;
 use nimiq_primitives::{account::AccountType, coin::Coin};
-use nimiq_transaction::account::htlc_contract::AnyHash;
+use nimiq_transaction::account::htlc_contract::{
+    AnyHash, CreationTransactionData as HtlcCreationTransactionData,
+};
+use nimiq_transaction::account::staking_contract::{
+    IncomingStakingTransactionData, SelfStakingTransactionData,
+};
+use nimiq_transaction::account::vesting_contract::CreationTransactionData as VestingCreationTransactionData;
+use nimiq_transaction::TransactionFlags;
 use nimiq_vrf::VrfSeed;
 
 use crate::error::Error;
@@ -315,6 +324,292 @@ impl Transaction {
     }
 }
 
+/// A transaction that is currently pending in the mempool, i.e. not yet included in a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransaction {
+    pub hash: Blake2bHash,
+
+    pub from: Address,
+
+    #[serde(with = "crate::serde_helpers::account_type")]
+    pub from_type: AccountType,
+
+    pub to: Address,
+
+    #[serde(with = "crate::serde_helpers::account_type")]
+    pub to_type: AccountType,
+
+    pub value: Coin,
+
+    pub fee: Coin,
+
+    pub fee_per_byte: f64,
+
+    pub size: usize,
+
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub data: Vec<u8>,
+
+    pub flags: u8,
+
+    pub validity_start_height: u32,
+
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub proof: Vec<u8>,
+}
+
+impl MempoolTransaction {
+    pub fn from_transaction(transaction: &nimiq_transaction::Transaction) -> Self {
+        MempoolTransaction {
+            hash: transaction.hash(),
+            from: transaction.sender.clone(),
+            from_type: transaction.sender_type,
+            to: transaction.recipient.clone(),
+            to_type: transaction.recipient_type,
+            value: transaction.value,
+            fee: transaction.fee,
+            fee_per_byte: transaction.fee_per_byte(),
+            size: BeSerialize::serialized_size(transaction),
+            data: transaction.data.clone(),
+            flags: transaction.flags.bits() as u8,
+            validity_start_height: transaction.validity_start_height,
+            proof: transaction.proof.clone(),
+        }
+    }
+}
+
+/// The data field of a transaction, decoded according to the recipient's account type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type", content = "data")]
+pub enum TransactionData {
+    /// Data of an incoming transaction to the staking contract (e.g. stake, create validator).
+    Staking(IncomingStakingTransactionData),
+
+    /// Data of a staking contract self transaction (i.e. retire, reactivate or rededicate stake).
+    SelfStaking(SelfStakingTransactionData),
+
+    /// Data of a transaction creating a HTLC.
+    HtlcCreation(HtlcCreationTransactionData),
+
+    /// Data of a transaction creating a vesting contract.
+    VestingCreation(VestingCreationTransactionData),
+}
+
+impl TransactionData {
+    /// Decodes the data field of the transaction. Returns `None` if the recipient doesn't interpret the data
+    /// field or the data is malformed.
+    pub fn from_transaction(transaction: &nimiq_transaction::Transaction) -> Option<Self> {
+        let is_contract_creation = transaction
+            .flags
+            .contains(TransactionFlags::CONTRACT_CREATION);
+
+        match transaction.recipient_type {
+            AccountType::Staking if transaction.sender == transaction.recipient => {
+                SelfStakingTransactionData::parse(transaction)
+                    .ok()
+                    .map(TransactionData::SelfStaking)
+            }
+            AccountType::Staking => IncomingStakingTransactionData::parse(transaction)
+                .ok()
+                .map(TransactionData::Staking),
+            AccountType::HTLC if is_contract_creation => {
+                HtlcCreationTransactionData::parse(transaction)
+                    .ok()
+                    .map(TransactionData::HtlcCreation)
+            }
+            AccountType::Vesting if is_contract_creation => {
+                VestingCreationTransactionData::parse(transaction)
+                    .ok()
+                    .map(TransactionData::VestingCreation)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Information about a serialized transaction that was passed to the node, but not necessarily sent to the
+/// network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTransactionInfo {
+    #[serde(flatten)]
+    pub transaction: MempoolTransaction,
+
+    /// The decoded data field, if the recipient interprets it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded_data: Option<TransactionData>,
+
+    /// Whether the transaction passed the static (intrinsic) verification.
+    pub valid: bool,
+
+    /// The reason why the static verification failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Whether the transaction is currently pending in the mempool.
+    pub in_mempool: bool,
+
+    /// The block the transaction was included in, if it is already known to the blockchain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceipt {
+    pub transaction_hash: Blake2bHash,
+
+    pub block_hash: Blake2bHash,
+
+    pub block_number: u32,
+
+    pub timestamp: u64,
+
+    pub confirmations: u32,
+
+    /// Whether the transaction is an inherent (e.g. a slash) rather than a regular transaction.
+    pub is_inherent: bool,
+
+    /// The result of executing the transaction against the accounts. Blocks only contain transactions that
+    /// were applied successfully, so this is always `true` for transactions found in the history.
+    pub execution_result: bool,
+}
+
+/// Either only the hash of a transaction or the full transaction, depending on what the caller requested.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HashOrTx {
+    Hash(Blake2bHash),
+    Tx(MempoolTransaction),
+}
+
+impl HashOrTx {
+    pub fn from_transaction(
+        transaction: &nimiq_transaction::Transaction,
+        include_transaction: bool,
+    ) -> Self {
+        if include_transaction {
+            HashOrTx::Tx(MempoolTransaction::from_transaction(transaction))
+        } else {
+            HashOrTx::Hash(transaction.hash())
+        }
+    }
+}
+
+/// An event concerning a transaction in the mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum MempoolTransactionEvent {
+    /// The transaction was added to the mempool (or was already pending when subscribing).
+    Added { transaction: MempoolTransaction },
+
+    /// The transaction was removed from the mempool without being included in a block, e.g. because it
+    /// expired, became invalid or was evicted.
+    Removed { transaction: MempoolTransaction },
+
+    /// The transaction was included in a block.
+    Included { transaction: MempoolTransaction },
+}
+
+impl MempoolTransactionEvent {
+    pub fn transaction(&self) -> &MempoolTransaction {
+        match self {
+            MempoolTransactionEvent::Added { transaction }
+            | MempoolTransactionEvent::Removed { transaction }
+            | MempoolTransactionEvent::Included { transaction } => transaction,
+        }
+    }
+}
+
+/// A histogram bucket of the mempool. Counts all transactions with a fee per byte greater or equal to
+/// `fee_per_byte` and lower than the `fee_per_byte` of the next higher bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolBucket {
+    pub fee_per_byte: u64,
+
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolInfo {
+    /// Total number of transactions in the mempool.
+    pub total: usize,
+
+    /// Number of transactions per fee per byte bucket, sorted descending by fee per byte. Empty buckets are
+    /// omitted.
+    pub buckets: Vec<MempoolBucket>,
+}
+
+impl MempoolInfo {
+    /// The lower bounds (in Luna per byte) of the fee per byte buckets, sorted descending.
+    pub const BUCKETS: [u64; 14] = [
+        10000, 5000, 2000, 1000, 500, 200, 100, 50, 20, 10, 5, 2, 1, 0,
+    ];
+
+    pub fn from_transactions<'a, I>(transactions: I) -> Self
+    where
+        I: IntoIterator<Item = &'a nimiq_transaction::Transaction>,
+    {
+        let mut total = 0;
+        let mut counts = [0usize; Self::BUCKETS.len()];
+
+        for transaction in transactions {
+            let fee_per_byte = transaction.fee_per_byte();
+
+            // The last bucket has a lower bound of 0, so we always find one.
+            if let Some(i) = Self::BUCKETS
+                .iter()
+                .position(|bucket| fee_per_byte >= *bucket as f64)
+            {
+                counts[i] += 1;
+            }
+
+            total += 1;
+        }
+
+        let buckets = Self::BUCKETS
+            .iter()
+            .zip(counts.iter())
+            .filter(|(_, count)| **count > 0)
+            .map(|(fee_per_byte, count)| MempoolBucket {
+                fee_per_byte: *fee_per_byte,
+                count: *count,
+            })
+            .collect();
+
+        MempoolInfo { total, buckets }
+    }
+}
+
+/// The status of a transaction submitted over RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum TransactionStatus {
+    /// The transaction hasn't been included in a finalized block yet.
+    #[serde(rename_all = "camelCase")]
+    Pending { validity_start_height: u32 },
+    /// The transaction was included in a block that is finalized.
+    #[serde(rename_all = "camelCase")]
+    Confirmed { block_number: u32 },
+    /// The transaction expired and was resubmitted as the transaction with the given hash.
+    Resubmitted { hash: Blake2bHash },
+    /// The transaction expired and couldn't be resubmitted.
+    Expired,
+}
+
+/// Suggested fees per byte, in Luna, to be included in a block within 1 (fast), 3 (normal) or 10
+/// (slow) blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    pub fast: f64,
+    pub normal: f64,
+    pub slow: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Inherent {
@@ -680,7 +975,111 @@ impl FromStr for ValidityStartHeight {
     }
 }
 
+/// Selects the transactions of an address by how they affect it. Inherents are incoming to their
+/// target.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionDirection {
+    Any,
+    Incoming,
+    Outgoing,
+}
+
+impl Default for TransactionDirection {
+    fn default() -> Self {
+        TransactionDirection::Any
+    }
+}
+
+impl FromStr for TransactionDirection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "any" => Ok(TransactionDirection::Any),
+            "incoming" => Ok(TransactionDirection::Incoming),
+            "outgoing" => Ok(TransactionDirection::Outgoing),
+            _ => Err(Error::InvalidTransactionDirection(s.to_owned())),
+        }
+    }
+}
+
+impl From<TransactionDirection> for nimiq_blockchain::TransactionDirection {
+    fn from(direction: TransactionDirection) -> Self {
+        match direction {
+            TransactionDirection::Any => nimiq_blockchain::TransactionDirection::Any,
+            TransactionDirection::Incoming => nimiq_blockchain::TransactionDirection::Incoming,
+            TransactionDirection::Outgoing => nimiq_blockchain::TransactionDirection::Outgoing,
+        }
+    }
+}
+
+/// Selects the transactions of an address that are returned by a query. All fields are optional
+/// and the bounds of the ranges are inclusive.
+///
+/// To page through the history of an address, pass the hash of the last returned transaction as
+/// `startAfterHash`. If both `startAfterHash` and `startAfterBlockNumber` are given, the hash is
+/// used.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryFilter {
+    pub direction: TransactionDirection,
+    pub start_after_hash: Option<Blake2bHash>,
+    pub start_after_block_number: Option<u32>,
+    pub min_block_number: Option<u32>,
+    pub max_block_number: Option<u32>,
+    pub min_timestamp: Option<u64>,
+    pub max_timestamp: Option<u64>,
+}
+
+impl From<HistoryFilter> for nimiq_blockchain::HistoryFilter {
+    fn from(filter: HistoryFilter) -> Self {
+        let start_after = match (filter.start_after_hash, filter.start_after_block_number) {
+            (Some(hash), _) => Some(nimiq_blockchain::HistoryPosition::Hash(hash)),
+            (None, Some(block_number)) => {
+                Some(nimiq_blockchain::HistoryPosition::BlockNumber(block_number))
+            }
+            (None, None) => None,
+        };
+
+        nimiq_blockchain::HistoryFilter {
+            direction: filter.direction.into(),
+            start_after,
+            min_block_number: filter.min_block_number,
+            max_block_number: filter.max_block_number,
+            min_timestamp: filter.min_timestamp,
+            max_timestamp: filter.max_timestamp,
+        }
+    }
+}
+
+/// Whether we dialed a peer or the peer dialed us.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionDirection {
+    Inbound,
+    Outbound,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Peer {
-    // TODO
+    pub peer_id: String,
+
+    /// The remote address of our connection to the peer.
+    pub address: String,
+
+    pub direction: ConnectionDirection,
+
+    /// The addresses the peer announced in its peer contact.
+    pub addresses: Vec<String>,
+
+    /// The services the peer announced in its peer contact, as a bitmask.
+    pub services: Option<u32>,
+
+    /// The last measured round-trip time in milliseconds.
+    pub latency: Option<u64>,
+
+    /// The gossipsub topics the peer is subscribed to.
+    pub topics: Vec<String>,
 }
@@ -1,5 +1,12 @@
 use async_trait::async_trait;
 
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{account::ValidatorId, coin::Coin};
+
+use crate::types::{
+    BlockProductionStatistics, Slots, UpcomingSlot, ValidatorKeys, ValidatorStakingState,
+};
+
 #[cfg_attr(
     feature = "proxy",
     nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")
@@ -7,4 +14,23 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait ValidatorInterface {
     type Error;
+
+    async fn get_validator_id(&mut self) -> Result<Option<ValidatorId>, Self::Error>;
+
+    async fn get_public_keys(&mut self) -> Result<ValidatorKeys, Self::Error>;
+
+    async fn get_slots(&mut self) -> Result<Option<Slots>, Self::Error>;
+
+    async fn get_upcoming_slots(
+        &mut self,
+        max_views: Option<u32>,
+    ) -> Result<Vec<UpcomingSlot>, Self::Error>;
+
+    async fn get_staking_state(&mut self) -> Result<ValidatorStakingState, Self::Error>;
+
+    async fn get_block_production_statistics(
+        &mut self,
+    ) -> Result<BlockProductionStatistics, Self::Error>;
+
+    async fn unpark(&mut self, fee: Coin) -> Result<Blake2bHash, Self::Error>;
 }
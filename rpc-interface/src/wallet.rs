@@ -17,6 +17,23 @@ pub struct ReturnAccount {
     pub private_key: PrivateKey,
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnMultisigAccount {
+    pub address: Address,
+    pub min_signatures: u8,
+    pub public_keys: Vec<PublicKey>,
+}
+
+/// A commitment for one multisig signing round. The commitment is shared with the other signers,
+/// the random secret must be kept private and must only be used once.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnCommitment {
+    pub commitment: String,
+    pub random_secret: String,
+}
+
 #[cfg_attr(
     feature = "proxy",
     nimiq_jsonrpc_derive::proxy(name = "WalletProxy", rename_all = "camelCase")
@@ -31,8 +48,39 @@ pub trait WalletInterface {
         passphrase: Option<String>,
     ) -> Result<Address, Self::Error>;
 
+    /// Imports the accounts of a BIP39 or legacy mnemonic. Accounts of BIP39 mnemonics are
+    /// derived along `m/44'/242'/0'/<index>'` until `gap_limit` consecutive accounts have no
+    /// history. The seed of BIP39 mnemonics is protected by the optional `bip39_password`.
+    /// Returns the addresses of the imported accounts.
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        passphrase: Option<String>,
+        gap_limit: Option<u32>,
+        bip39_password: Option<String>,
+    ) -> Result<Vec<Address>, Self::Error>;
+
     async fn list_accounts(&mut self) -> Result<Vec<Address>, Self::Error>;
 
+    /// Exports the given accounts, or all accounts if none are given, into a backup encrypted
+    /// with `backup_password`. Accounts that aren't unlocked are unlocked with `passphrase`.
+    /// Returns the backup as hex string.
+    async fn export_accounts(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        backup_password: String,
+    ) -> Result<String, Self::Error>;
+
+    /// Imports the accounts of a backup created by `export_accounts`, after verifying its
+    /// integrity. The accounts are locked with `passphrase`.
+    async fn import_accounts(
+        &mut self,
+        backup: String,
+        backup_password: String,
+        passphrase: Option<String>,
+    ) -> Result<Vec<Address>, Self::Error>;
+
     async fn lock_account(&mut self, address: Address) -> Result<(), Self::Error>;
 
     async fn create_account(
@@ -62,4 +110,42 @@ pub trait WalletInterface {
         signature: Signature,
         is_hex: bool,
     ) -> Result<bool, Self::Error>;
+
+    async fn create_multisig_account(
+        &mut self,
+        min_signatures: u8,
+        public_keys: Vec<PublicKey>,
+    ) -> Result<ReturnMultisigAccount, Self::Error>;
+
+    async fn list_multisig_accounts(&mut self) -> Result<Vec<Address>, Self::Error>;
+
+    async fn get_multisig_account(
+        &mut self,
+        address: Address,
+    ) -> Result<ReturnMultisigAccount, Self::Error>;
+
+    async fn create_multisig_commitment(&mut self) -> Result<ReturnCommitment, Self::Error>;
+
+    /// Creates the partial signature of the unlocked `wallet` for the transaction `raw_tx` sent
+    /// from the multisig account. `signers` and `commitments` belong to all participants of this
+    /// signing round, `random_secret` belongs to our own commitment.
+    async fn partially_sign_multisig_transaction(
+        &mut self,
+        multisig: Address,
+        wallet: Address,
+        raw_tx: String,
+        signers: Vec<PublicKey>,
+        commitments: Vec<String>,
+        random_secret: String,
+    ) -> Result<String, Self::Error>;
+
+    /// Combines the partial signatures of all `signers` into a signature proof, returned as hex
+    /// string.
+    async fn combine_multisig_signatures(
+        &mut self,
+        multisig: Address,
+        signers: Vec<PublicKey>,
+        commitments: Vec<String>,
+        partial_signatures: Vec<String>,
+    ) -> Result<String, Self::Error>;
 }
@@ -1,27 +1,41 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::stream::{BoxStream, StreamExt};
 
+use beserial::Deserialize;
 use nimiq_account::Account;
-use nimiq_blockchain::{AbstractBlockchain, Blockchain, BlockchainEvent};
-use nimiq_hash::Blake2bHash;
+use nimiq_blockchain::{AbstractBlockchain, Blockchain, BlockchainEvent, ExtTxData};
+use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
+use nimiq_mempool::Mempool;
+use nimiq_primitives::account::ValidatorId;
 use nimiq_primitives::policy;
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
-    types::{Block, Inherent, SlashedSlots, Slot, Stake, Stakes, Transaction, Validator},
+    types::{
+        Block, HistoryFilter, Inherent, MempoolTransaction, RawTransactionInfo, SlashedSlots, Slot,
+        Stake, Stakes, StakingEvent, Transaction, TransactionData, TransactionDirection,
+        TransactionReceipt, Validator,
+    },
 };
 
 use crate::error::Error;
 
 pub struct BlockchainDispatcher {
     blockchain: Arc<Blockchain>,
+
+    mempool: Option<Arc<Mempool>>,
 }
 
 impl BlockchainDispatcher {
-    pub fn new(blockchain: Arc<Blockchain>) -> Self {
-        Self { blockchain }
+    pub fn new(blockchain: Arc<Blockchain>, mempool: Option<Arc<Mempool>>) -> Self {
+        Self {
+            blockchain,
+            mempool,
+        }
     }
 }
 
@@ -127,8 +141,37 @@ impl BlockchainInterface for BlockchainDispatcher {
         })
     }
 
-    async fn get_raw_transaction_info(&mut self, _raw_tx: String) -> Result<(), Error> {
-        Err(Error::NotImplemented)
+    async fn get_raw_transaction_info(
+        &mut self,
+        raw_tx: String,
+    ) -> Result<RawTransactionInfo, Error> {
+        let transaction: nimiq_transaction::Transaction =
+            Deserialize::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+        let hash: Blake2bHash = transaction.hash();
+
+        let verification = transaction.verify(self.blockchain.network_id);
+
+        let in_mempool = self
+            .mempool
+            .as_ref()
+            .map(|mempool| mempool.contains(&hash))
+            .unwrap_or(false);
+
+        let block_number = self
+            .blockchain
+            .history_store
+            .get_ext_tx_by_hash(&hash, None)
+            .pop()
+            .map(|ext_tx| ext_tx.block_number);
+
+        Ok(RawTransactionInfo {
+            transaction: MempoolTransaction::from_transaction(&transaction),
+            decoded_data: TransactionData::from_transaction(&transaction),
+            valid: verification.is_ok(),
+            error: verification.err().map(|e| e.to_string()),
+            in_mempool,
+            block_number,
+        })
     }
 
     async fn get_transaction_by_hash(&mut self, hash: Blake2bHash) -> Result<Transaction, Error> {
@@ -150,18 +193,52 @@ impl BlockchainInterface for BlockchainDispatcher {
             extended_tx_vec.pop().unwrap()
         };
 
-        let transaction = extended_tx.unwrap_basic(); // Because we found the extended_tx above, this cannot be None
+        // Inherents are indexed by hash as well, but they aren't transactions.
+        let transaction = match extended_tx.data {
+            ExtTxData::Basic(transaction) => transaction,
+            ExtTxData::Inherent(_) => return Err(Error::TransactionNotFound(hash)),
+        };
 
         Ok(Transaction::from_blockchain(
-            transaction.clone(),
+            transaction,
             extended_tx.block_number,
             extended_tx.block_time,
             self.blockchain.block_number(),
         ))
     }
 
-    async fn get_transaction_receipt(&mut self, _hash: Blake2bHash) -> Result<(), Error> {
-        Err(Error::NotImplemented)
+    async fn get_transaction_receipt(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<TransactionReceipt, Error> {
+        let extended_tx = self
+            .blockchain
+            .history_store
+            .get_ext_tx_by_hash(&hash, None)
+            .pop()
+            .ok_or_else(|| Error::TransactionNotFound(hash.clone()))?;
+
+        let block_hash = self
+            .blockchain
+            .get_block_at(extended_tx.block_number, false, None)
+            .ok_or_else(|| Error::BlockNotFound(extended_tx.block_number.into()))?
+            .hash();
+
+        Ok(TransactionReceipt {
+            transaction_hash: hash,
+            block_hash,
+            block_number: extended_tx.block_number,
+            timestamp: extended_tx.block_time,
+            confirmations: self
+                .blockchain
+                .block_number()
+                .saturating_sub(extended_tx.block_number)
+                + 1,
+            is_inherent: extended_tx.is_inherent(),
+            // Transactions that fail to apply invalidate the whole block, so every transaction in the
+            // history was executed successfully.
+            execution_result: true,
+        })
     }
 
     async fn get_transactions_by_block_number(
@@ -249,10 +326,12 @@ impl BlockchainInterface for BlockchainDispatcher {
     async fn get_transaction_hashes_by_address(
         &mut self,
         address: Address,
+        filter: Option<HistoryFilter>,
         max: Option<u16>,
     ) -> Result<Vec<Blake2bHash>, Error> {
         Ok(self.blockchain.history_store.get_tx_hashes_by_address(
             &address,
+            &filter.unwrap_or_default().into(),
             max.unwrap_or(500),
             None,
         ))
@@ -261,23 +340,92 @@ impl BlockchainInterface for BlockchainDispatcher {
     async fn get_transactions_by_address(
         &mut self,
         address: Address,
+        filter: Option<HistoryFilter>,
         max: Option<u16>,
     ) -> Result<Vec<Transaction>, Error> {
-        let tx_hashes = self.get_transaction_hashes_by_address(address, max).await?;
+        let tx_hashes = self
+            .get_transaction_hashes_by_address(address, filter, max)
+            .await?;
 
         let mut txs = vec![];
 
         // TODO: Use a single database transaction for all queries
 
         for tx_hash in tx_hashes {
-            txs.push(self.get_transaction_by_hash(tx_hash).await?);
+            for ext_tx in self
+                .blockchain
+                .history_store
+                .get_ext_tx_by_hash(&tx_hash, None)
+            {
+                if let ExtTxData::Basic(tx) = ext_tx.data {
+                    txs.push(Transaction::from_blockchain(
+                        tx,
+                        ext_tx.block_number,
+                        ext_tx.block_time,
+                        self.blockchain.block_number(),
+                    ));
+                }
+            }
         }
 
         Ok(txs)
     }
 
-    async fn list_stakes(&mut self) -> Result<Stakes, Error> {
-        let staking_contract = self.blockchain.get_staking_contract();
+    async fn get_inherents_by_address(
+        &mut self,
+        address: Address,
+        filter: Option<HistoryFilter>,
+        max: Option<u16>,
+    ) -> Result<Vec<Inherent>, Error> {
+        let filter = HistoryFilter {
+            direction: TransactionDirection::Incoming,
+            ..filter.unwrap_or_default()
+        };
+        let tx_hashes = self
+            .get_transaction_hashes_by_address(address, Some(filter), max)
+            .await?;
+
+        let mut inherents = vec![];
+
+        for tx_hash in tx_hashes {
+            for ext_tx in self
+                .blockchain
+                .history_store
+                .get_ext_tx_by_hash(&tx_hash, None)
+            {
+                if let ExtTxData::Inherent(inherent) = ext_tx.data {
+                    inherents.push(Inherent::from_transaction(
+                        inherent,
+                        ext_tx.block_number,
+                        ext_tx.block_time,
+                    ));
+                }
+            }
+        }
+
+        Ok(inherents)
+    }
+
+    async fn count_transactions_by_address(
+        &mut self,
+        address: Address,
+        filter: Option<HistoryFilter>,
+    ) -> Result<usize, Error> {
+        Ok(self.blockchain.history_store.count_tx_hashes_by_address(
+            &address,
+            &filter.unwrap_or_default().into(),
+            None,
+        ))
+    }
+
+    async fn list_stakes(&mut self, block_number: Option<u32>) -> Result<Stakes, Error> {
+        let staking_contract = match block_number {
+            Some(block_number) => self
+                .blockchain
+                .get_staking_contract_at(block_number)
+                .ok_or(Error::AccountStateNotAvailable(block_number))?,
+            None => self.blockchain.get_staking_contract(),
+        };
 
         let active_validators = staking_contract
             .active_validators_by_id
@@ -326,8 +474,53 @@ impl BlockchainInterface for BlockchainDispatcher {
             .boxed())
     }
 
-    async fn get_account(&mut self, address: Address) -> Result<Account, Error> {
-        let account = self.blockchain.get_account(&address);
+    #[stream]
+    async fn subscribe_for_staking_events(
+        &mut self,
+        validator_ids: Vec<ValidatorId>,
+        staker_addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, StakingEvent>, Error> {
+        let validator_ids: HashSet<ValidatorId> = HashSet::from_iter(validator_ids);
+        let staker_addresses: HashSet<Address> = HashSet::from_iter(staker_addresses);
+
+        // Empty filters subscribe to all staking events. `Unstaked` events don't have a validator,
+        // so they only match the staker addresses.
+        let subscribe_all = validator_ids.is_empty() && staker_addresses.is_empty();
+
+        Ok(self
+            .blockchain
+            .staking_notifier
+            .write()
+            .as_stream()
+            .filter(move |event| {
+                let is_relevant = subscribe_all
+                    || event
+                        .kind
+                        .validator_ids()
+                        .into_iter()
+                        .any(|validator_id| validator_ids.contains(validator_id))
+                    || event
+                        .kind
+                        .staker_address()
+                        .map_or(false, |address| staker_addresses.contains(address));
+
+                async move { is_relevant }
+            })
+            .boxed())
+    }
+
+    async fn get_account(
+        &mut self,
+        address: Address,
+        block_number: Option<u32>,
+    ) -> Result<Account, Error> {
+        let account = match block_number {
+            Some(block_number) => self
+                .blockchain
+                .get_account_at(&address, block_number)
+                .ok_or(Error::AccountStateNotAvailable(block_number))?,
+            None => self.blockchain.get_account(&address),
+        };
         if matches!(account, Account::Staking(_)) {
             Err(Error::GetAccountUnsupportedStakingContract)
         } else {
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use parking_lot::RwLock;
 
 use beserial::{Deserialize, Serialize};
@@ -8,38 +9,58 @@ use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 use nimiq_consensus::ConsensusProxy;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, KeyPair};
+use nimiq_mempool::fee_estimation::{FeePriority, DEFAULT_FEE_ESTIMATION_BLOCKS};
 use nimiq_mempool::ReturnCode;
 use nimiq_network_libp2p::Network;
 use nimiq_primitives::{account::ValidatorId, coin::Coin, networks::NetworkId};
-use nimiq_transaction::Transaction;
+use nimiq_transaction::{
+    account::htlc_contract::{AnyHash, HashAlgorithm},
+    SignatureProof, Transaction,
+};
 use nimiq_transaction_builder::TransactionBuilder;
 
-use nimiq_rpc_interface::{consensus::ConsensusInterface, types::ValidityStartHeight};
+use nimiq_rpc_interface::{
+    consensus::ConsensusInterface,
+    types::{TransactionStatus, ValidityStartHeight},
+};
 
-use crate::{error::Error, wallets::UnlockedWallets};
+use crate::{error::Error, transaction_tracker::TransactionTracker, wallets::UnlockedWallets};
 use nimiq_blockchain::AbstractBlockchain;
 
 pub struct ConsensusDispatcher {
     consensus: ConsensusProxy<Network>,
 
     unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+
+    /// If set, the `send_*` methods replace a zero fee with the estimated fee for this priority.
+    auto_fee: Option<FeePriority>,
+
+    /// Follows the transactions sent through all endpoints until they are confirmed.
+    tracker: Arc<TransactionTracker>,
 }
 
 impl ConsensusDispatcher {
     pub fn new(
         consensus: ConsensusProxy<Network>,
         unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+        auto_fee: Option<FeePriority>,
+        tracker: Arc<TransactionTracker>,
     ) -> Self {
         Self {
             consensus,
             unlocked_wallets,
+            auto_fee,
+            tracker,
         }
     }
 
     async fn push_transaction(&self, tx: Transaction) -> Result<Blake2bHash, Error> {
         let txid = tx.hash::<Blake2bHash>();
-        match self.consensus.send_transaction(tx).await {
-            Ok(ReturnCode::Accepted) => Ok(txid),
+        match self.consensus.send_transaction(tx.clone()).await {
+            Ok(ReturnCode::Accepted) => {
+                self.tracker.track(tx, self.unlocked_wallets.clone());
+                Ok(txid)
+            }
             Ok(return_code) => Err(Error::TransactionRejected(return_code)),
             Err(e) => Err(Error::NetworkError(e)),
         }
@@ -57,6 +78,30 @@ impl ConsensusDispatcher {
             .clone()) // TODO: Avoid cloning
     }
 
+    /// Creates a transaction with `create` and sends it. If the given `fee` is zero and fees are
+    /// filled in automatically, the fee is estimated from a transaction created without fee, since
+    /// the fee doesn't change the size of the transaction.
+    async fn send_with_fee<F>(&mut self, fee: Coin, create: F) -> Result<Blake2bHash, Error>
+    where
+        F: for<'a> Fn(&'a mut Self, Coin) -> BoxFuture<'a, Result<String, Error>> + Send + Sync,
+    {
+        let fee = match self.auto_fee.filter(|_| fee.is_zero()) {
+            Some(priority) => {
+                let raw_tx = create(self, Coin::ZERO).await?;
+                let transaction = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+                self.consensus.mempool.estimate_fee(
+                    priority,
+                    &transaction,
+                    DEFAULT_FEE_ESTIMATION_BLOCKS,
+                )
+            }
+            None => fee,
+        };
+
+        let raw_tx = create(self, fee).await?;
+        self.send_raw_transaction(raw_tx).await
+    }
+
     fn network_id(&self) -> NetworkId {
         self.consensus.blockchain.network_id
     }
@@ -84,6 +129,22 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.push_transaction(tx).await
     }
 
+    async fn get_transaction_status(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<TransactionStatus, Self::Error> {
+        self.tracker
+            .status(&hash)
+            .ok_or(Error::TransactionNotFound(hash))
+    }
+
+    async fn list_pending_transactions(
+        &mut self,
+        sender: Option<Address>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        Ok(self.tracker.pending(sender.as_ref()))
+    }
+
     async fn create_basic_transaction(
         &mut self,
         wallet: Address,
@@ -112,11 +173,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_basic_transaction(wallet, recipient, value, fee, validity_start_height)
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_basic_transaction(
+                wallet.clone(),
+                recipient.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
     }
 
     async fn create_stake_transaction(
@@ -151,18 +217,17 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_stake_transaction(
-                wallet,
-                validator_id,
-                staker_address,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_stake_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                staker_address.clone(),
                 value,
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_rededicate_transaction(
@@ -197,18 +262,17 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_rededicate_transaction(
-                wallet,
-                from_validator_id,
-                to_validator_id,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_rededicate_transaction(
+                wallet.clone(),
+                from_validator_id.clone(),
+                to_validator_id.clone(),
                 value,
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_retire_transaction(
@@ -240,11 +304,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_retire_transaction(wallet, validator_id, value, fee, validity_start_height)
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_retire_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
     }
 
     async fn create_reactivate_transaction(
@@ -276,11 +345,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_reactivate_transaction(wallet, validator_id, value, fee, validity_start_height)
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_reactivate_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
     }
 
     async fn create_unstake_transaction(
@@ -312,11 +386,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_unstake_transaction(wallet, recipient, value, fee, validity_start_height)
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_unstake_transaction(
+                wallet.clone(),
+                recipient.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
     }
 
     async fn create_new_validator_transaction(
@@ -356,18 +435,17 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_new_validator_transaction(
-                wallet,
-                reward_address,
-                validator_secret_key,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_new_validator_transaction(
+                wallet.clone(),
+                reward_address.clone(),
+                validator_secret_key.clone(),
                 value,
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_update_validator_transaction(
@@ -419,19 +497,18 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_update_validator_transaction(
-                wallet,
-                validator_id,
-                new_reward_address,
-                old_validator_secret_key,
-                new_validator_secret_key,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_update_validator_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                new_reward_address.clone(),
+                old_validator_secret_key.clone(),
+                new_validator_secret_key.clone(),
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_retire_validator_transaction(
@@ -468,17 +545,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_retire_validator_transaction(
-                wallet,
-                validator_id,
-                validator_secret_key,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_retire_validator_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                validator_secret_key.clone(),
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_reactivate_validator_transaction(
@@ -515,17 +591,16 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_reactivate_validator_transaction(
-                wallet,
-                validator_id,
-                validator_secret_key,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_reactivate_validator_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                validator_secret_key.clone(),
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_drop_validator_transaction(
@@ -565,18 +640,17 @@ impl ConsensusInterface for ConsensusDispatcher {
         fee: Coin,
         validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
-        let raw_tx = self
-            .create_drop_validator_transaction(
-                validator_id,
-                recipient,
-                validator_secret_key,
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_drop_validator_transaction(
+                validator_id.clone(),
+                recipient.clone(),
+                validator_secret_key.clone(),
                 value,
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
-        self.send_raw_transaction(raw_tx).await
+        })
+        .await
     }
 
     async fn create_unpark_validator_transaction(
@@ -612,17 +686,351 @@ impl ConsensusInterface for ConsensusDispatcher {
         validator_secret_key: String,
         fee: Coin,
         validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_unpark_validator_transaction(
+                wallet.clone(),
+                validator_id.clone(),
+                validator_secret_key.clone(),
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
+
+    async fn create_new_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        htlc_sender: Address,
+        htlc_recipient: Address,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        timeout: u64,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let transaction = TransactionBuilder::new_create_htlc(
+            &self.get_wallet_keypair(&wallet)?,
+            htlc_sender,
+            htlc_recipient,
+            hash_root,
+            hash_count,
+            hash_algorithm,
+            timeout,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_new_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        htlc_sender: Address,
+        htlc_recipient: Address,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        timeout: u64,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_new_htlc_transaction(
+                wallet.clone(),
+                htlc_sender.clone(),
+                htlc_recipient.clone(),
+                hash_root.clone(),
+                hash_count,
+                hash_algorithm,
+                timeout,
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
+
+    async fn create_redeem_regular_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        pre_image: AnyHash,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let transaction = TransactionBuilder::new_redeem_htlc_regular(
+            &self.get_wallet_keypair(&wallet)?,
+            contract_address,
+            recipient,
+            pre_image,
+            hash_root,
+            hash_count,
+            hash_algorithm,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_redeem_regular_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        pre_image: AnyHash,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_redeem_regular_htlc_transaction(
+                wallet.clone(),
+                contract_address.clone(),
+                recipient.clone(),
+                pre_image.clone(),
+                hash_root.clone(),
+                hash_count,
+                hash_algorithm,
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
+
+    async fn create_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let transaction = TransactionBuilder::new_redeem_htlc_timeout(
+            &self.get_wallet_keypair(&wallet)?,
+            contract_address,
+            recipient,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_redeem_timeout_htlc_transaction(
+                wallet.clone(),
+                contract_address.clone(),
+                recipient.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
+
+    async fn sign_redeem_early_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let signature = TransactionBuilder::sign_redeem_htlc_early(
+            &self.get_wallet_keypair(&wallet)?,
+            contract_address,
+            recipient,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(hex::encode(&signature.serialize_to_vec()))
+    }
+
+    async fn create_redeem_early_htlc_transaction(
+        &mut self,
+        contract_address: Address,
+        recipient: Address,
+        htlc_sender_signature: String,
+        htlc_recipient_signature: String,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let htlc_sender_signature =
+            SignatureProof::deserialize_from_vec(&hex::decode(htlc_sender_signature)?)?;
+        let htlc_recipient_signature =
+            SignatureProof::deserialize_from_vec(&hex::decode(htlc_recipient_signature)?)?;
+
+        let transaction = TransactionBuilder::new_redeem_htlc_early(
+            contract_address,
+            recipient,
+            htlc_sender_signature,
+            htlc_recipient_signature,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_redeem_early_htlc_transaction(
+        &mut self,
+        contract_address: Address,
+        recipient: Address,
+        htlc_sender_signature: String,
+        htlc_recipient_signature: String,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
     ) -> Result<Blake2bHash, Error> {
         let raw_tx = self
-            .create_unpark_validator_transaction(
-                wallet,
-                validator_id,
-                validator_secret_key,
+            .create_redeem_early_htlc_transaction(
+                contract_address,
+                recipient,
+                htlc_sender_signature,
+                htlc_recipient_signature,
+                value,
                 fee,
                 validity_start_height,
             )
-            .await
-            .unwrap();
+            .await?;
         self.send_raw_transaction(raw_tx).await
     }
+
+    async fn create_new_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let transaction = TransactionBuilder::new_create_vesting(
+            &self.get_wallet_keypair(&wallet)?,
+            owner,
+            start_time,
+            time_step,
+            num_steps,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_new_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_new_vesting_transaction(
+                wallet.clone(),
+                owner.clone(),
+                start_time,
+                time_step,
+                num_steps,
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
+
+    async fn create_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<String, Error> {
+        let transaction = TransactionBuilder::new_redeem_vesting(
+            &self.get_wallet_keypair(&wallet)?,
+            contract_address,
+            recipient,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction))
+    }
+
+    async fn send_redeem_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> Result<Blake2bHash, Error> {
+        self.send_with_fee(fee, move |this, fee| {
+            this.create_redeem_vesting_transaction(
+                wallet.clone(),
+                contract_address.clone(),
+                recipient.clone(),
+                value,
+                fee,
+                validity_start_height,
+            )
+        })
+        .await
+    }
 }
@@ -1,11 +1,19 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
 use parking_lot::RwLock;
 
-use nimiq_hash::Blake2bHash;
-use nimiq_mempool::Mempool;
-use nimiq_rpc_interface::mempool::MempoolInterface;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_mempool::fee_estimation::DEFAULT_FEE_ESTIMATION_BLOCKS;
+use nimiq_mempool::{Mempool, MempoolEvent};
+use nimiq_rpc_interface::{
+    mempool::MempoolInterface,
+    types::{FeeEstimate, HashOrTx, MempoolInfo, MempoolTransaction, MempoolTransactionEvent},
+};
 
 use crate::{error::Error, wallets::UnlockedWallets};
 
@@ -35,23 +43,117 @@ impl MempoolDispatcher {
 impl MempoolInterface for MempoolDispatcher {
     type Error = Error;
 
-    async fn get_transaction(&mut self, _txid: Blake2bHash) -> Result<Option<()>, Error> {
-        /*Ok(self.mempool
-        .get_transaction(&txid)
-        // TODO: We can return a `Arc<Transaction>`, if we implement `serde::Serialize` for it.
-        .map(|tx| Transaction::clone(&tx)))*/
-        Err(Error::NotImplemented)
+    async fn get_transaction(
+        &mut self,
+        txid: Blake2bHash,
+    ) -> Result<Option<MempoolTransaction>, Error> {
+        Ok(self
+            .mempool
+            .get_transaction(&txid)
+            .map(|tx| MempoolTransaction::from_transaction(&tx)))
     }
 
-    async fn mempool_content(&mut self, _include_transactions: bool) -> Result<Vec<()>, Error> {
-        Err(Error::NotImplemented)
+    async fn mempool_content(
+        &mut self,
+        include_transactions: bool,
+    ) -> Result<Vec<HashOrTx>, Error> {
+        Ok(self
+            .mempool
+            .get_transactions(usize::MAX, 0f64)
+            .iter()
+            // `get_transactions` returns the transactions sorted ascending by fee, we want the most valuable first.
+            .rev()
+            .map(|tx| HashOrTx::from_transaction(tx, include_transactions))
+            .collect())
     }
 
-    async fn mempool(&mut self) -> Result<(), Error> {
-        Err(Error::NotImplemented)
+    async fn mempool(&mut self) -> Result<MempoolInfo, Error> {
+        let transactions = self.mempool.get_transactions(usize::MAX, 0f64);
+
+        Ok(MempoolInfo::from_transactions(
+            transactions.iter().map(|tx| tx.as_ref()),
+        ))
+    }
+
+    async fn estimate_fees(&mut self, num_blocks: Option<u32>) -> Result<FeeEstimate, Error> {
+        let estimate = self
+            .mempool
+            .estimate_fees(num_blocks.unwrap_or(DEFAULT_FEE_ESTIMATION_BLOCKS));
+
+        Ok(FeeEstimate {
+            fast: estimate.fast,
+            normal: estimate.normal,
+            slow: estimate.slow,
+        })
     }
 
-    async fn get_mempool_transaction(&mut self) -> Result<(), Error> {
-        Err(Error::NotImplemented)
+    async fn get_mempool_transaction(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> Result<MempoolTransaction, Error> {
+        self.mempool
+            .get_transaction(&hash)
+            .map(|tx| MempoolTransaction::from_transaction(&tx))
+            .ok_or(Error::TransactionNotFound(hash))
+    }
+
+    #[stream]
+    async fn subscribe_for_mempool_transactions(
+        &mut self,
+        addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, MempoolTransactionEvent>, Error> {
+        let addresses: HashSet<Address> = HashSet::from_iter(addresses);
+
+        // Subscribe before taking the snapshot, such that we don't miss any events in between.
+        let events = self.mempool.notifier.write().as_stream();
+
+        // An empty set of addresses subscribes to all transactions.
+        let snapshot = if addresses.is_empty() {
+            self.mempool.get_transactions(usize::MAX, 0f64)
+        } else {
+            self.mempool
+                .get_transactions_by_addresses(addresses.clone(), usize::MAX)
+        };
+
+        // A transaction between two of the addresses is returned for both of them.
+        let mut known_hashes = HashSet::new();
+        let snapshot = snapshot
+            .into_iter()
+            .filter(|tx| known_hashes.insert(tx.hash::<Blake2bHash>()))
+            .map(|tx| MempoolTransactionEvent::Added {
+                transaction: MempoolTransaction::from_transaction(&tx),
+            })
+            .collect::<Vec<_>>();
+
+        let events = events.filter_map(move |event| {
+            let event = match event {
+                MempoolEvent::TransactionAdded(_, tx) | MempoolEvent::TransactionRestored(tx) => {
+                    MempoolTransactionEvent::Added {
+                        transaction: MempoolTransaction::from_transaction(&tx),
+                    }
+                }
+                MempoolEvent::TransactionMined(tx) => MempoolTransactionEvent::Included {
+                    transaction: MempoolTransaction::from_transaction(&tx),
+                },
+                MempoolEvent::TransactionEvicted(tx) => MempoolTransactionEvent::Removed {
+                    transaction: MempoolTransaction::from_transaction(&tx),
+                },
+            };
+
+            let transaction = event.transaction();
+            let is_relevant = addresses.is_empty()
+                || addresses.contains(&transaction.from)
+                || addresses.contains(&transaction.to);
+
+            async move {
+                if is_relevant {
+                    Some(event)
+                } else {
+                    None
+                }
+            }
+        });
+
+        Ok(stream::iter(snapshot).chain(events).boxed())
     }
 }
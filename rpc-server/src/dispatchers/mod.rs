@@ -2,6 +2,7 @@ mod blockchain;
 mod consensus;
 mod mempool;
 mod network;
+#[cfg(feature = "validator")]
 mod validator;
 mod wallet;
 
@@ -9,5 +10,6 @@ pub use blockchain::BlockchainDispatcher;
 pub use consensus::ConsensusDispatcher;
 pub use mempool::MempoolDispatcher;
 pub use network::NetworkDispatcher;
+#[cfg(feature = "validator")]
 pub use validator::ValidatorDispatcher;
 pub use wallet::WalletDispatcher;
@@ -1,10 +1,17 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use nimiq_network_libp2p::Network;
+use nimiq_network_interface::network::Network as _;
+use nimiq_network_libp2p::{
+    libp2p::core::connection::ConnectedPoint, Multiaddr, Network, PeerId, PeerInfo,
+};
 
-use nimiq_rpc_interface::{network::NetworkInterface, types::Peer};
+use nimiq_rpc_interface::{
+    network::NetworkInterface,
+    types::{ConnectionDirection, Peer},
+};
 
 use crate::error::Error;
 
@@ -18,6 +25,40 @@ impl NetworkDispatcher {
     }
 }
 
+fn parse_peer_id(peer_id: &str) -> Result<PeerId, Error> {
+    PeerId::from_str(peer_id).map_err(|_| Error::InvalidPeerId(peer_id.to_owned()))
+}
+
+fn peer_from_info(peer_info: PeerInfo) -> Peer {
+    let (address, direction) = match peer_info.endpoint {
+        ConnectedPoint::Dialer { address } => (address, ConnectionDirection::Outbound),
+        ConnectedPoint::Listener { send_back_addr, .. } => {
+            (send_back_addr, ConnectionDirection::Inbound)
+        }
+    };
+
+    Peer {
+        peer_id: peer_info.peer_id.to_string(),
+        address: address.to_string(),
+        direction,
+        addresses: peer_info
+            .contact
+            .as_ref()
+            .map(|contact| contact.addresses().map(ToString::to_string).collect())
+            .unwrap_or_default(),
+        services: peer_info
+            .contact
+            .as_ref()
+            .map(|contact| contact.services().bits()),
+        latency: peer_info.latency.map(|latency| latency.as_millis() as u64),
+        topics: peer_info
+            .topics
+            .iter()
+            .map(|topic| topic.as_str().to_owned())
+            .collect(),
+    }
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl NetworkInterface for NetworkDispatcher {
@@ -28,17 +69,50 @@ impl NetworkInterface for NetworkDispatcher {
     }
 
     async fn get_peer_count(&mut self) -> Result<usize, Self::Error> {
-        // TODO: Wait for connection pool implementation
-        todo!()
+        Ok(self.network.get_peers().len())
     }
 
     async fn get_peer_list(&mut self) -> Result<Vec<Peer>, Self::Error> {
-        // TODO: Wait for connection pool implementation
-        todo!()
+        Ok(self
+            .network
+            .peer_infos()
+            .await?
+            .into_iter()
+            .map(peer_from_info)
+            .collect())
+    }
+
+    async fn get_peer_state(&mut self, peer_id: String) -> Result<Peer, Self::Error> {
+        let id = parse_peer_id(&peer_id)?;
+
+        self.network
+            .peer_infos()
+            .await?
+            .into_iter()
+            .find(|peer_info| peer_info.peer_id == id)
+            .map(peer_from_info)
+            .ok_or(Error::PeerNotFound(peer_id))
+    }
+
+    async fn dial_address(&mut self, address: String) -> Result<(), Self::Error> {
+        let multiaddr =
+            Multiaddr::from_str(&address).map_err(|_| Error::InvalidMultiaddr(address.clone()))?;
+
+        Ok(self.network.dial_address(multiaddr).await?)
+    }
+
+    async fn disconnect_peer(&mut self, peer_id: String) -> Result<(), Self::Error> {
+        Ok(self
+            .network
+            .disconnect_peer(parse_peer_id(&peer_id)?)
+            .await?)
+    }
+
+    async fn ban_peer(&mut self, peer_id: String) -> Result<(), Self::Error> {
+        Ok(self.network.ban_peer(parse_peer_id(&peer_id)?).await?)
     }
 
-    async fn get_peer_state(&mut self, _peer_id: String) -> Result<Peer, Self::Error> {
-        // TODO: Wait for connection pool implementation
-        todo!()
+    async fn unban_peer(&mut self, peer_id: String) -> Result<(), Self::Error> {
+        Ok(self.network.unban_peer(parse_peer_id(&peer_id)?).await?)
     }
 }
@@ -1,14 +1,33 @@
 use async_trait::async_trait;
 
-use crate::error::Error;
+use nimiq_block::BlockType;
+use nimiq_blockchain::AbstractBlockchain;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_mempool::ReturnCode;
+use nimiq_network_libp2p::Network;
+use nimiq_primitives::{account::ValidatorId, coin::Coin};
+use nimiq_rpc_interface::{
+    types::{BlockProductionStatistics, Slots, UpcomingSlot, ValidatorKeys, ValidatorStakingState},
+    validator::ValidatorInterface,
+};
+use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_validator::validator::{
+    ValidatorProxy, ValidatorStakingState as AbstractValidatorStakingState,
+};
 
-use nimiq_rpc_interface::validator::ValidatorInterface;
+use crate::error::Error;
 
-pub struct ValidatorDispatcher {}
+pub struct ValidatorDispatcher {
+    validator: ValidatorProxy<Network>,
+}
 
 impl ValidatorDispatcher {
-    pub fn new() -> Self {
-        Self {}
+    /// The default number of views for which the upcoming slots are returned.
+    const DEFAULT_UPCOMING_VIEWS: u32 = 10;
+
+    pub fn new(validator: ValidatorProxy<Network>) -> Self {
+        Self { validator }
     }
 }
 
@@ -16,4 +35,133 @@ impl ValidatorDispatcher {
 #[async_trait]
 impl ValidatorInterface for ValidatorDispatcher {
     type Error = Error;
+
+    async fn get_validator_id(&mut self) -> Result<Option<ValidatorId>, Error> {
+        Ok(self.validator.staking_state().0)
+    }
+
+    async fn get_public_keys(&mut self) -> Result<ValidatorKeys, Error> {
+        let wallet_key = self.validator.wallet_key.as_ref();
+
+        Ok(ValidatorKeys {
+            signing_key: self.validator.signing_key.public_key.compress(),
+            wallet_address: wallet_key.map(Address::from),
+            wallet_public_key: wallet_key.map(|key_pair| key_pair.public),
+        })
+    }
+
+    async fn get_slots(&mut self) -> Result<Option<Slots>, Error> {
+        let slot_band = match self.validator.slot_band() {
+            Some(slot_band) => slot_band,
+            None => return Ok(None),
+        };
+
+        let slots = self
+            .validator
+            .consensus
+            .blockchain
+            .current_validators()
+            .map(Slots::from_slots)
+            .and_then(|mut slots| {
+                if (slot_band as usize) < slots.len() {
+                    Some(slots.swap_remove(slot_band as usize))
+                } else {
+                    None
+                }
+            });
+
+        Ok(slots)
+    }
+
+    async fn get_upcoming_slots(
+        &mut self,
+        max_views: Option<u32>,
+    ) -> Result<Vec<UpcomingSlot>, Error> {
+        let blockchain = &self.validator.consensus.blockchain;
+
+        // Macro blocks are produced by all validators together.
+        if blockchain.get_next_block_type(None) != BlockType::Micro {
+            return Ok(vec![]);
+        }
+
+        let head = blockchain.head();
+        let block_number = head.block_number() + 1;
+        let first_view_number = head.next_view_number();
+        let max_views = max_views.unwrap_or(Self::DEFAULT_UPCOMING_VIEWS);
+        let public_key = self.validator.signing_key.public_key.compress();
+
+        let slots = (first_view_number..first_view_number.saturating_add(max_views))
+            .filter_map(|view_number| {
+                let (validator, slot_number) =
+                    blockchain.get_slot_owner_at(block_number, view_number, None)?;
+
+                if validator.public_key.compressed() == &public_key {
+                    Some(UpcomingSlot {
+                        block_number,
+                        view_number,
+                        slot_number,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(slots)
+    }
+
+    async fn get_staking_state(&mut self) -> Result<ValidatorStakingState, Error> {
+        let state = match self.validator.staking_state().1 {
+            AbstractValidatorStakingState::Active => ValidatorStakingState::Active,
+            AbstractValidatorStakingState::Parked => ValidatorStakingState::Parked,
+            AbstractValidatorStakingState::Inactive => ValidatorStakingState::Inactive,
+            AbstractValidatorStakingState::NoStake => ValidatorStakingState::NoStake,
+        };
+
+        Ok(state)
+    }
+
+    async fn get_block_production_statistics(
+        &mut self,
+    ) -> Result<BlockProductionStatistics, Error> {
+        let statistics = self.validator.statistics();
+
+        Ok(BlockProductionStatistics {
+            produced_blocks: statistics.produced_blocks,
+            missed_blocks: statistics.missed_blocks,
+        })
+    }
+
+    async fn unpark(&mut self, fee: Coin) -> Result<Blake2bHash, Error> {
+        let wallet_key = self
+            .validator
+            .wallet_key
+            .as_ref()
+            .ok_or(Error::ValidatorWalletNotConfigured)?;
+
+        let validator_id = self
+            .validator
+            .staking_state()
+            .0
+            .ok_or(Error::ValidatorNotFound)?;
+
+        let blockchain = &self.validator.consensus.blockchain;
+
+        let transaction = TransactionBuilder::new_unpark_validator(
+            None,
+            wallet_key,
+            &validator_id,
+            &self.validator.signing_key,
+            fee,
+            blockchain.block_number(),
+            blockchain.network_id(),
+        );
+
+        let txid = transaction.hash::<Blake2bHash>();
+        match self.validator.consensus.send_transaction(transaction).await {
+            Ok(ReturnCode::Accepted) => Ok(txid),
+            Ok(return_code) => Err(Error::TransactionRejected(return_code)),
+            Err(e) => Err(Error::NetworkError(e)),
+        }
+    }
 }
@@ -3,11 +3,20 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use parking_lot::RwLock;
 
-use beserial::Deserialize;
+use beserial::{Deserialize, Serialize};
+use nimiq_blockchain::{Blockchain, HistoryFilter};
+use nimiq_keys::multisig::{Commitment, PartialSignature, RandomSecret};
 use nimiq_keys::{Address, KeyPair, PrivateKey, PublicKey, Signature};
-use nimiq_rpc_interface::wallet::{ReturnAccount, ReturnSignature, WalletInterface};
+use nimiq_mnemonic::Mnemonic;
+use nimiq_rpc_interface::wallet::{
+    ReturnAccount, ReturnCommitment, ReturnMultisigAccount, ReturnSignature, WalletInterface,
+};
+use nimiq_transaction::Transaction;
 use nimiq_utils::otp::Locked;
-use nimiq_wallet::{WalletAccount, WalletStore};
+use nimiq_wallet::{
+    EncryptedWalletBackup, MnemonicWallet, MultisigAccount, WalletAccount, WalletBackup,
+    WalletStore,
+};
 
 use crate::{error::Error, wallets::UnlockedWallets};
 
@@ -19,18 +28,68 @@ fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
     }
 }
 
+fn bytes32_from_hex(s: &str) -> Result<[u8; 32], Error> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(s, &mut bytes)?;
+    Ok(bytes)
+}
+
+fn commitments_from_hex(commitments: &[String]) -> Result<Vec<Commitment>, Error> {
+    commitments
+        .iter()
+        .map(|commitment| {
+            Commitment::from_bytes(bytes32_from_hex(commitment)?).ok_or(Error::InvalidCommitment)
+        })
+        .collect()
+}
+
 pub struct WalletDispatcher {
     wallet_store: Arc<WalletStore>,
     pub unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    /// Used to discover the accounts of imported mnemonics.
+    blockchain: Option<Arc<Blockchain>>,
 }
 
 impl WalletDispatcher {
-    pub fn new(wallet_store: Arc<WalletStore>) -> Self {
+    const DEFAULT_GAP_LIMIT: u32 = 20;
+    /// Each unused account up to the gap limit is derived, so larger gap limits are rejected.
+    const MAX_GAP_LIMIT: u32 = 1000;
+
+    pub fn new(wallet_store: Arc<WalletStore>, blockchain: Option<Arc<Blockchain>>) -> Self {
         Self {
             wallet_store,
             unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            blockchain,
         }
     }
+
+    /// An address is used if it has any transactions or a balance.
+    fn is_used(&self, address: &Address) -> bool {
+        match &self.blockchain {
+            Some(blockchain) => {
+                !blockchain
+                    .history_store
+                    .get_tx_hashes_by_address(address, &HistoryFilter::default(), 1, None)
+                    .is_empty()
+                    || !blockchain.get_account(address).balance().is_zero()
+            }
+            None => false,
+        }
+    }
+
+    fn multisig_account(&self, address: &Address) -> Result<MultisigAccount, Error> {
+        self.wallet_store
+            .get_multisig(address, None)
+            .ok_or_else(|| Error::MultisigAccountNotFound(address.clone()))
+    }
+}
+
+fn return_multisig_account(account: MultisigAccount) -> ReturnMultisigAccount {
+    ReturnMultisigAccount {
+        address: account.address,
+        min_signatures: account.min_signatures,
+        public_keys: account.public_keys,
+    }
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
@@ -60,10 +119,118 @@ impl WalletInterface for WalletDispatcher {
         Ok(address)
     }
 
+    async fn import_mnemonic(
+        &mut self,
+        mnemonic: String,
+        passphrase: Option<String>,
+        gap_limit: Option<u32>,
+        bip39_password: Option<String>,
+    ) -> Result<Vec<Address>, Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let gap_limit = gap_limit.unwrap_or(Self::DEFAULT_GAP_LIMIT);
+        if gap_limit > Self::MAX_GAP_LIMIT {
+            return Err(Error::GapLimitTooLarge(Self::MAX_GAP_LIMIT));
+        }
+
+        let mnemonic: Mnemonic = mnemonic
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .parse()
+            .map_err(|_| Error::InvalidMnemonic)?;
+
+        let wallet = MnemonicWallet::from_mnemonic(&mnemonic, bip39_password.as_deref())
+            .ok_or(Error::InvalidMnemonic)?;
+
+        let (accounts, hd_wallet) = match wallet {
+            MnemonicWallet::Hd(hd_wallet) => {
+                let accounts =
+                    hd_wallet.discover_accounts(gap_limit, |address| self.is_used(address));
+                (accounts, Some(hd_wallet))
+            }
+            MnemonicWallet::Legacy(account) => (vec![account], None),
+        };
+
+        let mut txn = self.wallet_store.create_write_transaction();
+
+        if let Some(hd_wallet) = hd_wallet {
+            let address = hd_wallet.address.clone();
+            let hd_wallet = Locked::with_defaults(hd_wallet, passphrase.as_bytes())?;
+            self.wallet_store
+                .put_hd_wallet(&address, &hd_wallet, &mut txn);
+        }
+
+        let mut addresses = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let address = account.address.clone();
+            let account = Locked::with_defaults(account, passphrase.as_bytes())?;
+            self.wallet_store.put(&address, &account, &mut txn);
+            addresses.push(address);
+        }
+        txn.commit();
+
+        Ok(addresses)
+    }
+
     async fn list_accounts(&mut self) -> Result<Vec<Address>, Error> {
         Ok(self.wallet_store.list(None))
     }
 
+    async fn export_accounts(
+        &mut self,
+        addresses: Option<Vec<Address>>,
+        passphrase: Option<String>,
+        backup_password: String,
+    ) -> Result<String, Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let addresses = addresses.unwrap_or_else(|| self.wallet_store.list(None));
+
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            let account = match self.unlocked_wallets.read().get(&address) {
+                Some(account) => account.clone(),
+                None => self
+                    .wallet_store
+                    .get(&address, None)
+                    .ok_or_else(|| Error::AccountNotFound(address.clone()))?
+                    .unlock(passphrase.as_bytes())
+                    .map_err(|_locked| Error::WrongPassphrase)?
+                    .clone(),
+            };
+            accounts.push(account);
+        }
+
+        let backup =
+            EncryptedWalletBackup::new(WalletBackup::new(accounts), backup_password.as_bytes())?;
+
+        Ok(hex::encode(backup.serialize_to_vec()))
+    }
+
+    async fn import_accounts(
+        &mut self,
+        backup: String,
+        backup_password: String,
+        passphrase: Option<String>,
+    ) -> Result<Vec<Address>, Error> {
+        let passphrase = passphrase.unwrap_or_default();
+        let backup = EncryptedWalletBackup::deserialize_from_vec(&hex::decode(&backup)?)?;
+        let accounts = backup
+            .decrypt(backup_password.as_bytes())
+            .ok_or(Error::InvalidBackup)?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        let mut addresses = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let address = account.address.clone();
+            let account = Locked::with_defaults(account, passphrase.as_bytes())?;
+            self.wallet_store.put(&address, &account, &mut txn);
+            addresses.push(address);
+        }
+        txn.commit();
+
+        Ok(addresses)
+    }
+
     async fn lock_account(&mut self, address: Address) -> Result<(), Error> {
         self.unlocked_wallets.write().remove(&address);
         Ok(())
@@ -162,4 +329,95 @@ impl WalletInterface for WalletDispatcher {
             &signature,
         ))
     }
+
+    async fn create_multisig_account(
+        &mut self,
+        min_signatures: u8,
+        public_keys: Vec<PublicKey>,
+    ) -> Result<ReturnMultisigAccount, Error> {
+        let account = MultisigAccount::new(min_signatures, public_keys)
+            .ok_or(Error::InvalidMultisigAccount)?;
+
+        let mut txn = self.wallet_store.create_write_transaction();
+        self.wallet_store
+            .put_multisig(&account.address, &account, &mut txn);
+        txn.commit();
+
+        Ok(return_multisig_account(account))
+    }
+
+    async fn list_multisig_accounts(&mut self) -> Result<Vec<Address>, Error> {
+        Ok(self.wallet_store.list_multisig(None))
+    }
+
+    async fn get_multisig_account(
+        &mut self,
+        address: Address,
+    ) -> Result<ReturnMultisigAccount, Error> {
+        Ok(return_multisig_account(self.multisig_account(&address)?))
+    }
+
+    async fn create_multisig_commitment(&mut self) -> Result<ReturnCommitment, Error> {
+        let commitment_pair = MultisigAccount::create_commitment();
+
+        Ok(ReturnCommitment {
+            commitment: hex::encode(commitment_pair.commitment().to_bytes()),
+            random_secret: hex::encode(commitment_pair.random_secret().to_bytes()),
+        })
+    }
+
+    async fn partially_sign_multisig_transaction(
+        &mut self,
+        multisig: Address,
+        wallet: Address,
+        raw_tx: String,
+        signers: Vec<PublicKey>,
+        commitments: Vec<String>,
+        random_secret: String,
+    ) -> Result<String, Error> {
+        let account = self.multisig_account(&multisig)?;
+        let transaction: Transaction = Deserialize::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+        let commitments = commitments_from_hex(&commitments)?;
+        let random_secret = RandomSecret::from(bytes32_from_hex(&random_secret)?);
+
+        let unlocked_wallets = self.unlocked_wallets.read();
+        let wallet = unlocked_wallets
+            .get(&wallet)
+            .ok_or_else(|| Error::UnlockedWalletNotFound(wallet.clone()))?;
+
+        let partial_signature = account
+            .partially_sign_transaction(
+                &wallet.key_pair,
+                &transaction,
+                &signers,
+                &random_secret,
+                &commitments,
+            )
+            .ok_or(Error::InvalidMultisigSigners)?;
+
+        Ok(hex::encode(partial_signature.as_bytes()))
+    }
+
+    async fn combine_multisig_signatures(
+        &mut self,
+        multisig: Address,
+        signers: Vec<PublicKey>,
+        commitments: Vec<String>,
+        partial_signatures: Vec<String>,
+    ) -> Result<String, Error> {
+        let account = self.multisig_account(&multisig)?;
+        let commitments = commitments_from_hex(&commitments)?;
+        let partial_signatures = partial_signatures
+            .iter()
+            .map(|partial_signature| {
+                Ok(PartialSignature::from(bytes32_from_hex(partial_signature)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let proof = account
+            .create_signature_proof(&signers, &commitments, &partial_signatures)
+            .ok_or(Error::InvalidMultisigSigners)?;
+
+        Ok(hex::encode(proof.serialize_to_vec()))
+    }
 }
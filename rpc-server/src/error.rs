@@ -28,12 +28,39 @@ pub enum Error {
     #[error("Failed to build a transaction: {0}")]
     TransactionBuilder(#[from] nimiq_transaction_builder::TransactionBuilderError),
 
+    #[error("Invalid vesting contract: {0}")]
+    VestingContract(
+        #[from]
+        nimiq_transaction_builder::recipient::vesting_contract::VestingRecipientBuilderError,
+    ),
+
     #[error("No account with address: {0}")]
     AccountNotFound(Address),
 
     #[error("Wrong passphrase")]
     WrongPassphrase,
 
+    #[error("Invalid mnemonic")]
+    InvalidMnemonic,
+
+    #[error("The gap limit must not exceed {0}")]
+    GapLimitTooLarge(u32),
+
+    #[error("Wrong backup password or corrupted backup")]
+    InvalidBackup,
+
+    #[error("No multisig account with address: {0}")]
+    MultisigAccountNotFound(Address),
+
+    #[error("Invalid multisig account parameters")]
+    InvalidMultisigAccount,
+
+    #[error("The signers are not a valid set of participants of the multisig account")]
+    InvalidMultisigSigners,
+
+    #[error("Invalid commitment")]
+    InvalidCommitment,
+
     #[error("No unlocked wallet with address: {0}")]
     UnlockedWalletNotFound(Address),
 
@@ -55,8 +82,26 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("No validator with our signing key in the staking contract")]
+    ValidatorNotFound,
+
+    #[error("The validator has no wallet account configured")]
+    ValidatorWalletNotConfigured,
+
+    #[error("Invalid peer id: {0}")]
+    InvalidPeerId(String),
+
+    #[error("Invalid multiaddr: {0}")]
+    InvalidMultiaddr(String),
+
+    #[error("Not connected to peer: {0}")]
+    PeerNotFound(String),
+
     #[error("getAccount doesn't support returning the staking contract. Use listStakes instead.")]
     GetAccountUnsupportedStakingContract,
+
+    #[error("Account state not available at block #{0}")]
+    AccountStateNotAvailable(u32),
 }
 
 impl From<Error> for nimiq_jsonrpc_core::RpcError {
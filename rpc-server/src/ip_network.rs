@@ -0,0 +1,225 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IpNetworkParseError {
+    #[error("Invalid IP address: {0}")]
+    InvalidAddress(#[from] std::net::AddrParseError),
+    #[error("Invalid prefix length: {0}")]
+    InvalidPrefixLength(String),
+}
+
+/// An IP network in CIDR notation, e.g. `192.168.0.0/16` or `fd00::/8`. A plain IP address is
+/// parsed as a network containing only that address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<Self, IpNetworkParseError> {
+        let max_prefix_len = Self::max_prefix_len(&address);
+        if prefix_len > max_prefix_len {
+            return Err(IpNetworkParseError::InvalidPrefixLength(
+                prefix_len.to_string(),
+            ));
+        }
+
+        // Clear the host bits, so that the network address is canonical.
+        let address = match address {
+            IpAddr::V4(address) => IpAddr::V4(Ipv4Addr::from(
+                u32::from(address) & Self::mask_v4(prefix_len),
+            )),
+            IpAddr::V6(address) => IpAddr::V6(Ipv6Addr::from(
+                u128::from(address) & Self::mask_v6(prefix_len),
+            )),
+        };
+
+        Ok(IpNetwork {
+            address,
+            prefix_len,
+        })
+    }
+
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns whether the given address is part of this network.
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.address, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                u32::from(*address) & Self::mask_v4(self.prefix_len) == u32::from(network)
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                u128::from(*address) & Self::mask_v6(self.prefix_len) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+
+    fn max_prefix_len(address: &IpAddr) -> u8 {
+        match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn mask_v4(prefix_len: u8) -> u32 {
+        u32::MAX
+            .checked_shl(32 - u32::from(prefix_len))
+            .unwrap_or(0)
+    }
+
+    fn mask_v6(prefix_len: u8) -> u128 {
+        u128::MAX
+            .checked_shl(128 - u32::from(prefix_len))
+            .unwrap_or(0)
+    }
+}
+
+impl From<IpAddr> for IpNetwork {
+    fn from(address: IpAddr) -> Self {
+        IpNetwork {
+            address,
+            prefix_len: Self::max_prefix_len(&address),
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.find('/') {
+            Some(i) => {
+                let address = s[..i].parse()?;
+                let prefix_len = s[i + 1..]
+                    .parse()
+                    .map_err(|_| IpNetworkParseError::InvalidPrefixLength(s[i + 1..].to_owned()))?;
+                Self::new(address, prefix_len)
+            }
+            None => Ok(IpNetwork::from(s.parse::<IpAddr>()?)),
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(s: &str) -> IpNetwork {
+        s.parse().unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn it_parses_ipv4_networks() {
+        let net = network("192.168.0.0/16");
+        assert_eq!(net.address(), ip("192.168.0.0"));
+        assert_eq!(net.prefix_len(), 16);
+        assert_eq!(net.to_string(), "192.168.0.0/16");
+
+        let net = network("0.0.0.0/0");
+        assert_eq!(net.prefix_len(), 0);
+
+        // A plain address is a network containing only that address.
+        let net = network("10.1.2.3");
+        assert_eq!(net.address(), ip("10.1.2.3"));
+        assert_eq!(net.prefix_len(), 32);
+    }
+
+    #[test]
+    fn it_parses_ipv6_networks() {
+        let net = network("fd00::/8");
+        assert_eq!(net.address(), ip("fd00::"));
+        assert_eq!(net.prefix_len(), 8);
+        assert_eq!(net.to_string(), "fd00::/8");
+
+        let net = network("::1");
+        assert_eq!(net.address(), ip("::1"));
+        assert_eq!(net.prefix_len(), 128);
+    }
+
+    #[test]
+    fn it_clears_host_bits() {
+        let net = network("192.168.1.77/24");
+        assert_eq!(net.address(), ip("192.168.1.0"));
+        assert_eq!(net, network("192.168.1.0/24"));
+
+        let net = network("fd12:3456::1/32");
+        assert_eq!(net.address(), ip("fd12:3456::"));
+
+        let net = network("255.255.255.255/0");
+        assert_eq!(net.address(), ip("0.0.0.0"));
+    }
+
+    #[test]
+    fn it_rejects_invalid_networks() {
+        assert!(matches!(
+            "10.0.0.0/33".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidPrefixLength(_))
+        ));
+        assert!(matches!(
+            "fd00::/129".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidPrefixLength(_))
+        ));
+        assert!(matches!(
+            "10.0.0.0/-1".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidPrefixLength(_))
+        ));
+        assert!(matches!(
+            "10.0.0.0/".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidPrefixLength(_))
+        ));
+        assert!(matches!(
+            "10.0.0/8".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidAddress(_))
+        ));
+        assert!(matches!(
+            "localhost".parse::<IpNetwork>(),
+            Err(IpNetworkParseError::InvalidAddress(_))
+        ));
+    }
+
+    #[test]
+    fn it_checks_if_it_contains_an_address() {
+        let net = network("192.168.0.0/16");
+        assert!(net.contains(&ip("192.168.0.0")));
+        assert!(net.contains(&ip("192.168.255.255")));
+        assert!(!net.contains(&ip("192.169.0.0")));
+        assert!(!net.contains(&ip("::ffff:192.168.0.1")));
+
+        let net = network("0.0.0.0/0");
+        assert!(net.contains(&ip("1.2.3.4")));
+        assert!(!net.contains(&ip("::1")));
+
+        let net = network("fd00::/8");
+        assert!(net.contains(&ip("fdff:ffff::1")));
+        assert!(!net.contains(&ip("fe00::")));
+
+        let net = network("::/0");
+        assert!(net.contains(&ip("2001:db8::1")));
+
+        let net = network("127.0.0.1");
+        assert!(net.contains(&ip("127.0.0.1")));
+        assert!(!net.contains(&ip("127.0.0.2")));
+    }
+}
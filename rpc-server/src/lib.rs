@@ -1,5 +1,8 @@
 pub mod dispatchers;
 pub mod error;
+pub mod ip_network;
+pub mod server;
+pub mod transaction_tracker;
 pub mod wallets;
 
 pub use error::Error;
@@ -0,0 +1,415 @@
+use std::convert::Infallible;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures::future::{self, Either};
+use futures::{SinkExt, StreamExt};
+use hyper::body::Bytes;
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE};
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn, Service};
+use hyper::{Body, HeaderMap, Response, StatusCode, Uri};
+use serde_json::{json, Value};
+use subtle::{Choice, ConstantTimeEq};
+use tokio::sync::{mpsc, Mutex};
+use warp::filters::cors::Builder as Cors;
+use warp::filters::BoxedFilter;
+use warp::ws::{Message, WebSocket, Ws};
+use warp::{Filter, Rejection, Reply};
+
+use nimiq_jsonrpc_core::{Credentials, Request};
+use nimiq_jsonrpc_server::Dispatcher;
+
+use crate::ip_network::IpNetwork;
+
+/// The configuration of an [`RpcServer`].
+pub struct RpcServerConfig {
+    /// The address the server listens on.
+    pub bind_to: SocketAddr,
+    /// Also accept connections over websocket.
+    pub enable_websocket: bool,
+    /// The rules that requests have to follow.
+    pub access: RpcAccess,
+    /// If set, cross-origin requests from these origins are allowed. `*` allows any origin.
+    pub allowed_origins: Option<Vec<String>>,
+}
+
+/// The rules that requests to an [`RpcServer`] have to follow.
+#[derive(Default)]
+pub struct RpcAccess {
+    /// If set, only requests from these networks are served.
+    allowed_networks: Option<Vec<IpNetwork>>,
+    /// The `Authorization` header that carries the credentials using HTTP basic auth.
+    basic_auth: Option<Vec<u8>>,
+    /// The `Authorization` header that carries the bearer token.
+    bearer_auth: Option<Vec<u8>>,
+}
+
+impl RpcAccess {
+    /// Creates the rules for an [`RpcServer`]. If credentials or a bearer token are given, requests
+    /// must carry them using HTTP basic auth or as bearer token respectively. If both are given,
+    /// either of them is accepted.
+    pub fn new(
+        allowed_networks: Option<Vec<IpNetwork>>,
+        credentials: Option<Credentials>,
+        bearer_token: Option<String>,
+    ) -> Self {
+        RpcAccess {
+            allowed_networks,
+            basic_auth: credentials.map(|credentials| {
+                let encoded =
+                    base64::encode(format!("{}:{}", credentials.username, credentials.password));
+                format!("Basic {}", encoded).into_bytes()
+            }),
+            bearer_auth: bearer_token.map(|token| format!("Bearer {}", token).into_bytes()),
+        }
+    }
+
+    /// Returns whether requests from the given IP address are served.
+    pub fn allows_ip(&self, ip: &IpAddr) -> bool {
+        match &self.allowed_networks {
+            Some(networks) => networks.iter().any(|network| network.contains(ip)),
+            None => true,
+        }
+    }
+
+    /// Returns whether a request with the given `Authorization` header is authorized. The header
+    /// is compared in constant time.
+    pub fn authorizes(&self, header: Option<&HeaderValue>) -> bool {
+        if self.basic_auth.is_none() && self.bearer_auth.is_none() {
+            return true;
+        }
+
+        let header = match header {
+            Some(header) => header.as_bytes(),
+            None => return false,
+        };
+
+        let matches = |expected: &Option<Vec<u8>>| {
+            expected.as_ref().map_or(Choice::from(0), |expected| {
+                expected.as_slice().ct_eq(header)
+            })
+        };
+
+        (matches(&self.basic_auth) | matches(&self.bearer_auth)).into()
+    }
+
+    /// The authentication schemes to announce to unauthorized clients.
+    fn challenges(&self) -> Vec<HeaderValue> {
+        let mut challenges = vec![];
+        if self.basic_auth.is_some() {
+            challenges.push(HeaderValue::from_static("Basic realm=\"nimiq\""));
+        }
+        if self.bearer_auth.is_some() {
+            challenges.push(HeaderValue::from_static("Bearer realm=\"nimiq\""));
+        }
+        challenges
+    }
+}
+
+/// A JSON-RPC server whose clients are checked against networks of allowed IP addresses and
+/// authenticated before their requests reach the dispatcher.
+///
+/// The IP address of a client is checked once per connection, before any request on it is read.
+/// Requests are accepted over HTTP POST and, if enabled, over a websocket at `/ws`, which also
+/// carries the notifications of subscriptions.
+pub struct RpcServer<D: Dispatcher> {
+    handler: Arc<RpcHandler<D>>,
+    bind_to: SocketAddr,
+    enable_websocket: bool,
+    cors: Option<Cors>,
+}
+
+impl<D: Dispatcher> RpcServer<D> {
+    pub fn new(config: RpcServerConfig, dispatcher: D) -> io::Result<Self> {
+        let cors = match config.allowed_origins {
+            Some(origins) => Some(Self::cors(&origins)?),
+            None => None,
+        };
+
+        Ok(RpcServer {
+            handler: Arc::new(RpcHandler {
+                access: config.access,
+                dispatcher: Mutex::new(dispatcher),
+                next_id: AtomicU64::new(1),
+            }),
+            bind_to: config.bind_to,
+            enable_websocket: config.enable_websocket,
+            cors,
+        })
+    }
+
+    /// Runs the server until it fails.
+    pub async fn run(&self) {
+        let routes = self.routes();
+        let handler = Arc::clone(&self.handler);
+
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let remote_ip = conn.remote_addr().ip();
+            let allowed = handler.access.allows_ip(&remote_ip);
+            let mut service = warp::service(routes.clone());
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    if allowed {
+                        Either::Left(service.call(request))
+                    } else {
+                        log::debug!("Rejected RPC request from {}", remote_ip);
+                        Either::Right(future::ok(error_response(StatusCode::FORBIDDEN)))
+                    }
+                }))
+            }
+        });
+
+        let server = match hyper::Server::try_bind(&self.bind_to) {
+            Ok(builder) => builder.serve(make_service),
+            Err(e) => {
+                log::error!("Failed to bind RPC server to {}: {}", self.bind_to, e);
+                return;
+            }
+        };
+
+        if let Err(e) = server.await {
+            log::error!("RPC server on {} failed: {}", self.bind_to, e);
+        }
+    }
+
+    /// Builds the routes of the server. Requests without valid credentials are rejected, except
+    /// for CORS preflight requests, which are answered before they reach the routes.
+    fn routes(&self) -> BoxedFilter<(Box<dyn Reply>,)> {
+        let handler = Arc::clone(&self.handler);
+        let authorized = warp::header::headers_cloned()
+            .and_then(move |headers: HeaderMap| {
+                let handler = Arc::clone(&handler);
+                async move {
+                    if handler.access.authorizes(headers.get(AUTHORIZATION)) {
+                        Ok(())
+                    } else {
+                        Err(warp::reject::custom(Unauthorized))
+                    }
+                }
+            })
+            .untuple_one();
+
+        let handler = Arc::clone(&self.handler);
+        let http = warp::path::end()
+            .and(warp::post())
+            .and(authorized.clone())
+            .and(warp::body::bytes())
+            .and_then(move |body: Bytes| {
+                let handler = Arc::clone(&handler);
+                async move {
+                    let data = handler
+                        .handle_raw_request(&body, None)
+                        .await
+                        .unwrap_or_default();
+                    Ok::<_, Rejection>(json_response(data))
+                }
+            });
+
+        let enable_websocket = self.enable_websocket;
+        let handler = Arc::clone(&self.handler);
+        let websocket = warp::path("ws")
+            .and(warp::path::end())
+            .and_then(move || async move {
+                if enable_websocket {
+                    Ok(())
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            })
+            .untuple_one()
+            .and(authorized)
+            .and(warp::ws())
+            .map(move |ws: Ws| {
+                let handler = Arc::clone(&handler);
+                ws.on_upgrade(move |websocket| handler.handle_websocket(websocket))
+            });
+
+        let handler = Arc::clone(&self.handler);
+        let routes = http.or(websocket).recover(move |rejection: Rejection| {
+            let handler = Arc::clone(&handler);
+            async move {
+                if rejection.find::<Unauthorized>().is_none() {
+                    return Err(rejection);
+                }
+
+                let mut response = error_response(StatusCode::UNAUTHORIZED);
+                for challenge in handler.access.challenges() {
+                    response.headers_mut().append(WWW_AUTHENTICATE, challenge);
+                }
+                Ok(response)
+            }
+        });
+
+        match &self.cors {
+            Some(cors) => routes
+                .with(cors.clone())
+                .map(|reply| Box::new(reply) as Box<dyn Reply>)
+                .boxed(),
+            None => routes
+                .map(|reply| Box::new(reply) as Box<dyn Reply>)
+                .boxed(),
+        }
+    }
+
+    /// Builds the CORS rules for the given origins. Fails if an origin isn't of the form
+    /// `scheme://host[:port]`.
+    fn cors(origins: &[String]) -> io::Result<Cors> {
+        let cors = warp::cors()
+            .allow_methods(vec!["POST"])
+            .allow_headers(vec![AUTHORIZATION, CONTENT_TYPE]);
+
+        if origins.iter().any(|origin| origin == "*") {
+            return Ok(cors.allow_any_origin());
+        }
+
+        for origin in origins {
+            let valid = origin.parse::<Uri>().map_or(false, |uri| {
+                uri.scheme().is_some() && uri.authority().is_some() && !origin.ends_with('/')
+            });
+            if !valid {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid CORS origin: {}", origin),
+                ));
+            }
+        }
+
+        Ok(cors.allow_origins(origins.iter().map(String::as_str)))
+    }
+}
+
+/// Passes the requests of all connections of an [`RpcServer`] to its dispatcher.
+struct RpcHandler<D: Dispatcher> {
+    access: RpcAccess,
+    dispatcher: Mutex<D>,
+    next_id: AtomicU64,
+}
+
+impl<D: Dispatcher> RpcHandler<D> {
+    /// Handles a single request or a batch of requests. Returns None if there is nothing to
+    /// respond, i.e. if all requests were notifications.
+    async fn handle_raw_request(
+        &self,
+        request: &[u8],
+        tx: Option<&mpsc::Sender<Message>>,
+    ) -> Option<Vec<u8>> {
+        let response = match serde_json::from_slice::<Value>(request) {
+            Ok(Value::Array(requests)) => {
+                let mut responses = vec![];
+                for request in requests {
+                    if let Some(response) = self.handle_request(request, tx).await {
+                        responses.push(response);
+                    }
+                }
+
+                if responses.is_empty() {
+                    return None;
+                }
+                Value::Array(responses)
+            }
+            Ok(request) => self.handle_request(request, tx).await?,
+            Err(e) => error_object(Value::Null, -32700, "Parse error", &e),
+        };
+
+        Some(serde_json::to_vec(&response).expect("Failed to serialize JSON-RPC response"))
+    }
+
+    async fn handle_request(
+        &self,
+        request: Value,
+        tx: Option<&mpsc::Sender<Message>>,
+    ) -> Option<Value> {
+        let request_id = request.get("id").cloned().unwrap_or(Value::Null);
+
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) => request,
+            Err(e) => return Some(error_object(request_id, -32600, "Invalid Request", &e)),
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .dispatcher
+            .lock()
+            .await
+            .dispatch(request, tx, id)
+            .await?;
+
+        Some(serde_json::to_value(response).expect("Failed to serialize JSON-RPC response"))
+    }
+
+    /// Handles the requests of a websocket connection until it is closed. The notifications of
+    /// subscriptions are sent over the same connection.
+    async fn handle_websocket(self: Arc<Self>, websocket: WebSocket) {
+        let (mut ws_tx, mut ws_rx) = websocket.split();
+        let (tx, mut rx) = mpsc::channel::<Message>(16);
+
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                if let Err(e) = ws_tx.send(message).await {
+                    log::debug!("Failed to send RPC websocket message: {}", e);
+                    break;
+                }
+            }
+        });
+
+        while let Some(message) = ws_rx.next().await {
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => {
+                    log::debug!("RPC websocket connection failed: {}", e);
+                    break;
+                }
+            };
+
+            if message.is_close() {
+                break;
+            }
+            if !message.is_text() && !message.is_binary() {
+                continue;
+            }
+
+            if let Some(response) = self.handle_raw_request(message.as_bytes(), Some(&tx)).await {
+                let response = String::from_utf8(response).expect("JSON is valid UTF-8");
+                if tx.clone().send(Message::text(response)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// The rejection of a request without valid credentials.
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+fn json_response(data: Vec<u8>) -> Response<Body> {
+    let mut response = Response::new(Body::from(data));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn error_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+fn error_object(id: Value, code: i64, message: &str, error: &serde_json::Error) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": code,
+            "message": message,
+            "data": error.to_string(),
+        },
+        "id": id,
+    })
+}
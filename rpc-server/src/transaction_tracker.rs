@@ -0,0 +1,368 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use futures::stream::StreamExt;
+use parking_lot::RwLock;
+
+use beserial::{Deserialize, Serialize};
+use nimiq_blockchain::{AbstractBlockchain, BlockchainEvent};
+use nimiq_consensus::ConsensusProxy;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Address, KeyPair};
+use nimiq_mempool::ReturnCode;
+use nimiq_network_libp2p::Network;
+use nimiq_primitives::account::AccountType;
+use nimiq_rpc_interface::types::TransactionStatus;
+use nimiq_transaction::{SignatureProof, Transaction, TransactionFlags};
+
+use crate::wallets::UnlockedWallets;
+
+/// Follows the transactions submitted over RPC until their inclusion in a block is final. A single
+/// tracker is shared by all RPC endpoints.
+///
+/// A transaction is only confirmed once the block including it is finalized by a macro block,
+/// since a rebranch might still revert micro blocks. A transaction that expires before being
+/// included is re-signed with a new validity start height and resubmitted, if it is signed by a
+/// wallet that is unlocked on the endpoint it was submitted to. It is only resubmitted once its expiry is finalized as well, such that
+/// it can never be executed twice.
+///
+/// The tracked transactions are only kept in memory. When the node restarts, they are forgotten
+/// and expired transactions aren't resubmitted anymore.
+pub struct TransactionTracker {
+    consensus: ConsensusProxy<Network>,
+    state: RwLock<TrackerState>,
+}
+
+/// A transaction that hasn't been included in a block yet.
+struct PendingTransaction {
+    transaction: Transaction,
+    /// The unlocked wallets of the endpoint that the transaction was submitted to.
+    unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    /// The transactions that haven't been included in a block yet.
+    pending: HashMap<Blake2bHash, PendingTransaction>,
+    /// The final status of transactions that aren't pending anymore.
+    finished: HashMap<Blake2bHash, TransactionStatus>,
+    /// The order in which transactions finished, to forget the oldest ones first.
+    finished_order: VecDeque<Blake2bHash>,
+}
+
+impl TransactionTracker {
+    /// The number of finished transactions whose status is remembered.
+    const MAX_FINISHED: usize = 100_000;
+
+    /// Creates the tracker and spawns the task that checks the pending transactions whenever the
+    /// blockchain changes.
+    pub fn new(consensus: ConsensusProxy<Network>) -> Arc<Self> {
+        let tracker = Arc::new(TransactionTracker {
+            consensus,
+            state: RwLock::new(TrackerState::default()),
+        });
+
+        let mut events = tracker.consensus.blockchain.notifier.write().as_stream();
+        let weak = Arc::downgrade(&tracker);
+        tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let tracker = match weak.upgrade() {
+                    Some(tracker) => tracker,
+                    None => break,
+                };
+
+                match event {
+                    // Only macro blocks finalize the inclusion or the expiry of transactions. A
+                    // rebranch doesn't revert macro blocks, but the adopted blocks can include one.
+                    BlockchainEvent::Finalized(_)
+                    | BlockchainEvent::EpochFinalized(_)
+                    | BlockchainEvent::Rebranched(_, _) => tracker.check_pending().await,
+                    BlockchainEvent::Extended(_) => {}
+                }
+            }
+        });
+
+        tracker
+    }
+
+    /// Starts following a transaction that was accepted by the mempool. If it expires, it is
+    /// re-signed with the given unlocked wallets, which belong to the endpoint that it was
+    /// submitted to.
+    pub fn track(
+        &self,
+        transaction: Transaction,
+        unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+    ) {
+        self.state.write().pending.insert(
+            transaction.hash(),
+            PendingTransaction {
+                transaction,
+                unlocked_wallets,
+            },
+        );
+    }
+
+    pub fn status(&self, hash: &Blake2bHash) -> Option<TransactionStatus> {
+        let state = self.state.read();
+        match state.pending.get(hash) {
+            Some(pending) => Some(TransactionStatus::Pending {
+                validity_start_height: pending.transaction.validity_start_height,
+            }),
+            None => state.finished.get(hash).cloned(),
+        }
+    }
+
+    /// Returns the hashes of the pending transactions, optionally only those sent from `sender`.
+    pub fn pending(&self, sender: Option<&Address>) -> Vec<Blake2bHash> {
+        self.state
+            .read()
+            .pending
+            .iter()
+            .filter(|(_, pending)| {
+                sender.map_or(true, |sender| &pending.transaction.sender == sender)
+            })
+            .map(|(hash, _)| hash.clone())
+            .collect()
+    }
+
+    async fn check_pending(&self) {
+        let blockchain = &self.consensus.blockchain;
+        let finalized_block_number = blockchain.macro_head().header.block_number;
+        let head_block_number = blockchain.block_number();
+
+        let pending: Vec<(
+            Blake2bHash,
+            Transaction,
+            Option<Arc<RwLock<UnlockedWallets>>>,
+        )> = self
+            .state
+            .read()
+            .pending
+            .iter()
+            .map(|(hash, pending)| {
+                (
+                    hash.clone(),
+                    pending.transaction.clone(),
+                    pending.unlocked_wallets.clone(),
+                )
+            })
+            .collect();
+
+        for (hash, transaction, unlocked_wallets) in pending {
+            let included_at = blockchain
+                .history_store
+                .get_ext_tx_by_hash(&hash, None)
+                .first()
+                .map(|ext_tx| ext_tx.block_number);
+
+            match Outcome::of(&transaction, included_at, finalized_block_number) {
+                Outcome::Pending => {}
+                Outcome::Confirmed(block_number) => {
+                    self.finish(hash, TransactionStatus::Confirmed { block_number })
+                }
+                Outcome::Expired => {
+                    let status = match self
+                        .resubmit(transaction, unlocked_wallets, head_block_number)
+                        .await
+                    {
+                        Some(new_hash) => TransactionStatus::Resubmitted { hash: new_hash },
+                        None => TransactionStatus::Expired,
+                    };
+                    self.finish(hash, status);
+                }
+            }
+        }
+    }
+
+    /// Re-signs the expired `transaction` to be valid from `validity_start_height` on and sends it.
+    /// Returns the hash of the new transaction if it was accepted.
+    async fn resubmit(
+        &self,
+        transaction: Transaction,
+        unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+        validity_start_height: u32,
+    ) -> Option<Blake2bHash> {
+        if !can_resign(&transaction) {
+            return None;
+        }
+        let key_pair = unlocked_wallets
+            .as_ref()?
+            .read()
+            .get(&transaction.sender)
+            .map(|wallet| wallet.key_pair.clone())?;
+
+        let transaction = resign(transaction, &key_pair, validity_start_height);
+        let hash = transaction.hash::<Blake2bHash>();
+        match self.consensus.send_transaction(transaction.clone()).await {
+            Ok(ReturnCode::Accepted) => {
+                log::info!("Resubmitted expired transaction as {}", hash);
+                self.track(transaction, unlocked_wallets);
+                Some(hash)
+            }
+            Ok(return_code) => {
+                log::warn!("Resubmitted transaction was rejected: {:?}", return_code);
+                None
+            }
+            Err(e) => {
+                log::warn!("Failed to resubmit transaction: {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn finish(&self, hash: Blake2bHash, status: TransactionStatus) {
+        let mut state = self.state.write();
+        state.pending.remove(&hash);
+        state.finished.insert(hash.clone(), status);
+        state.finished_order.push_back(hash);
+
+        while state.finished_order.len() > Self::MAX_FINISHED {
+            if let Some(hash) = state.finished_order.pop_front() {
+                state.finished.remove(&hash);
+            }
+        }
+    }
+}
+
+/// What happened to a pending transaction, according to the finalized part of the chain.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// The transaction might still be included, or its inclusion isn't final yet.
+    Pending,
+    /// The transaction is included in the finalized block with the given number.
+    Confirmed(u32),
+    /// The transaction can't be included in the finalized chain anymore.
+    Expired,
+}
+
+impl Outcome {
+    /// Returns the outcome of `transaction`, given the number of the block that includes it on
+    /// the main chain, if any, and the number of the last finalized block.
+    fn of(
+        transaction: &Transaction,
+        included_at: Option<u32>,
+        finalized_block_number: u32,
+    ) -> Self {
+        match included_at {
+            Some(block_number) if block_number <= finalized_block_number => {
+                Outcome::Confirmed(block_number)
+            }
+            Some(_) => Outcome::Pending,
+            None if finalized_block_number >= transaction.validity_start_height
+                && !transaction.is_valid_at(finalized_block_number) =>
+            {
+                Outcome::Expired
+            }
+            None => Outcome::Pending,
+        }
+    }
+}
+
+/// Returns whether `transaction` can be re-signed by the key pair of its sender alone.
+/// Signalling transactions are also signed by a validator key and transactions from contracts
+/// have other proofs, so they can't be re-signed.
+fn can_resign(transaction: &Transaction) -> bool {
+    if transaction.sender_type != AccountType::Basic
+        || transaction.flags.contains(TransactionFlags::SIGNALLING)
+    {
+        return false;
+    }
+
+    SignatureProof::deserialize_from_vec(&transaction.proof)
+        .map_or(false, |proof| proof.is_signed_by(&transaction.sender))
+}
+
+/// Re-signs `transaction` with `key_pair` to be valid from `validity_start_height` on.
+fn resign(
+    mut transaction: Transaction,
+    key_pair: &KeyPair,
+    validity_start_height: u32,
+) -> Transaction {
+    transaction.validity_start_height = validity_start_height;
+    let signature = key_pair.sign(transaction.serialize_content().as_slice());
+    transaction.proof = SignatureProof::from(key_pair.public, signature).serialize_to_vec();
+    transaction
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_keys::SecureGenerate;
+    use nimiq_primitives::coin::Coin;
+    use nimiq_primitives::networks::NetworkId;
+    use nimiq_primitives::policy;
+
+    use super::*;
+
+    fn signed_transaction(key_pair: &KeyPair, validity_start_height: u32) -> Transaction {
+        let transaction = Transaction::new_basic(
+            Address::from(&key_pair.public),
+            Address::from([1u8; Address::SIZE]),
+            Coin::from_u64_unchecked(100),
+            Coin::from_u64_unchecked(1),
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        );
+        resign(transaction, key_pair, validity_start_height)
+    }
+
+    #[test]
+    fn it_confirms_finalized_transactions() {
+        let transaction = signed_transaction(&KeyPair::generate_default_csprng(), 10);
+
+        assert_eq!(
+            Outcome::of(&transaction, Some(12), 32),
+            Outcome::Confirmed(12)
+        );
+        assert_eq!(
+            Outcome::of(&transaction, Some(32), 32),
+            Outcome::Confirmed(32)
+        );
+
+        // The including block might still be reverted by a rebranch.
+        assert_eq!(Outcome::of(&transaction, Some(33), 32), Outcome::Pending);
+    }
+
+    #[test]
+    fn it_expires_transactions_once_the_expiry_is_finalized() {
+        let transaction = signed_transaction(&KeyPair::generate_default_csprng(), 10);
+        let expiry = 10 + policy::TRANSACTION_VALIDITY_WINDOW;
+
+        assert_eq!(Outcome::of(&transaction, None, 0), Outcome::Pending);
+        assert_eq!(
+            Outcome::of(&transaction, None, expiry - 1),
+            Outcome::Pending
+        );
+        assert_eq!(Outcome::of(&transaction, None, expiry), Outcome::Expired);
+
+        // A transaction that is included isn't expired, even if its inclusion isn't final yet.
+        assert_eq!(
+            Outcome::of(&transaction, Some(expiry - 1), expiry - 2),
+            Outcome::Pending
+        );
+    }
+
+    #[test]
+    fn it_resubmits_only_transactions_signed_by_the_sender() {
+        let key_pair = KeyPair::generate_default_csprng();
+        let transaction = signed_transaction(&key_pair, 10);
+        assert!(can_resign(&transaction));
+
+        let resigned = resign(transaction.clone(), &key_pair, 100);
+        assert_eq!(resigned.validity_start_height, 100);
+        assert_ne!(
+            resigned.hash::<Blake2bHash>(),
+            transaction.hash::<Blake2bHash>()
+        );
+        assert_eq!(resigned.verify(NetworkId::UnitAlbatross), Ok(()));
+
+        // Transactions signed by another key can't be re-signed by the sender.
+        let mut foreign = transaction.clone();
+        foreign.proof = resign(transaction.clone(), &KeyPair::generate_default_csprng(), 10).proof;
+        assert!(!can_resign(&foreign));
+
+        // Signalling transactions also carry a proof of another key.
+        let mut signalling = transaction;
+        signalling.flags = TransactionFlags::SIGNALLING;
+        assert!(!can_resign(&signalling));
+    }
+}
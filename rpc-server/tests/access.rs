@@ -0,0 +1,81 @@
+use hyper::header::HeaderValue;
+
+use nimiq_jsonrpc_core::Credentials;
+use nimiq_rpc_server::ip_network::IpNetwork;
+use nimiq_rpc_server::server::RpcAccess;
+
+fn basic(username: &str, password: &str) -> HeaderValue {
+    let encoded = base64::encode(format!("{}:{}", username, password));
+    HeaderValue::from_str(&format!("Basic {}", encoded)).unwrap()
+}
+
+fn bearer(token: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!("Bearer {}", token)).unwrap()
+}
+
+fn credentials() -> Credentials {
+    Credentials {
+        username: "super".to_owned(),
+        password: "secret".to_owned(),
+    }
+}
+
+#[test]
+fn it_allows_everything_without_rules() {
+    let access = RpcAccess::default();
+
+    assert!(access.allows_ip(&"1.2.3.4".parse().unwrap()));
+    assert!(access.allows_ip(&"::1".parse().unwrap()));
+    assert!(access.authorizes(None));
+    assert!(access.authorizes(Some(&bearer("anything"))));
+}
+
+#[test]
+fn it_checks_ips_against_networks() {
+    let access = RpcAccess::new(
+        Some(vec![
+            "127.0.0.1".parse::<IpNetwork>().unwrap(),
+            "10.0.0.0/8".parse::<IpNetwork>().unwrap(),
+        ]),
+        None,
+        None,
+    );
+
+    assert!(access.allows_ip(&"127.0.0.1".parse().unwrap()));
+    assert!(access.allows_ip(&"10.255.0.1".parse().unwrap()));
+    assert!(!access.allows_ip(&"127.0.0.2".parse().unwrap()));
+    assert!(!access.allows_ip(&"11.0.0.1".parse().unwrap()));
+    assert!(!access.allows_ip(&"::1".parse().unwrap()));
+}
+
+#[test]
+fn it_checks_basic_auth() {
+    let access = RpcAccess::new(None, Some(credentials()), None);
+
+    assert!(access.authorizes(Some(&basic("super", "secret"))));
+    assert!(!access.authorizes(Some(&basic("super", "wrong"))));
+    assert!(!access.authorizes(Some(&basic("other", "secret"))));
+    assert!(!access.authorizes(Some(&bearer("secret"))));
+    assert!(!access.authorizes(None));
+}
+
+#[test]
+fn it_checks_bearer_tokens() {
+    let access = RpcAccess::new(None, None, Some("token".to_owned()));
+
+    assert!(access.authorizes(Some(&bearer("token"))));
+    assert!(!access.authorizes(Some(&bearer("other"))));
+    assert!(!access.authorizes(Some(&HeaderValue::from_static("token"))));
+    assert!(!access.authorizes(Some(&basic("super", "secret"))));
+    assert!(!access.authorizes(None));
+}
+
+#[test]
+fn it_accepts_either_credentials_or_token() {
+    let access = RpcAccess::new(None, Some(credentials()), Some("token".to_owned()));
+
+    assert!(access.authorizes(Some(&basic("super", "secret"))));
+    assert!(access.authorizes(Some(&bearer("token"))));
+    assert!(!access.authorizes(Some(&bearer("secret"))));
+    assert!(!access.authorizes(None));
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use futures::executor::block_on;
+
+use nimiq_database::volatile::VolatileEnvironment;
+use nimiq_rpc_interface::wallet::WalletInterface;
+use nimiq_rpc_server::dispatchers::WalletDispatcher;
+use nimiq_rpc_server::Error;
+use nimiq_wallet::WalletStore;
+
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+fn wallet_dispatcher() -> WalletDispatcher {
+    let env = VolatileEnvironment::new(10).unwrap();
+    WalletDispatcher::new(Arc::new(WalletStore::new(env)), None)
+}
+
+#[test]
+fn it_rejects_large_gap_limits() {
+    let mut dispatcher = wallet_dispatcher();
+
+    let result =
+        block_on(dispatcher.import_mnemonic(MNEMONIC.to_owned(), None, Some(u32::MAX), None));
+    assert!(matches!(result, Err(Error::GapLimitTooLarge(_))));
+
+    let addresses =
+        block_on(dispatcher.import_mnemonic(MNEMONIC.to_owned(), None, Some(5), None)).unwrap();
+    assert_eq!(addresses.len(), 1);
+}
+
+#[test]
+fn it_derives_other_accounts_with_a_bip39_password() {
+    let mut dispatcher = wallet_dispatcher();
+
+    let addresses =
+        block_on(dispatcher.import_mnemonic(MNEMONIC.to_owned(), None, None, None)).unwrap();
+    let protected_addresses = block_on(dispatcher.import_mnemonic(
+        MNEMONIC.to_owned(),
+        None,
+        None,
+        Some("password".to_owned()),
+    ))
+    .unwrap();
+
+    assert_eq!(addresses.len(), 1);
+    assert_eq!(protected_addresses.len(), 1);
+    assert_ne!(addresses, protected_addresses);
+}
@@ -14,9 +14,12 @@ use keys::{Address, KeyPair};
 use primitives::account::{AccountType, ValidatorId};
 use primitives::coin::Coin;
 use primitives::networks::NetworkId;
-use transaction::Transaction;
+use transaction::account::htlc_contract::{AnyHash, HashAlgorithm};
+use transaction::{SignatureProof, Transaction};
 
+use crate::proof::htlc_contract::HtlcProofBuilder;
 pub use crate::proof::TransactionProofBuilder;
+use crate::recipient::vesting_contract::VestingRecipientBuilderError;
 pub use crate::recipient::Recipient;
 
 pub mod proof;
@@ -1042,4 +1045,285 @@ impl TransactionBuilder {
             _ => unreachable!(),
         }
     }
+
+    /// Creates a transaction that creates a new HTLC contract.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the transaction. The funds of the contract are sent
+    ///                             from the account belonging to this key pair.
+    ///  - `htlc_sender`:           The address that can resolve the contract after the timeout.
+    ///  - `htlc_recipient`:        The address that can redeem the funds by presenting the pre-image.
+    ///  - `hash_root`:             The result of hashing the pre-image `hash_count` times.
+    ///  - `hash_count`:            The number of times the pre-image has been hashed.
+    ///  - `hash_algorithm`:        The hash algorithm used to compute `hash_root`.
+    ///  - `timeout`:               Block height from which the `htlc_sender` can resolve the contract.
+    ///  - `value`:                 The funds locked in the contract.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction (signed using `key_pair`).
+    ///
+    pub fn new_create_htlc(
+        key_pair: &KeyPair,
+        htlc_sender: Address,
+        htlc_recipient: Address,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        timeout: u64,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut recipient = Recipient::new_htlc_builder();
+        recipient
+            .with_sender(htlc_sender)
+            .with_recipient(htlc_recipient)
+            .with_hash(hash_root, hash_count, hash_algorithm)
+            .with_timeout(timeout);
+
+        let mut builder = Self::new();
+        builder
+            .with_sender(Address::from(key_pair))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate().unwrap();
+        match proof_builder {
+            TransactionProofBuilder::Basic(mut builder) => {
+                builder.sign_with_key_pair(&key_pair);
+                builder.generate().unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a transaction that redeems funds from the HTLC contract at `contract_address` by
+    /// presenting the `pre_image`. The transaction is signed with the `key_pair` of the HTLC recipient.
+    ///
+    /// The `hash_root`, `hash_count` and `hash_algorithm` must match the contract. If `pre_image`
+    /// needs to be hashed k < `hash_count` times to yield the `hash_root`, only 1/k of the funds
+    /// can be redeemed.
+    pub fn new_redeem_htlc_regular(
+        key_pair: &KeyPair,
+        contract_address: Address,
+        recipient: Address,
+        pre_image: AnyHash,
+        hash_root: AnyHash,
+        hash_count: u8,
+        hash_algorithm: HashAlgorithm,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut builder = Self::htlc_proof_builder(
+            contract_address,
+            recipient,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        );
+        let signature = builder.signature_with_key_pair(key_pair);
+        builder.regular_transfer(hash_algorithm, pre_image, hash_count, hash_root, signature);
+        builder.generate().unwrap()
+    }
+
+    /// Creates a transaction that resolves the HTLC contract at `contract_address` after its
+    /// timeout. The transaction is signed with the `key_pair` of the HTLC sender.
+    pub fn new_redeem_htlc_timeout(
+        key_pair: &KeyPair,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut builder = Self::htlc_proof_builder(
+            contract_address,
+            recipient,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        );
+        let signature = builder.signature_with_key_pair(key_pair);
+        builder.timeout_resolve(signature);
+        builder.generate().unwrap()
+    }
+
+    /// Creates a transaction that resolves the HTLC contract at `contract_address` before its
+    /// timeout. This requires the signatures of both the HTLC sender and the HTLC recipient, which
+    /// can be created with [`sign_redeem_htlc_early`] using the same transaction parameters.
+    ///
+    /// [`sign_redeem_htlc_early`]: struct.TransactionBuilder.html#method.sign_redeem_htlc_early
+    pub fn new_redeem_htlc_early(
+        contract_address: Address,
+        recipient: Address,
+        htlc_sender_signature: SignatureProof,
+        htlc_recipient_signature: SignatureProof,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut builder = Self::htlc_proof_builder(
+            contract_address,
+            recipient,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        );
+        builder.early_resolve(htlc_sender_signature, htlc_recipient_signature);
+        builder.generate().unwrap()
+    }
+
+    /// Signs a transaction that resolves the HTLC contract at `contract_address` before its
+    /// timeout with the given `key_pair`. The resulting signature can be used in
+    /// [`new_redeem_htlc_early`].
+    ///
+    /// [`new_redeem_htlc_early`]: struct.TransactionBuilder.html#method.new_redeem_htlc_early
+    pub fn sign_redeem_htlc_early(
+        key_pair: &KeyPair,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> SignatureProof {
+        Self::htlc_proof_builder(
+            contract_address,
+            recipient,
+            value,
+            fee,
+            validity_start_height,
+            network_id,
+        )
+        .signature_with_key_pair(key_pair)
+    }
+
+    /// Creates a transaction that creates a new vesting contract.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the transaction. The funds of the contract are sent
+    ///                             from the account belonging to this key pair.
+    ///  - `owner`:                 The address that owns the funds in the contract.
+    ///  - `start_time`:            The start of the release schedule.
+    ///  - `time_step`:             The distance between two releases of funds.
+    ///  - `num_steps`:             The number of releases. `value` is split evenly between them.
+    ///  - `value`:                 The funds locked in the contract.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction (signed using `key_pair`), or
+    /// [`VestingRecipientBuilderError::InvalidSteps`] if `num_steps` or `time_step` is zero.
+    ///
+    /// [`VestingRecipientBuilderError::InvalidSteps`]: recipient/vesting_contract/enum.VestingRecipientBuilderError.html#variant.InvalidSteps
+    pub fn new_create_vesting(
+        key_pair: &KeyPair,
+        owner: Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, VestingRecipientBuilderError> {
+        if num_steps == 0 || time_step == 0 {
+            return Err(VestingRecipientBuilderError::InvalidSteps);
+        }
+
+        let mut recipient = Recipient::new_vesting_builder(owner);
+        recipient.with_steps(value, start_time, time_step, num_steps);
+
+        let mut builder = Self::new();
+        builder
+            .with_sender(Address::from(key_pair))
+            .with_recipient(recipient.generate()?)
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate().unwrap();
+        match proof_builder {
+            TransactionProofBuilder::Basic(mut builder) => {
+                builder.sign_with_key_pair(&key_pair);
+                Ok(builder.generate().unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a transaction that withdraws released funds from the vesting contract at
+    /// `contract_address` to a basic `recipient`. The transaction is signed with the `key_pair`
+    /// of the contract owner.
+    pub fn new_redeem_vesting(
+        key_pair: &KeyPair,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut builder = Self::new();
+        builder
+            .with_sender(contract_address)
+            .with_sender_type(AccountType::Vesting)
+            .with_recipient(Recipient::new_basic(recipient))
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate().unwrap();
+        match proof_builder {
+            TransactionProofBuilder::Vesting(mut builder) => {
+                builder.sign_with_key_pair(&key_pair);
+                builder.generate().unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates the proof builder for a transaction from the HTLC contract at `contract_address`
+    /// to a basic `recipient`.
+    fn htlc_proof_builder(
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> HtlcProofBuilder {
+        let mut builder = Self::new();
+        builder
+            .with_sender(contract_address)
+            .with_sender_type(AccountType::HTLC)
+            .with_recipient(Recipient::new_basic(recipient))
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        builder.generate().unwrap().unwrap_htlc()
+    }
 }
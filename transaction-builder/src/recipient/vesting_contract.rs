@@ -47,6 +47,10 @@ pub enum VestingRecipientBuilderError {
     /// [`with_total_amount`]: struct.VestingRecipientBuilder.html#method.with_total_amount
     #[error("The vesting total amount is missing.")]
     NoTotalAmount,
+    /// A release schedule created from a number of steps needs at least one step and a non-zero
+    /// distance between the steps.
+    #[error("The vesting schedule needs at least one step and a non-zero step distance.")]
+    InvalidSteps,
 }
 
 /// A `VestingRecipientBuilder` can be used to create new vesting contracts.
@@ -111,6 +115,10 @@ impl VestingRecipientBuilder {
 
     /// This convenience function allows to quickly create a release schedule of `num_steps`
     /// payouts starting at `start_time + time_step`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_steps` is zero.
     pub fn with_steps(
         &mut self,
         total_amount: Coin,
@@ -4,6 +4,7 @@ use beserial::{Deserialize, Serialize};
 use nimiq_account::AccountType;
 use nimiq_hash::{Blake2bHash, Blake2bHasher, HashOutput, Hasher};
 use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_primitives::coin::Coin;
 use nimiq_primitives::networks::NetworkId;
 use nimiq_transaction::account::htlc_contract::{AnyHash, HashAlgorithm, ProofType};
 use nimiq_transaction::{SignatureProof, Transaction};
@@ -230,3 +231,144 @@ fn it_can_create_timeout_resolve() {
 
     assert_eq!(tx2, tx);
 }
+
+#[test]
+fn it_can_create_htlc_with_key_pair() {
+    let (_, _, hash_root, sender_key_pair, _, _, _) = prepare_outgoing_transaction();
+    let sender = Address::from(&sender_key_pair);
+
+    let mut htlc_builder = Recipient::new_htlc_builder();
+    htlc_builder
+        .with_sender(sender.clone())
+        .with_recipient(Address::from([1u8; 20]))
+        .with_hash(hash_root.clone(), 2, HashAlgorithm::Blake2b)
+        .with_timeout(1000);
+
+    let mut builder = TransactionBuilder::new();
+    builder
+        .with_sender(sender.clone())
+        .with_recipient(htlc_builder.generate().unwrap())
+        .with_value(100.try_into().unwrap())
+        .with_fee(1.try_into().unwrap())
+        .with_validity_start_height(1)
+        .with_network_id(NetworkId::Dummy);
+    let mut proof_builder = builder
+        .generate()
+        .expect("Builder should be able to create transaction")
+        .unwrap_basic();
+    proof_builder.sign_with_key_pair(&sender_key_pair);
+    let tx = proof_builder
+        .generate()
+        .expect("Builder should be able to create proof");
+
+    let tx2 = TransactionBuilder::new_create_htlc(
+        &sender_key_pair,
+        sender,
+        Address::from([1u8; 20]),
+        hash_root,
+        2,
+        HashAlgorithm::Blake2b,
+        1000,
+        Coin::from_u64_unchecked(100),
+        Coin::from_u64_unchecked(1),
+        1,
+        NetworkId::Dummy,
+    );
+
+    assert_eq!(tx2, tx);
+}
+
+#[test]
+#[allow(unused_must_use)]
+fn it_can_redeem_htlc_with_key_pairs() {
+    let (
+        tx,
+        pre_image,
+        hash_root,
+        sender_key_pair,
+        sender_signature_proof,
+        recipient_key_pair,
+        recipient_signature_proof,
+    ) = prepare_outgoing_transaction();
+    let contract_address = Address::from([0u8; 20]);
+    let recipient = Address::from([1u8; 20]);
+    let value = Coin::from_u64_unchecked(1000);
+
+    // Regular transfer
+    let mut proof =
+        Vec::with_capacity(3 + 2 * AnyHash::SIZE + recipient_signature_proof.serialized_size());
+    Serialize::serialize(&ProofType::RegularTransfer, &mut proof);
+    Serialize::serialize(&HashAlgorithm::Blake2b, &mut proof);
+    Serialize::serialize(&1u8, &mut proof);
+    Serialize::serialize(&hash_root, &mut proof);
+    Serialize::serialize(&pre_image, &mut proof);
+    Serialize::serialize(&recipient_signature_proof, &mut proof);
+    let mut expected = tx.clone();
+    expected.proof = proof;
+
+    let tx2 = TransactionBuilder::new_redeem_htlc_regular(
+        &recipient_key_pair,
+        contract_address.clone(),
+        recipient.clone(),
+        pre_image,
+        hash_root,
+        1,
+        HashAlgorithm::Blake2b,
+        value,
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+    assert_eq!(tx2, expected);
+
+    // Timeout resolve
+    let mut proof = Vec::with_capacity(1 + sender_signature_proof.serialized_size());
+    Serialize::serialize(&ProofType::TimeoutResolve, &mut proof);
+    Serialize::serialize(&sender_signature_proof, &mut proof);
+    let mut expected = tx.clone();
+    expected.proof = proof;
+
+    let tx2 = TransactionBuilder::new_redeem_htlc_timeout(
+        &sender_key_pair,
+        contract_address.clone(),
+        recipient.clone(),
+        value,
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+    assert_eq!(tx2, expected);
+
+    // Early resolve, with signatures created separately
+    let mut proof = Vec::with_capacity(
+        1 + recipient_signature_proof.serialized_size() + sender_signature_proof.serialized_size(),
+    );
+    Serialize::serialize(&ProofType::EarlyResolve, &mut proof);
+    Serialize::serialize(&recipient_signature_proof, &mut proof);
+    Serialize::serialize(&sender_signature_proof, &mut proof);
+    let mut expected = tx;
+    expected.proof = proof;
+
+    let sign = |key_pair: &KeyPair| {
+        TransactionBuilder::sign_redeem_htlc_early(
+            key_pair,
+            contract_address.clone(),
+            recipient.clone(),
+            value,
+            Coin::ZERO,
+            1,
+            NetworkId::Dummy,
+        )
+    };
+    let tx2 = TransactionBuilder::new_redeem_htlc_early(
+        contract_address.clone(),
+        recipient.clone(),
+        sign(&sender_key_pair),
+        sign(&recipient_key_pair),
+        value,
+        Coin::ZERO,
+        1,
+        NetworkId::Dummy,
+    );
+    assert_eq!(tx2, expected);
+}
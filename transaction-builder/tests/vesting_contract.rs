@@ -6,6 +6,7 @@ use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::coin::Coin;
 use nimiq_primitives::networks::NetworkId;
 use nimiq_transaction::{SignatureProof, Transaction};
+use nimiq_transaction_builder::recipient::vesting_contract::VestingRecipientBuilderError;
 use nimiq_transaction_builder::{Recipient, TransactionBuilder};
 
 #[test]
@@ -144,3 +145,120 @@ fn it_can_create_outgoing_transactions() {
     proof_builder.sign_with_key_pair(&key_pair);
     assert_eq!(proof_builder.generate().unwrap(), tx);
 }
+
+#[test]
+fn it_can_create_vesting_with_key_pair() {
+    let sender_priv_key: PrivateKey = Deserialize::deserialize_from_vec(
+        &hex::decode("9d5bd02379e7e45cf515c788048f5cf3c454ffabd3e83bd1d7667716c325c3c0").unwrap(),
+    )
+    .unwrap();
+    let key_pair = KeyPair::from(sender_priv_key);
+    let owner = Address::from([1u8; 20]);
+
+    // 1000 coins released in 4 steps of 250 coins every 100 time units, starting at 50.
+    let mut recipient = Recipient::new_vesting_builder(owner.clone());
+    recipient
+        .with_start_time(50)
+        .with_time_step(100)
+        .with_step_amount(250.try_into().unwrap())
+        .with_total_amount(1000.try_into().unwrap());
+
+    let mut builder = TransactionBuilder::new();
+    builder
+        .with_sender(Address::from(&key_pair))
+        .with_recipient(recipient.generate().unwrap())
+        .with_value(1000.try_into().unwrap())
+        .with_fee(2.try_into().unwrap())
+        .with_validity_start_height(1)
+        .with_network_id(NetworkId::Dummy);
+    let mut proof_builder = builder
+        .generate()
+        .expect("Builder should be able to create transaction")
+        .unwrap_basic();
+    proof_builder.sign_with_key_pair(&key_pair);
+    let tx = proof_builder
+        .generate()
+        .expect("Builder should be able to create proof");
+
+    let tx2 = TransactionBuilder::new_create_vesting(
+        &key_pair,
+        owner.clone(),
+        50,
+        100,
+        4,
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(2),
+        1,
+        NetworkId::Dummy,
+    )
+    .expect("Builder should be able to create transaction");
+    assert_eq!(tx2, tx);
+
+    // A release schedule without steps is rejected.
+    let result = TransactionBuilder::new_create_vesting(
+        &key_pair,
+        owner.clone(),
+        50,
+        100,
+        0,
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(2),
+        1,
+        NetworkId::Dummy,
+    );
+    assert!(matches!(
+        result,
+        Err(VestingRecipientBuilderError::InvalidSteps)
+    ));
+
+    // A release schedule without a distance between the steps is rejected.
+    let result = TransactionBuilder::new_create_vesting(
+        &key_pair,
+        owner,
+        50,
+        0,
+        4,
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(2),
+        1,
+        NetworkId::Dummy,
+    );
+    assert!(matches!(
+        result,
+        Err(VestingRecipientBuilderError::InvalidSteps)
+    ));
+}
+
+#[test]
+fn it_can_redeem_vesting_with_key_pair() {
+    let owner_priv_key: PrivateKey = Deserialize::deserialize_from_vec(
+        &hex::decode("9d5bd02379e7e45cf515c788048f5cf3c454ffabd3e83bd1d7667716c325c3c0").unwrap(),
+    )
+    .unwrap();
+    let key_pair = KeyPair::from(owner_priv_key);
+
+    let mut tx = Transaction::new_basic(
+        Address::from([1u8; 20]),
+        Address::from([2u8; 20]),
+        1.try_into().unwrap(),
+        1000.try_into().unwrap(),
+        1,
+        NetworkId::Dummy,
+    );
+    tx.sender_type = AccountType::Vesting;
+
+    let signature = key_pair.sign(&tx.serialize_content()[..]);
+    let signature_proof = SignatureProof::from(key_pair.public, signature);
+    tx.proof = signature_proof.serialize_to_vec();
+
+    let tx2 = TransactionBuilder::new_redeem_vesting(
+        &key_pair,
+        Address::from([1u8; 20]),
+        Address::from([2u8; 20]),
+        Coin::from_u64_unchecked(1),
+        Coin::from_u64_unchecked(1000),
+        1,
+        NetworkId::Dummy,
+    );
+    assert_eq!(tx2, tx);
+}
@@ -21,6 +21,7 @@ use network_interface::{
     peer::Peer,
 };
 use nimiq_block_production::BlockProducer;
+use nimiq_primitives::account::ValidatorId;
 use nimiq_tendermint::TendermintReturn;
 use nimiq_validator_network::ValidatorNetwork;
 
@@ -42,13 +43,28 @@ impl Topic for ProposalTopic {
     }
 }
 
-enum ValidatorStakingState {
+/// The state of a validator in the staking contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidatorStakingState {
+    /// The validator is active and eligible for slots.
     Active,
+    /// The validator is active, but was parked and needs to be unparked to keep its slots.
     Parked,
+    /// The validator was retired and is not eligible for slots.
     Inactive,
+    /// There is no validator with our signing key in the staking contract.
     NoStake,
 }
 
+/// Counts the micro blocks this validator was supposed to produce since the client was started.
+#[derive(Clone, Debug, Default)]
+pub struct BlockProductionStatistics {
+    /// The number of blocks on the main chain that were produced by us.
+    pub produced_blocks: u64,
+    /// The number of our slots that were skipped by a view change.
+    pub missed_blocks: u64,
+}
+
 struct ActiveEpochState {
     validator_id: u16,
 }
@@ -78,8 +94,9 @@ pub struct Validator<TNetwork: Network, TValidatorNetwork: ValidatorNetwork + 's
     blockchain_event_rx: mpsc::UnboundedReceiver<BlockchainEvent>,
     fork_event_rx: mpsc::UnboundedReceiver<ForkEvent>,
 
-    epoch_state: Option<ActiveEpochState>,
+    epoch_state: Arc<RwLock<Option<ActiveEpochState>>>,
     blockchain_state: BlockchainState,
+    statistics: Arc<RwLock<BlockProductionStatistics>>,
 
     macro_producer: Option<ProduceMacroBlock>,
     macro_state: Option<PersistedMacroState<TValidatorNetwork>>,
@@ -141,8 +158,9 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
             blockchain_event_rx,
             fork_event_rx,
 
-            epoch_state: None,
+            epoch_state: Arc::new(RwLock::new(None)),
             blockchain_state,
+            statistics: Arc::new(RwLock::new(BlockProductionStatistics::default())),
 
             macro_producer: None,
             macro_state,
@@ -177,15 +195,17 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
         // TODO: This code block gets this validators position in the validators struct by searching it
         //  with its public key. This is an insane way of doing this. Just start saving the validator
         //  id in the Validator struct (the one in this crate).
-        self.epoch_state = None;
+        let mut epoch_state = self.epoch_state.write();
+        *epoch_state = None;
         for (i, validator) in validators.iter().enumerate() {
             if validator.public_key.compressed() == &self.signing_key.public_key.compress() {
-                self.epoch_state = Some(ActiveEpochState {
+                *epoch_state = Some(ActiveEpochState {
                     validator_id: i as u16,
                 });
                 break;
             }
         }
+        drop(epoch_state);
 
         let validator_keys: Vec<CompressedPublicKey> = validators
             .iter()
@@ -306,6 +326,7 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
             .get_block(hash, true, None)
             .expect("Head block not found");
         self.blockchain_state.fork_proofs.apply_block(&block);
+        self.count_block(&block, false);
     }
 
     fn on_blockchain_rebranched(
@@ -315,9 +336,56 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
     ) {
         for (_hash, block) in old_chain.iter() {
             self.blockchain_state.fork_proofs.revert_block(block);
+            self.count_block(block, true);
         }
         for (_hash, block) in new_chain.iter() {
             self.blockchain_state.fork_proofs.apply_block(&block);
+            self.count_block(block, false);
+        }
+    }
+
+    /// Updates the block production statistics with a micro block that was added to (or, if
+    /// `revert` is set, removed from) the main chain. Every view up to the view of the block is a
+    /// slot, the last one produced the block and the others were skipped by view changes.
+    fn count_block(&self, block: &Block, revert: bool) {
+        if !self.is_active() || !block.is_micro() {
+            return;
+        }
+
+        let blockchain = &self.consensus.blockchain;
+        let first_view_number = match blockchain.get_block(block.parent_hash(), false, None) {
+            Some(parent) => parent.next_view_number(),
+            None => return,
+        };
+
+        let public_key = self.signing_key.public_key.compress();
+        let mut produced_blocks = 0;
+        let mut missed_blocks = 0;
+
+        for view_number in first_view_number..=block.view_number() {
+            let is_ours = blockchain
+                .get_slot_owner_at(block.block_number(), view_number, None)
+                .map(|(validator, _)| validator.public_key.compressed() == &public_key)
+                .unwrap_or(false);
+
+            if !is_ours {
+                continue;
+            }
+
+            if view_number == block.view_number() {
+                produced_blocks += 1;
+            } else {
+                missed_blocks += 1;
+            }
+        }
+
+        let mut statistics = self.statistics.write();
+        if revert {
+            statistics.produced_blocks = statistics.produced_blocks.saturating_sub(produced_blocks);
+            statistics.missed_blocks = statistics.missed_blocks.saturating_sub(missed_blocks);
+        } else {
+            statistics.produced_blocks += produced_blocks;
+            statistics.missed_blocks += missed_blocks;
         }
     }
 
@@ -434,11 +502,12 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
     }
 
     fn is_active(&self) -> bool {
-        self.epoch_state.is_some()
+        self.epoch_state.read().is_some()
     }
 
     pub fn validator_id(&self) -> u16 {
         self.epoch_state
+            .read()
             .as_ref()
             .expect("Validator not active")
             .validator_id
@@ -447,6 +516,90 @@ impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork>
     pub fn signing_key(&self) -> bls::KeyPair {
         self.signing_key.clone()
     }
+
+    /// Returns a proxy that gives access to the state of this validator, while the validator
+    /// itself is running.
+    pub fn proxy(&self) -> ValidatorProxy<TNetwork> {
+        ValidatorProxy {
+            consensus: self.consensus.clone(),
+            signing_key: self.signing_key.clone(),
+            wallet_key: self.wallet_key.clone(),
+            epoch_state: Arc::clone(&self.epoch_state),
+            statistics: Arc::clone(&self.statistics),
+        }
+    }
+}
+
+/// A handle to a running validator, e.g. for the RPC server.
+pub struct ValidatorProxy<TNetwork: Network> {
+    pub consensus: ConsensusProxy<TNetwork>,
+    pub signing_key: bls::KeyPair,
+    pub wallet_key: Option<keys::KeyPair>,
+    epoch_state: Arc<RwLock<Option<ActiveEpochState>>>,
+    statistics: Arc<RwLock<BlockProductionStatistics>>,
+}
+
+impl<TNetwork: Network> Clone for ValidatorProxy<TNetwork> {
+    fn clone(&self) -> Self {
+        Self {
+            consensus: self.consensus.clone(),
+            signing_key: self.signing_key.clone(),
+            wallet_key: self.wallet_key.clone(),
+            epoch_state: Arc::clone(&self.epoch_state),
+            statistics: Arc::clone(&self.statistics),
+        }
+    }
+}
+
+impl<TNetwork: Network> ValidatorProxy<TNetwork> {
+    /// Returns our slot band in the current epoch, or None if we don't have any slots.
+    pub fn slot_band(&self) -> Option<u16> {
+        self.epoch_state
+            .read()
+            .as_ref()
+            .map(|epoch_state| epoch_state.validator_id)
+    }
+
+    /// Returns the id of our validator in the staking contract and its state. The validator is
+    /// identified by our signing key.
+    pub fn staking_state(&self) -> (Option<ValidatorId>, ValidatorStakingState) {
+        let staking_contract = self.consensus.blockchain.get_staking_contract();
+        let public_key = self.signing_key.public_key.compress();
+
+        if let Some(validator) = staking_contract
+            .active_validators_sorted
+            .iter()
+            .find(|validator| validator.validator_key == public_key)
+        {
+            let state = if staking_contract
+                .current_epoch_parking
+                .contains(&validator.id)
+                || staking_contract
+                    .previous_epoch_parking
+                    .contains(&validator.id)
+            {
+                ValidatorStakingState::Parked
+            } else {
+                ValidatorStakingState::Active
+            };
+            return (Some(validator.id.clone()), state);
+        }
+
+        if let Some(validator_id) = staking_contract
+            .inactive_validators_by_id
+            .iter()
+            .find(|(_, inactive)| inactive.validator.validator_key == public_key)
+            .map(|(validator_id, _)| validator_id.clone())
+        {
+            return (Some(validator_id), ValidatorStakingState::Inactive);
+        }
+
+        (None, ValidatorStakingState::NoStake)
+    }
+
+    pub fn statistics(&self) -> BlockProductionStatistics {
+        self.statistics.read().clone()
+    }
 }
 
 impl<TNetwork: Network, TValidatorNetwork: ValidatorNetwork> Future
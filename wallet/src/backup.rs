@@ -0,0 +1,73 @@
+use beserial::{Deserialize, ReadBytesExt, Serialize, SerializeWithLength, SerializingError};
+use nimiq_hash::argon2kdf::Argon2Error;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_utils::otp::{Locked, Unlocked, Verify};
+
+use crate::wallet_account::WalletAccount;
+
+/// Accounts exported from a wallet store, together with a checksum that is verified when the
+/// backup is decrypted.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletBackup {
+    #[beserial(len_type(u16))]
+    pub accounts: Vec<WalletAccount>,
+    checksum: Blake2bHash,
+}
+
+impl WalletBackup {
+    pub fn new(accounts: Vec<WalletAccount>) -> Self {
+        let checksum = Self::compute_checksum(&accounts);
+        WalletBackup { accounts, checksum }
+    }
+
+    fn compute_checksum(accounts: &[WalletAccount]) -> Blake2bHash {
+        let mut hasher = Blake2bHasher::default();
+        SerializeWithLength::serialize::<u16, _>(accounts, &mut hasher).unwrap();
+        hasher.finish()
+    }
+}
+
+impl Verify for WalletBackup {
+    fn verify(&self) -> bool {
+        self.checksum == Self::compute_checksum(&self.accounts)
+            && self.accounts.iter().all(|account| account.verify())
+    }
+}
+
+/// The versioned file format of a wallet backup. The backup is encrypted with a password using
+/// the same Argon2 based lock as the wallet store.
+#[derive(Serialize)]
+pub struct EncryptedWalletBackup {
+    version: u8,
+    backup: Locked<WalletBackup>,
+}
+
+impl EncryptedWalletBackup {
+    pub const VERSION: u8 = 1;
+
+    /// Calling code should make sure to clear the password from memory after use.
+    pub fn new(backup: WalletBackup, password: &[u8]) -> Result<Self, Argon2Error> {
+        Ok(EncryptedWalletBackup {
+            version: Self::VERSION,
+            backup: Locked::with_defaults(backup, password)?,
+        })
+    }
+
+    /// Decrypts the backup and verifies its integrity. Returns `None` if the password is wrong or
+    /// the backup is corrupted.
+    pub fn decrypt(self, password: &[u8]) -> Option<Vec<WalletAccount>> {
+        let backup = self.backup.unlock(password).ok()?;
+        Some(Unlocked::unlocked_data(&backup).accounts.clone())
+    }
+}
+
+impl Deserialize for EncryptedWalletBackup {
+    fn deserialize<R: ReadBytesExt>(reader: &mut R) -> Result<Self, SerializingError> {
+        let version: u8 = Deserialize::deserialize(reader)?;
+        if version != Self::VERSION {
+            return Err(SerializingError::InvalidValue);
+        }
+        let backup: Locked<WalletBackup> = Deserialize::deserialize(reader)?;
+        Ok(EncryptedWalletBackup { version, backup })
+    }
+}
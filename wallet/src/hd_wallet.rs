@@ -0,0 +1,115 @@
+use beserial::{Deserialize, Serialize};
+use key_derivation::ExtendedPrivateKey;
+use keys::{Address, KeyPair, PrivateKey};
+use nimiq_mnemonic::{Mnemonic, MnemonicType, WORDLIST_EN};
+use nimiq_utils::otp::Verify;
+
+use crate::wallet_account::WalletAccount;
+
+/// A wallet restored from a mnemonic.
+pub enum MnemonicWallet {
+    /// A BIP39 mnemonic, from which accounts are derived.
+    Hd(HdWallet),
+    /// A legacy mnemonic, which encodes the private key of a single account.
+    Legacy(WalletAccount),
+}
+
+impl MnemonicWallet {
+    /// Restores the wallet from `mnemonic`. Returns `None` if the mnemonic is invalid. Mnemonics
+    /// that are valid both as BIP39 and legacy mnemonic are treated as BIP39 mnemonics.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, password: Option<&str>) -> Option<Self> {
+        match mnemonic.get_type(WORDLIST_EN) {
+            MnemonicType::BIP39 | MnemonicType::UNKNOWN => {
+                let seed = mnemonic.to_seed(password).ok()?;
+                Some(MnemonicWallet::Hd(HdWallet::from_seed(seed)))
+            }
+            MnemonicType::LEGACY => {
+                let entropy = mnemonic.to_entropy_legacy(WORDLIST_EN)?;
+                let private_key = PrivateKey::from(<[u8; 32]>::from(entropy));
+                Some(MnemonicWallet::Legacy(WalletAccount::from(KeyPair::from(
+                    private_key,
+                ))))
+            }
+            MnemonicType::INVALID => None,
+        }
+    }
+}
+
+/// The seed of a wallet imported from a BIP39 mnemonic. Accounts are derived from it along the
+/// path `m/44'/242'/0'/<index>'`, like in the Nimiq web wallet.
+///
+/// The wallet is identified by the address of its first account.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HdWallet {
+    #[beserial(len_type(u8))]
+    seed: Vec<u8>,
+    pub address: Address,
+}
+
+impl HdWallet {
+    pub const DERIVATION_PATH: &'static str = "m/44'/242'/0'";
+
+    pub fn from_seed(seed: Vec<u8>) -> Self {
+        let mut wallet = HdWallet {
+            seed,
+            address: Address::default(),
+        };
+        wallet.address = wallet.derive_account(0).address;
+        wallet
+    }
+
+    fn derive_key(&self, index: u32) -> Option<ExtendedPrivateKey> {
+        ExtendedPrivateKey::from_seed(self.seed.clone()).derive_path(&format!(
+            "{}/{}'",
+            Self::DERIVATION_PATH,
+            index
+        ))
+    }
+
+    /// Derives the account with the given `index`.
+    pub fn derive_account(&self, index: u32) -> WalletAccount {
+        let key = self.derive_key(index).expect("Account index out of range");
+        WalletAccount::from(KeyPair::from(key.into_private_key()))
+    }
+
+    /// Derives accounts until `gap_limit` consecutive accounts are unused according to `is_used`.
+    /// Returns all accounts up to the last used one, but at least the first account.
+    pub fn discover_accounts<F: Fn(&Address) -> bool>(
+        &self,
+        gap_limit: u32,
+        is_used: F,
+    ) -> Vec<WalletAccount> {
+        let mut accounts = vec![self.derive_account(0)];
+        let mut num_used = 1;
+        let mut gap = 0;
+        let mut index = 1;
+
+        if !is_used(&accounts[0].address) {
+            gap += 1;
+        }
+
+        while gap < gap_limit {
+            let account = self.derive_account(index);
+            if is_used(&account.address) {
+                gap = 0;
+                num_used = index as usize + 1;
+            } else {
+                gap += 1;
+            }
+            accounts.push(account);
+            index += 1;
+        }
+
+        accounts.truncate(num_used);
+        accounts
+    }
+}
+
+impl Verify for HdWallet {
+    fn verify(&self) -> bool {
+        // Check that the seed derives the first account.
+        self.derive_key(0)
+            .map(|key| key.to_address() == self.address)
+            .unwrap_or(false)
+    }
+}
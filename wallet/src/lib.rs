@@ -6,8 +6,14 @@ extern crate nimiq_keys as keys;
 extern crate nimiq_primitives as primitives;
 extern crate nimiq_transaction as transaction;
 
+pub use backup::{EncryptedWalletBackup, WalletBackup};
+pub use hd_wallet::{HdWallet, MnemonicWallet};
+pub use multisig_account::MultisigAccount;
 pub use wallet_account::WalletAccount;
 pub use wallet_store::WalletStore;
 
+mod backup;
+mod hd_wallet;
+mod multisig_account;
 mod wallet_account;
 mod wallet_store;
@@ -0,0 +1,184 @@
+use std::io;
+
+use beserial::{Deserialize, Serialize};
+use database::{FromDatabaseValue, IntoDatabaseValue};
+use keys::multisig::{Commitment, CommitmentPair, PartialSignature, RandomSecret};
+use keys::{Address, KeyPair, PublicKey, SecureGenerate};
+use nimiq_hash::Blake2bHasher;
+use nimiq_utils::merkle::{compute_root_from_content, Blake2bMerklePath};
+use transaction::{SignatureProof, Transaction};
+
+/// A multisig account that is controlled by `min_signatures` out of the `public_keys`.
+///
+/// Its address is the merkle root over the aggregated public keys of every possible set of
+/// `min_signatures` signers. Public keys are sorted, so that all participants derive the same
+/// address.
+///
+/// The address is only computed in [`new`] and is stored along with the public keys, since
+/// computing it requires aggregating every possible set of signers.
+///
+/// [`new`]: struct.MultisigAccount.html#method.new
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultisigAccount {
+    pub min_signatures: u8,
+    #[beserial(len_type(u8))]
+    pub public_keys: Vec<PublicKey>,
+    pub address: Address,
+}
+
+impl MultisigAccount {
+    pub const MAX_PUBLIC_KEYS: usize = 16;
+
+    pub fn new(min_signatures: u8, mut public_keys: Vec<PublicKey>) -> Option<Self> {
+        public_keys.sort();
+        public_keys.dedup();
+
+        if min_signatures == 0
+            || public_keys.len() < min_signatures as usize
+            || public_keys.len() > Self::MAX_PUBLIC_KEYS
+        {
+            return None;
+        }
+
+        let address = Address::from(compute_root_from_content::<Blake2bHasher, _>(
+            &Self::aggregated_public_keys(min_signatures, &public_keys),
+        ));
+
+        Some(MultisigAccount {
+            min_signatures,
+            public_keys,
+            address,
+        })
+    }
+
+    /// Creates a fresh commitment for a signing round. The random secret must be kept until the
+    /// partial signature has been created and must never be reused.
+    pub fn create_commitment() -> CommitmentPair {
+        CommitmentPair::generate_default_csprng()
+    }
+
+    /// Creates the partial signature of `key_pair` for `transaction`. The `signers` are the
+    /// public keys of the `min_signatures` participants in this signing round and `commitments`
+    /// are their commitments. `random_secret` belongs to our own commitment. Returns `None` if
+    /// the transaction isn't sent from this account or if the signers aren't a valid set of
+    /// participants including `key_pair`.
+    pub fn partially_sign_transaction(
+        &self,
+        key_pair: &KeyPair,
+        transaction: &Transaction,
+        signers: &[PublicKey],
+        random_secret: &RandomSecret,
+        commitments: &[Commitment],
+    ) -> Option<PartialSignature> {
+        if transaction.sender != self.address {
+            return None;
+        }
+
+        let signers = self.sorted_signers(signers)?;
+        if !signers.contains(&key_pair.public) || commitments.len() != signers.len() {
+            return None;
+        }
+
+        let (partial_signature, _, _) = key_pair.partial_sign(
+            &signers,
+            random_secret,
+            commitments,
+            transaction.serialize_content().as_slice(),
+        );
+        Some(partial_signature)
+    }
+
+    /// Combines the partial signatures of the `signers` into the signature proof for a
+    /// transaction sent from this account. Returns `None` if the signers aren't a valid set of
+    /// participants.
+    pub fn create_signature_proof(
+        &self,
+        signers: &[PublicKey],
+        commitments: &[Commitment],
+        partial_signatures: &[PartialSignature],
+    ) -> Option<SignatureProof> {
+        let signers = self.sorted_signers(signers)?;
+        if commitments.len() != signers.len() || partial_signatures.len() != signers.len() {
+            return None;
+        }
+
+        let public_key = PublicKey::aggregate(&signers);
+        let aggregated_commitment: Commitment = commitments.iter().sum();
+        let signature: PartialSignature = partial_signatures.iter().sum();
+
+        Some(SignatureProof {
+            public_key,
+            merkle_path: Blake2bMerklePath::new::<Blake2bHasher, _>(
+                &Self::aggregated_public_keys(self.min_signatures, &self.public_keys),
+                &public_key,
+            ),
+            signature: signature.to_signature(&aggregated_commitment),
+        })
+    }
+
+    /// Sorts the `signers` like the public keys of the account, so that all participants
+    /// aggregate them in the same order.
+    fn sorted_signers(&self, signers: &[PublicKey]) -> Option<Vec<PublicKey>> {
+        let mut signers = signers.to_vec();
+        signers.sort();
+        signers.dedup();
+
+        if signers.len() != self.min_signatures as usize
+            || !signers
+                .iter()
+                .all(|signer| self.public_keys.contains(signer))
+        {
+            return None;
+        }
+
+        Some(signers)
+    }
+
+    fn aggregated_public_keys(min_signatures: u8, public_keys: &[PublicKey]) -> Vec<PublicKey> {
+        let mut aggregated_public_keys: Vec<PublicKey> =
+            combinations(public_keys, min_signatures as usize)
+                .iter()
+                .map(|signers| PublicKey::aggregate(signers))
+                .collect();
+        aggregated_public_keys.sort();
+        aggregated_public_keys
+    }
+}
+
+/// Returns all combinations of `k` elements of `values`, preserving their order.
+fn combinations<T: Clone>(values: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if values.len() < k {
+        return vec![];
+    }
+
+    let mut result = Vec::new();
+    for mut combination in combinations(&values[1..], k - 1) {
+        combination.insert(0, values[0].clone());
+        result.push(combination);
+    }
+    result.extend(combinations(&values[1..], k));
+    result
+}
+
+impl IntoDatabaseValue for MultisigAccount {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for MultisigAccount {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut cursor = io::Cursor::new(bytes);
+        Ok(Deserialize::deserialize(&mut cursor)?)
+    }
+}
@@ -3,20 +3,33 @@ use database::{Database, Environment, ReadTransaction, Transaction, WriteTransac
 use keys::Address;
 use nimiq_utils::otp::Locked;
 
+use crate::hd_wallet::HdWallet;
+use crate::multisig_account::MultisigAccount;
 use crate::wallet_account::WalletAccount;
 
 #[derive(Debug)]
 pub struct WalletStore {
     env: Environment,
     wallet_db: Database,
+    multisig_db: Database,
+    seed_db: Database,
 }
 
 impl WalletStore {
     const WALLET_DB_NAME: &'static str = "Wallet";
+    const MULTISIG_DB_NAME: &'static str = "MultisigWallet";
+    const SEED_DB_NAME: &'static str = "WalletSeed";
 
     pub fn new(env: Environment) -> Self {
         let wallet_db = env.open_database(Self::WALLET_DB_NAME.to_string());
-        WalletStore { env, wallet_db }
+        let multisig_db = env.open_database(Self::MULTISIG_DB_NAME.to_string());
+        let seed_db = env.open_database(Self::SEED_DB_NAME.to_string());
+        WalletStore {
+            env,
+            wallet_db,
+            multisig_db,
+            seed_db,
+        }
     }
 
     pub fn create_read_transaction(&self) -> ReadTransaction {
@@ -68,4 +81,88 @@ impl WalletStore {
     ) {
         txn.put_reserve(&self.wallet_db, address, wallet);
     }
+
+    pub fn list_multisig(&self, txn_option: Option<&Transaction>) -> Vec<Address> {
+        let read_txn: ReadTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = ReadTransaction::new(&self.env);
+                &read_txn
+            }
+        };
+
+        let mut wallets = Vec::new();
+        let mut cursor = txn.cursor(&self.multisig_db);
+        let mut wallet: Option<(Address, MultisigAccount)> = cursor.first();
+
+        while let Some((address, _)) = wallet {
+            wallets.push(address);
+            wallet = cursor.next();
+        }
+
+        wallets
+    }
+
+    pub fn get_multisig(
+        &self,
+        address: &Address,
+        txn_option: Option<&Transaction>,
+    ) -> Option<MultisigAccount> {
+        match txn_option {
+            Some(txn) => txn.get(&self.multisig_db, address),
+            None => ReadTransaction::new(&self.env).get(&self.multisig_db, address),
+        }
+    }
+
+    pub fn put_multisig(
+        &self,
+        address: &Address,
+        wallet: &MultisigAccount,
+        txn: &mut WriteTransaction,
+    ) {
+        txn.put_reserve(&self.multisig_db, address, wallet);
+    }
+
+    pub fn list_hd_wallets(&self, txn_option: Option<&Transaction>) -> Vec<Address> {
+        let read_txn: ReadTransaction;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = ReadTransaction::new(&self.env);
+                &read_txn
+            }
+        };
+
+        let mut wallets = Vec::new();
+        let mut cursor = txn.cursor(&self.seed_db);
+        let mut wallet: Option<(Address, Locked<HdWallet>)> = cursor.first();
+
+        while let Some((address, _)) = wallet {
+            wallets.push(address);
+            wallet = cursor.next();
+        }
+
+        wallets
+    }
+
+    pub fn get_hd_wallet(
+        &self,
+        address: &Address,
+        txn_option: Option<&Transaction>,
+    ) -> Option<Locked<HdWallet>> {
+        match txn_option {
+            Some(txn) => txn.get(&self.seed_db, address),
+            None => ReadTransaction::new(&self.env).get(&self.seed_db, address),
+        }
+    }
+
+    pub fn put_hd_wallet(
+        &self,
+        address: &Address,
+        wallet: &Locked<HdWallet>,
+        txn: &mut WriteTransaction,
+    ) {
+        txn.put_reserve(&self.seed_db, address, wallet);
+    }
 }
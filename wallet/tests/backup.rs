@@ -0,0 +1,47 @@
+extern crate beserial;
+extern crate nimiq_wallet as wallet;
+
+use beserial::{Deserialize, Serialize};
+use wallet::{EncryptedWalletBackup, WalletAccount, WalletBackup};
+
+fn backup() -> (Vec<WalletAccount>, Vec<u8>) {
+    let accounts = vec![WalletAccount::generate(), WalletAccount::generate()];
+    let backup = EncryptedWalletBackup::new(WalletBackup::new(accounts.clone()), b"password")
+        .unwrap()
+        .serialize_to_vec();
+    (accounts, backup)
+}
+
+#[test]
+fn test_export_import() {
+    let (accounts, backup) = backup();
+
+    let backup = EncryptedWalletBackup::deserialize_from_vec(&backup).unwrap();
+    assert_eq!(backup.decrypt(b"password"), Some(accounts));
+}
+
+#[test]
+fn test_wrong_password() {
+    let (_, backup) = backup();
+
+    let backup = EncryptedWalletBackup::deserialize_from_vec(&backup).unwrap();
+    assert_eq!(backup.decrypt(b"wrong password"), None);
+}
+
+#[test]
+fn test_corrupted_backup() {
+    let (_, mut backup) = backup();
+
+    // Flip a bit of the encrypted accounts.
+    backup[10] ^= 1;
+    let backup = EncryptedWalletBackup::deserialize_from_vec(&backup).unwrap();
+    assert_eq!(backup.decrypt(b"password"), None);
+}
+
+#[test]
+fn test_unsupported_version() {
+    let (_, mut backup) = backup();
+
+    backup[0] = EncryptedWalletBackup::VERSION + 1;
+    assert!(EncryptedWalletBackup::deserialize_from_vec(&backup).is_err());
+}
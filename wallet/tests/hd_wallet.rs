@@ -0,0 +1,69 @@
+extern crate beserial;
+extern crate nimiq_mnemonic as mnemonic;
+extern crate nimiq_utils as utils;
+extern crate nimiq_wallet as wallet;
+
+use beserial::{Deserialize, Serialize};
+use mnemonic::Mnemonic;
+use utils::otp::Verify;
+use wallet::{HdWallet, MnemonicWallet};
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+fn hd_wallet() -> HdWallet {
+    match MnemonicWallet::from_mnemonic(&Mnemonic::from(MNEMONIC), None).unwrap() {
+        MnemonicWallet::Hd(hd_wallet) => hd_wallet,
+        MnemonicWallet::Legacy(_) => panic!("Expected a BIP39 mnemonic"),
+    }
+}
+
+#[test]
+fn test_invalid_mnemonic() {
+    let mnemonic = Mnemonic::from("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon nimiq");
+    assert!(MnemonicWallet::from_mnemonic(&mnemonic, None).is_none());
+}
+
+#[test]
+fn test_derive_accounts() {
+    let hd_wallet = hd_wallet();
+
+    assert_eq!(hd_wallet.derive_account(0).address, hd_wallet.address);
+    assert_ne!(
+        hd_wallet.derive_account(0).address,
+        hd_wallet.derive_account(1).address
+    );
+    assert_eq!(
+        hd_wallet.derive_account(1).address,
+        hd_wallet.derive_account(1).address
+    );
+}
+
+#[test]
+fn test_serialize_deserialize() {
+    let hd_wallet = hd_wallet();
+
+    let deserialized = HdWallet::deserialize_from_vec(&hd_wallet.serialize_to_vec()).unwrap();
+    assert_eq!(hd_wallet, deserialized);
+    assert!(deserialized.verify());
+    assert!(!HdWallet::default().verify());
+}
+
+#[test]
+fn test_discover_accounts() {
+    let hd_wallet = hd_wallet();
+
+    // Without any used accounts only the first account is returned.
+    let accounts = hd_wallet.discover_accounts(5, |_| false);
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].address, hd_wallet.address);
+
+    // Accounts within the gap limit are discovered, accounts after a larger gap aren't.
+    let used = vec![
+        hd_wallet.derive_account(3).address,
+        hd_wallet.derive_account(6).address,
+        hd_wallet.derive_account(12).address,
+    ];
+    let accounts = hd_wallet.discover_accounts(5, |address| used.contains(address));
+    assert_eq!(accounts.len(), 7);
+    assert_eq!(accounts[6].address, used[1]);
+}
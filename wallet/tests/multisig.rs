@@ -0,0 +1,135 @@
+extern crate beserial;
+extern crate nimiq_keys as keys;
+extern crate nimiq_primitives as primitives;
+extern crate nimiq_transaction as transaction;
+extern crate nimiq_wallet as wallet;
+
+use beserial::{Deserialize, Serialize};
+use keys::{Address, KeyPair, PublicKey, SecureGenerate};
+use primitives::coin::Coin;
+use primitives::networks::NetworkId;
+use transaction::Transaction;
+use wallet::MultisigAccount;
+
+fn key_pairs(n: usize) -> Vec<KeyPair> {
+    (0..n).map(|_| KeyPair::generate_default_csprng()).collect()
+}
+
+#[test]
+fn test_address_does_not_depend_on_key_order() {
+    let key_pairs = key_pairs(3);
+    let mut public_keys: Vec<PublicKey> = key_pairs.iter().map(|kp| kp.public).collect();
+
+    let account = MultisigAccount::new(2, public_keys.clone()).unwrap();
+    public_keys.reverse();
+    let reversed = MultisigAccount::new(2, public_keys).unwrap();
+
+    assert_eq!(account.address, reversed.address);
+}
+
+#[test]
+fn test_invalid_threshold() {
+    let public_keys: Vec<PublicKey> = key_pairs(3).iter().map(|kp| kp.public).collect();
+
+    assert!(MultisigAccount::new(0, public_keys.clone()).is_none());
+    assert!(MultisigAccount::new(4, public_keys).is_none());
+}
+
+#[test]
+fn test_serialize_deserialize() {
+    let public_keys: Vec<PublicKey> = key_pairs(3).iter().map(|kp| kp.public).collect();
+    let account = MultisigAccount::new(2, public_keys).unwrap();
+
+    let deserialized = MultisigAccount::deserialize_from_vec(&account.serialize_to_vec()).unwrap();
+    assert_eq!(account, deserialized);
+}
+
+#[test]
+fn test_sign_transaction() {
+    let key_pairs = key_pairs(3);
+    let public_keys: Vec<PublicKey> = key_pairs.iter().map(|kp| kp.public).collect();
+    let account = MultisigAccount::new(2, public_keys).unwrap();
+
+    let mut transaction = Transaction::new_basic(
+        account.address.clone(),
+        Address::from_user_friendly_address("NQ16 C3HR 85U8 P7MK F52R E9RG SA3Y Q69C X563")
+            .unwrap(),
+        Coin::from_u64_unchecked(42),
+        Coin::ZERO,
+        0,
+        NetworkId::Main,
+    );
+
+    // The signers are passed in a different order than the account's keys.
+    let signers = vec![key_pairs[2].public, key_pairs[0].public];
+    let commitment_pairs = vec![
+        MultisigAccount::create_commitment(),
+        MultisigAccount::create_commitment(),
+    ];
+    let commitments: Vec<_> = commitment_pairs
+        .iter()
+        .map(|pair| *pair.commitment())
+        .collect();
+
+    let partial_signatures: Vec<_> = [&key_pairs[2], &key_pairs[0]]
+        .iter()
+        .zip(commitment_pairs.iter())
+        .map(|(key_pair, commitment_pair)| {
+            account
+                .partially_sign_transaction(
+                    key_pair,
+                    &transaction,
+                    &signers,
+                    commitment_pair.random_secret(),
+                    &commitments,
+                )
+                .unwrap()
+        })
+        .collect();
+
+    let proof = account
+        .create_signature_proof(&signers, &commitments, &partial_signatures)
+        .unwrap();
+    assert!(proof.is_signed_by(&account.address));
+
+    transaction.proof = proof.serialize_to_vec();
+    assert_eq!(Ok(()), transaction.verify(NetworkId::Main));
+}
+
+#[test]
+fn test_non_participant_cannot_sign() {
+    let key_pairs = key_pairs(3);
+    let public_keys: Vec<PublicKey> = key_pairs[..2].iter().map(|kp| kp.public).collect();
+    let account = MultisigAccount::new(2, public_keys).unwrap();
+
+    let signers = vec![key_pairs[0].public, key_pairs[2].public];
+    let proof = account.create_signature_proof(&signers, &[], &[]);
+    assert!(proof.is_none());
+}
+
+#[test]
+fn test_cannot_sign_transaction_from_other_sender() {
+    let key_pairs = key_pairs(2);
+    let public_keys: Vec<PublicKey> = key_pairs.iter().map(|kp| kp.public).collect();
+    let account = MultisigAccount::new(1, public_keys).unwrap();
+
+    let transaction = Transaction::new_basic(
+        Address::from(&key_pairs[0].public),
+        account.address.clone(),
+        Coin::from_u64_unchecked(42),
+        Coin::ZERO,
+        0,
+        NetworkId::Main,
+    );
+
+    let signers = vec![key_pairs[0].public];
+    let commitment_pair = MultisigAccount::create_commitment();
+    let partial_signature = account.partially_sign_transaction(
+        &key_pairs[0],
+        &transaction,
+        &signers,
+        commitment_pair.random_secret(),
+        &[*commitment_pair.commitment()],
+    );
+    assert!(partial_signature.is_none());
+}